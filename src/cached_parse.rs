@@ -0,0 +1,141 @@
+use std::{cell::RefCell, ops::Range};
+
+use crate::{
+    expression::Expression,
+    parse::{parse_groups, Groups, ParseError, ParserOptions},
+};
+
+/// An edit replacing a byte range of the input with new text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    range: Range<usize>,
+    replacement: String,
+}
+
+impl TextEdit {
+    /// Construct a new text edit
+    pub fn new(range: Range<usize>, replacement: impl Into<String>) -> Self {
+        Self {
+            range,
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Get the byte range replaced by this edit
+    pub fn get_range(&self) -> &Range<usize> {
+        &self.range
+    }
+
+    /// Get the text inserted by this edit
+    pub fn get_replacement(&self) -> &str {
+        &self.replacement
+    }
+}
+
+/// A parse of some input that caches the expressions built from it as the input is edited
+///
+/// Every edit runs the grammar over the whole input again, since whether a group's brackets even
+/// form a group depends on its surroundings, like `{1}` in `1\frac{1}{2}`. What's cached is the
+/// building of the expression: groups away from the edit are moved along and reused whole, so
+/// only the groups containing the edit are built again.
+#[derive(Debug)]
+pub struct CachedParse {
+    input: String,
+    options: ParserOptions,
+    groups: RefCell<Groups>,
+    result: Result<Box<dyn Expression>, ParseError>,
+}
+
+impl CachedParse {
+    /// Parse some input, caching its built groups for later edits
+    pub fn new(input: impl Into<String>, options: ParserOptions) -> Self {
+        let input = input.into();
+        let groups = RefCell::new(Groups::new());
+        let result = parse_groups(&input, &options, Some(&groups));
+        Self {
+            input,
+            options,
+            groups,
+            result,
+        }
+    }
+
+    /// Get the current input
+    pub fn get_input(&self) -> &str {
+        &self.input
+    }
+
+    /// Get the expression parsed from the current input, or the error it failed with
+    pub fn get_result(&self) -> Result<&dyn Expression, &ParseError> {
+        self.result.as_ref().map(|expression| expression.as_ref())
+    }
+
+    /// Apply an edit to the input and parse it again, reusing the built groups away from the edit
+    ///
+    /// Panics if the range of the edit isn't within the input on character boundaries
+    pub fn apply(&mut self, edit: &TextEdit) -> Result<&dyn Expression, &ParseError> {
+        let range = edit.get_range().clone();
+        self.input
+            .replace_range(range.clone(), edit.get_replacement());
+
+        // Drop the groups touched by the edit and shift the ones after it
+        let shift = |position: usize| position + edit.get_replacement().len() - range.len();
+        let groups = self.groups.take();
+        self.groups = RefCell::new(
+            groups
+                .into_iter()
+                .filter_map(|(group, (mut expression, height))| {
+                    if group.end <= range.start {
+                        Some((group, (expression, height)))
+                    } else if group.start >= range.end {
+                        expression.relocate(group.start, shift(group.start));
+                        Some((shift(group.start)..shift(group.end), (expression, height)))
+                    } else {
+                        None
+                    }
+                })
+                .collect(),
+        );
+
+        self.result = parse_groups(&self.input, &self.options, Some(&self.groups));
+        self.get_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{parse, ParserOptions};
+
+    use super::{CachedParse, TextEdit};
+
+    #[test]
+    fn edits() {
+        let mut parse_result = CachedParse::new("(1 + 2) \\cdot (x + 3)", ParserOptions::default());
+        assert!(parse_result.groups.borrow().contains_key(&(14..21)));
+
+        // Lengthening the first group shifts the second one along
+        parse_result.apply(&TextEdit::new(1..2, "10")).unwrap();
+        assert_eq!(parse_result.get_input(), "(10 + 2) \\cdot (x + 3)");
+        assert!(parse_result.groups.borrow().contains_key(&(15..22)));
+        assert_eq!(
+            parse_result.groups.borrow()[&(15..22)].0.get_span(),
+            Some(15..22)
+        );
+        assert_eq!(
+            parse_result.get_result().unwrap().to_string(),
+            parse(parse_result.get_input(), &ParserOptions::default())
+                .unwrap()
+                .to_string()
+        );
+
+        // Errors are reported until the input is valid again
+        assert!(parse_result.apply(&TextEdit::new(20..21, "")).is_err());
+        let expression = parse_result.apply(&TextEdit::new(20..20, "4")).unwrap();
+        assert_eq!(expression.simplified().to_string(), "(12 * (x + 4))");
+
+        // Editing a group can change what its surroundings parse as
+        let mut parse_result = CachedParse::new("1\\frac{x}{2}", ParserOptions::default());
+        let expression = parse_result.apply(&TextEdit::new(7..8, "1")).unwrap();
+        assert_eq!(expression.simplified().to_string(), "3/2");
+    }
+}
@@ -0,0 +1,302 @@
+use std::ops::Range;
+
+use crate::{
+    expression::{Bindings, Expression},
+    parse::{parse, ParseError, ParserOptions},
+    value::UndefinedValue,
+};
+
+/// Replacements for notation that isn't accepted, tried in order when suggesting a fix
+const REPLACEMENTS: [(&str, &str); 4] = [
+    ("**", " ^ "),
+    ("<=", " \\le "),
+    (">=", " \\ge "),
+    ("!=", " \\ne "),
+];
+
+/// A problem with some input, located by a byte range of it
+///
+/// This holds the structured data of the problem, for frontends to display however they like
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    message: String,
+    span: Range<usize>,
+    suggestion: Option<String>,
+    expected: Vec<String>,
+}
+
+impl Diagnostic {
+    /// Construct a new diagnostic
+    pub fn new(message: impl Into<String>, span: Range<usize>) -> Self {
+        Self {
+            message: message.into(),
+            span,
+            suggestion: None,
+            expected: Vec::new(),
+        }
+    }
+
+    /// Attach a suggested fix to this diagnostic
+    pub fn with_suggestion(self, suggestion: impl Into<String>) -> Self {
+        Self {
+            suggestion: Some(suggestion.into()),
+            ..self
+        }
+    }
+
+    /// Construct a diagnostic for an error from parsing some input
+    pub fn from_parse_error(input: &str, error: &ParseError) -> Self {
+        let span = error.get_span();
+        let diagnostic = match error {
+            ParseError::Syntax(_) => {
+                let start = span.start.min(input.len());
+                let diagnostic = match (error.get_custom_message(), input[start..].chars().next()) {
+                    (Some(message), _) => Self::new(message, span),
+                    (None, Some(character)) => Self::new(
+                        format!("unexpected `{}`", character),
+                        start..start + character.len_utf8(),
+                    ),
+                    (None, None) => Self::new("unexpected end of input", start..start),
+                };
+                match suggest(input) {
+                    Some(suggestion) => {
+                        diagnostic.with_suggestion(format!("did you mean `{}`?", suggestion))
+                    }
+                    None => diagnostic,
+                }
+            }
+            ParseError::TooLong { max_length, .. } => {
+                Self::new(format!("input is longer than {} bytes", max_length), span)
+            }
+            ParseError::TooDeep { max_depth, .. } => Self::new(
+                format!("expression nests deeper than {} levels", max_depth),
+                span,
+            ),
+            ParseError::UnknownOperator { name, .. } => {
+                Self::new(format!("unknown operator `{}`", name), span)
+            }
+            ParseError::RaggedMatrix {
+                columns, expected, ..
+            } => Self::new(
+                format!(
+                    "matrix row has {} columns, but {} were expected",
+                    columns, expected
+                ),
+                span,
+            ),
+        };
+        Self {
+            expected: error.get_expected().into_iter().map(String::from).collect(),
+            ..diagnostic
+        }
+    }
+
+    /// Construct a diagnostic locating the innermost part of an expression that is undefined
+    ///
+    /// Returns `None` if the expression is defined, or the undefined part wasn't parsed
+    pub fn undefined(expression: &dyn Expression, bindings: &Bindings) -> Option<Self> {
+        if !expression.evaluate(bindings).is::<UndefinedValue>() {
+            return None;
+        }
+        let innermost = expression
+            .get_children()
+            .into_iter()
+            .find_map(|child| Self::undefined(child, bindings));
+        innermost.or_else(|| {
+            expression
+                .get_span()
+                .map(|span| Self::new("expression is undefined", span))
+        })
+    }
+
+    /// Get the message of this diagnostic
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+
+    /// Get the byte range of the input this diagnostic points at
+    pub fn get_span(&self) -> &Range<usize> {
+        &self.span
+    }
+
+    /// Get the suggested fix of this diagnostic, if there is one
+    pub fn get_suggestion(&self) -> Option<&str> {
+        self.suggestion.as_deref()
+    }
+
+    /// Get what was expected instead of the input this diagnostic points at
+    pub fn get_expected(&self) -> &[String] {
+        &self.expected
+    }
+
+    /// Get the line and column, both starting at one, where this diagnostic starts in the input
+    pub fn get_location(&self, input: &str) -> (usize, usize) {
+        let before = &input[..self.get_bounded_span(input).start];
+        let line = before.matches('\n').count() + 1;
+        let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+        (line, column)
+    }
+
+    /// Get the span of this diagnostic, moved inside the input and onto character boundaries
+    fn get_bounded_span(&self, input: &str) -> Range<usize> {
+        let start = input.floor_char_boundary(self.span.start);
+        let end = input.ceil_char_boundary(self.span.end.clamp(start, input.len()));
+        start..end
+    }
+
+    /// Render this diagnostic under the line of the input it points at, with ANSI colors if requested
+    pub fn render(&self, input: &str, ansi: bool) -> String {
+        let paint = |code: &str, text: &str| {
+            if ansi {
+                format!("\x1b[{}m{}\x1b[0m", code, text)
+            } else {
+                text.to_string()
+            }
+        };
+        let (line, column) = self.get_location(input);
+        let source = input.lines().nth(line - 1).unwrap_or("");
+        let gutter = " ".repeat(line.to_string().len());
+
+        // Underline the span, at least one character wide and cut off at the end of the line
+        let span = self.get_bounded_span(input);
+        let width = input[span]
+            .lines()
+            .next()
+            .map_or(0, |text| text.chars().count())
+            .max(1);
+
+        let mut rendered = format!(
+            "{}: {}\n{}{} {}:{}\n{} {}\n{} {} {}\n{} {} {}{}\n",
+            paint("1;31", "error"),
+            paint("1", &self.message),
+            gutter,
+            paint("1;34", "-->"),
+            line,
+            column,
+            gutter,
+            paint("1;34", "|"),
+            paint("1;34", &line.to_string()),
+            paint("1;34", "|"),
+            source,
+            gutter,
+            paint("1;34", "|"),
+            " ".repeat(column - 1),
+            paint("1;31", &"^".repeat(width)),
+        );
+        if let Some((last, rest)) = self.expected.split_last() {
+            let expected = if rest.is_empty() {
+                last.clone()
+            } else {
+                format!("{} or {}", rest.join(", "), last)
+            };
+            rendered.push_str(&format!(
+                "{} {} {}\n",
+                gutter,
+                paint("1;34", "="),
+                paint("1", &format!("note: expected {}", expected))
+            ));
+        }
+        if let Some(suggestion) = &self.suggestion {
+            rendered.push_str(&format!(
+                "{} {} {}\n",
+                gutter,
+                paint("1;34", "="),
+                paint("1", &format!("help: {}", suggestion))
+            ));
+        }
+        rendered
+    }
+}
+
+/// Suggest a corrected version of some input that parses
+fn suggest(input: &str) -> Option<String> {
+    let options = ParserOptions::default();
+    let mut candidate = input.to_string();
+    for (from, to) in REPLACEMENTS {
+        candidate = candidate.replace(from, to);
+    }
+
+    // Close any brackets left open
+    let mut closing = Vec::new();
+    for character in candidate.chars() {
+        match character {
+            '(' => closing.push(')'),
+            '{' => closing.push('}'),
+            ')' | '}' => {
+                closing.pop();
+            }
+            _ => {}
+        }
+    }
+    candidate.extend(closing.into_iter().rev());
+
+    let candidate = candidate.split_whitespace().collect::<Vec<_>>().join(" ");
+    (candidate != input && parse(&candidate, &options).is_ok()).then_some(candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::Bindings,
+        parse::{parse, ParserOptions, DEFAULT_MAX_LENGTH},
+        value::RationalValue,
+    };
+
+    use super::Diagnostic;
+
+    #[test]
+    fn parse_errors() {
+        let input = "1 <= (2 + x";
+        let error = parse(input, &ParserOptions::default()).unwrap_err();
+        let diagnostic = Diagnostic::from_parse_error(input, &error);
+        assert_eq!(diagnostic.get_message(), "unexpected `=`");
+        assert_eq!(diagnostic.get_location(input), (1, 4));
+        assert_eq!(
+            diagnostic.get_suggestion(),
+            Some("did you mean `1 \\le (2 + x)`?")
+        );
+        assert_eq!(
+            diagnostic.render(input, false),
+            "error: unexpected `=`\n --> 1:4\n  |\n1 | 1 <= (2 + x\n  |    ^\n  = note: expected an expression\n  = help: did you mean `1 \\le (2 + x)`?\n"
+        );
+        assert_eq!(error.get_span(), 3..3);
+        assert_eq!(error.get_token(), Some('='));
+        assert_eq!(error.render(input), diagnostic.render(input, false));
+
+        // Errors raised while building the expression keep their message
+        let input = "1e99999";
+        let error = parse(input, &ParserOptions::default()).unwrap_err();
+        let diagnostic = Diagnostic::from_parse_error(input, &error);
+        assert!(diagnostic.get_message().contains("exponent"));
+        assert!(diagnostic.get_expected().is_empty());
+
+        let input = "\\frac{1}";
+        let error = parse(input, &ParserOptions::default()).unwrap_err();
+        assert_eq!(error.get_token(), None);
+        assert_eq!(error.get_expected(), ["an expression"]);
+    }
+
+    #[test]
+    fn long_input_with_multibyte_character() {
+        // The byte at the maximum length falls inside `÷`
+        let input = format!("{}\u{f7}", "1".repeat(DEFAULT_MAX_LENGTH - 1));
+        let error = parse(&input, &ParserOptions::default()).unwrap_err();
+        let diagnostic = Diagnostic::from_parse_error(&input, &error);
+        assert_eq!(diagnostic.get_location(&input), (1, DEFAULT_MAX_LENGTH));
+        assert!(error
+            .render(&input)
+            .ends_with(&format!("{}^\n", " ".repeat(DEFAULT_MAX_LENGTH - 1))));
+    }
+
+    #[test]
+    fn undefined_subexpression() {
+        let expression = parse("x + 1 / (x - 2)", &ParserOptions::default()).unwrap();
+        let mut bindings = Bindings::new();
+        bindings.insert(
+            "x".to_string(),
+            Box::new("2".parse::<RationalValue>().unwrap()),
+        );
+        let diagnostic = Diagnostic::undefined(expression.as_ref(), &bindings).unwrap();
+        assert_eq!(diagnostic.get_span(), &(4..15));
+    }
+}
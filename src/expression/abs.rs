@@ -0,0 +1,129 @@
+use std::ops::Range;
+
+use crate::{
+    format::FormatOptions,
+    trace,
+    value::{ConstantValue, FloatValue, IntervalUnionValue, RationalValue, UndefinedValue, Value},
+};
+
+use super::{Bindings, Expression, NegationExpression, ValueExpression};
+
+/// An absolute value expression
+#[derive(Debug, Clone)]
+pub struct AbsExpression {
+    argument: Box<dyn Expression>,
+    span: Option<Range<usize>>,
+}
+
+impl AbsExpression {
+    pub fn new(argument: Box<dyn Expression>) -> Self {
+        Self {
+            argument,
+            span: None,
+        }
+    }
+
+    pub fn get_argument(&self) -> &dyn Expression {
+        self.argument.as_ref()
+    }
+}
+
+/// Get the absolute value of a value, if it is numeric
+fn absolute(value: &dyn Value) -> Option<Box<dyn Value>> {
+    if let Some(value) = value.downcast_ref::<RationalValue>() {
+        Some(Box::new(value.get_absolute()))
+    } else if let Some(value) = value.downcast_ref::<ConstantValue>() {
+        // Constants may be negative, so flip the coefficient to match their sign
+        let negative = value.get_constant().expansion().0.is_negative();
+        let coefficient = value.get_coefficient();
+        Some(Box::new(ConstantValue::new(
+            if coefficient.is_negative() != negative {
+                coefficient.get_opposite()
+            } else {
+                coefficient.clone()
+            },
+            value.get_constant().clone(),
+        )))
+    } else {
+        value
+            .downcast_ref::<FloatValue>()
+            .map(|value| Box::new(FloatValue::new(value.get_value().abs())) as Box<dyn Value>)
+    }
+}
+
+impl Expression for AbsExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", name = "simplify", skip_all, fields(node = "abs"))
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        let argument = self.argument.simplified();
+
+        // Fold the absolute value of a numeric value
+        if let Some(value) = argument
+            .downcast_ref::<ValueExpression>()
+            .and_then(|argument| absolute(argument.get_value()))
+        {
+            trace::rule("abs", "fold value");
+            return Box::new(ValueExpression::new(value));
+        }
+
+        // Negating the argument or taking its absolute value again changes nothing
+        if let Some(negation) = argument.downcast_ref::<NegationExpression>() {
+            trace::rule("abs", "drop negation");
+            return AbsExpression::new(dyn_clone::clone_box(negation.get_argument())).simplified();
+        }
+        if argument.is::<AbsExpression>() {
+            trace::rule("abs", "nested absolute value");
+            return argument;
+        }
+        Box::new(AbsExpression::new(argument))
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        absolute(self.argument.evaluate(bindings).as_ref())
+            .unwrap_or_else(|| Box::new(UndefinedValue::new()))
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        self.argument.domain(variable)
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        vec![self.argument.as_ref()]
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        vec![&mut self.argument]
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        format!("|{}|", self.argument.format(options))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{parse, ParserOptions};
+
+    #[test]
+    fn absolute_values() {
+        let options = ParserOptions::default();
+        let simplified = |input: &str| parse(input, &options).unwrap().simplified().to_string();
+        assert_eq!(simplified("|3/4 - 3/2|"), "3/4");
+        assert_eq!(simplified("\\lvert \\pi - 2\\pi \\rvert"), "\\pi");
+        assert_eq!(simplified("2|x| + \\left| 1 \\right|"), "((2 * |x|) + 1)");
+        assert_eq!(simplified("|2||0 - 3|"), "6");
+        assert_eq!(simplified("|-(x + 1)|"), "|(x + 1)|");
+        assert_eq!(simplified("||-y||"), "|y|");
+        assert_eq!(simplified("|x|^2 + |x|^3"), "((x ^ 2) + (|x| ^ 3))");
+    }
+}
@@ -1,36 +1,121 @@
-use super::{Expression, ValueExpression};
+use std::ops::Range;
+
+use crate::{
+    format::FormatOptions,
+    trace,
+    value::{IntervalUnionValue, Value},
+};
+
+use super::{
+    is_exact_combination, matrix, sum, Bindings, Expression, MatrixExpression, ValueExpression,
+};
 
 /// An addition expression
 #[derive(Debug, Clone)]
 pub struct AdditionExpression {
     lhs: Box<dyn Expression>,
     rhs: Box<dyn Expression>,
+    span: Option<Range<usize>>,
 }
 
 impl AdditionExpression {
     pub fn new(lhs: Box<dyn Expression>, rhs: Box<dyn Expression>) -> Self {
-        Self { lhs, rhs }
+        Self {
+            lhs,
+            rhs,
+            span: None,
+        }
+    }
+
+    pub fn get_lhs(&self) -> &dyn Expression {
+        self.lhs.as_ref()
+    }
+
+    pub fn get_rhs(&self) -> &dyn Expression {
+        self.rhs.as_ref()
     }
 }
 
 impl Expression for AdditionExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "addition")
+        )
+    )]
     fn simplified(&self) -> Box<dyn Expression> {
         // Simplify both sides
         let lhs = self.lhs.simplified();
         let rhs = self.rhs.simplified();
 
-        // Combine if two values
-        if let (Some(lhs), Some(rhs)) = (
+        // Combine if two values with an exact result
+        if let (Some(lhs_value), Some(rhs_value)) = (
             lhs.downcast_ref::<ValueExpression>(),
             rhs.downcast_ref::<ValueExpression>(),
         ) {
-            Box::new(ValueExpression::new(lhs.get_value().add(rhs.get_value())))
-        } else {
-            Box::new(AdditionExpression::new(lhs, rhs))
+            let value = lhs_value.get_value().add(rhs_value.get_value());
+            if is_exact_combination(value.as_ref(), lhs_value.get_value(), rhs_value.get_value()) {
+                trace::rule("addition", "combine values");
+                return Box::new(ValueExpression::new(value));
+            }
+        }
+
+        // Add matrices of values entry by entry
+        if let (Some(lhs_matrix), Some(rhs_matrix)) = (
+            lhs.downcast_ref::<MatrixExpression>(),
+            rhs.downcast_ref::<MatrixExpression>(),
+        ) {
+            if let Some(sum) = matrix::fold_sum(lhs_matrix, rhs_matrix) {
+                trace::rule("addition", "add matrices");
+                return sum;
+            }
+        }
+
+        // Flatten nested sums and collect like terms
+        if !lhs.is::<ValueExpression>() || !rhs.is::<ValueExpression>() {
+            return sum::combine(vec![(lhs, false), (rhs, false)]);
         }
+        Box::new(AdditionExpression::new(lhs, rhs))
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        self.lhs
+            .evaluate(bindings)
+            .add(self.rhs.evaluate(bindings).as_ref())
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        Some(
+            self.lhs
+                .domain(variable)?
+                .intersection(&self.rhs.domain(variable)?),
+        )
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        vec![self.lhs.as_ref(), self.rhs.as_ref()]
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        vec![&mut self.lhs, &mut self.rhs]
     }
 
-    fn to_string(&self) -> String {
-        format!("({} + {})", self.lhs.to_string(), self.rhs.to_string())
+    fn format(&self, options: &FormatOptions) -> String {
+        format!(
+            "({} + {})",
+            self.lhs.format(options),
+            self.rhs.format(options)
+        )
     }
 }
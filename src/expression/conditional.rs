@@ -0,0 +1,199 @@
+use std::ops::Range;
+
+use crate::{
+    format::FormatOptions,
+    trace,
+    value::{BooleanValue, IntervalUnionValue, UndefinedValue, Value},
+};
+
+use super::{Bindings, Expression, PiecewiseExpression, ValueExpression};
+
+/// An expression taking one of two branches by a condition, like `\text{if } c \text{ then } a \text{ else } b`
+#[derive(Debug, Clone)]
+pub struct ConditionalExpression {
+    condition: Box<dyn Expression>,
+    then: Box<dyn Expression>,
+    otherwise: Box<dyn Expression>,
+    span: Option<Range<usize>>,
+}
+
+impl ConditionalExpression {
+    pub fn new(
+        condition: Box<dyn Expression>,
+        then: Box<dyn Expression>,
+        otherwise: Box<dyn Expression>,
+    ) -> Self {
+        Self {
+            condition,
+            then,
+            otherwise,
+            span: None,
+        }
+    }
+
+    pub fn get_condition(&self) -> &dyn Expression {
+        self.condition.as_ref()
+    }
+
+    pub fn get_then(&self) -> &dyn Expression {
+        self.then.as_ref()
+    }
+
+    pub fn get_otherwise(&self) -> &dyn Expression {
+        self.otherwise.as_ref()
+    }
+
+    /// Write this conditional as a piecewise expression with two branches
+    pub fn to_piecewise(&self) -> PiecewiseExpression {
+        PiecewiseExpression::new(vec![
+            (self.then.clone(), Some(self.condition.clone())),
+            (self.otherwise.clone(), None),
+        ])
+    }
+}
+
+impl Expression for ConditionalExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "conditional")
+        )
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        let condition = self.condition.simplified();
+
+        // Take a branch if the condition is decided
+        let holds = condition
+            .downcast_ref::<ValueExpression>()
+            .and_then(|condition| condition.get_value().downcast_ref::<BooleanValue>())
+            .map(BooleanValue::get_value);
+        match holds {
+            Some(true) => {
+                trace::rule("conditional", "take then branch");
+                return self.then.simplified();
+            }
+            Some(false) => {
+                trace::rule("conditional", "take else branch");
+                return self.otherwise.simplified();
+            }
+            None => {}
+        }
+
+        // The condition doesn't matter if both branches are the same
+        let then = self.then.simplified();
+        let otherwise = self.otherwise.simplified();
+        if then.to_string() == otherwise.to_string() {
+            trace::rule("conditional", "identical branches");
+            return then;
+        }
+        Box::new(ConditionalExpression::new(condition, then, otherwise))
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        match self
+            .condition
+            .evaluate(bindings)
+            .downcast_ref::<BooleanValue>()
+        {
+            Some(holds) if holds.get_value() => self.then.evaluate(bindings),
+            Some(_) => self.otherwise.evaluate(bindings),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn domain(&self, _variable: &str) -> Option<IntervalUnionValue> {
+        // Each branch only needs to be defined where it is taken, like a piecewise expression
+        None
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        vec![
+            self.condition.as_ref(),
+            self.then.as_ref(),
+            self.otherwise.as_ref(),
+        ]
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        vec![&mut self.condition, &mut self.then, &mut self.otherwise]
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        format!(
+            "\\text{{if }} {} \\text{{ then }} {} \\text{{ else }} {}",
+            self.condition.format(options),
+            self.then.format(options),
+            self.otherwise.format(options)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::{Bindings, Expression},
+        parse::{parse, ParserOptions},
+        value::{FloatValue, RationalValue},
+    };
+
+    use super::ConditionalExpression;
+
+    #[test]
+    fn conditionals() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap();
+        let simplified = |input: &str| parsed(input).simplified().to_string();
+        assert_eq!(
+            simplified("\\text{if } 1 < 2 \\text{ then } x + x \\text{ else } y"),
+            "(2 * x)"
+        );
+        assert_eq!(
+            simplified("\\text{if } 2 < 1 \\text{ then } x \\text{ else } y"),
+            "y"
+        );
+        assert_eq!(
+            simplified("\\text{if } y > 0 \\text{ then } 2x \\text{ else } x + x"),
+            "(2 * x)"
+        );
+
+        // Printed conditionals parse back to the same conditional
+        let expression = parsed("\\text{if } x \\ge 0 \\text{ then } x \\text{ else } -x");
+        assert!(expression.downcast_ref::<ConditionalExpression>().is_some());
+        assert_eq!(
+            parsed(&expression.to_string()).to_string(),
+            expression.to_string()
+        );
+
+        let at = |x: i32| {
+            let mut bindings = Bindings::new();
+            bindings.insert(
+                "x".to_string(),
+                Box::new(RationalValue::from(num::BigInt::from(x))),
+            );
+            FloatValue::approximate(expression.evaluate(&bindings).as_ref())
+        };
+        assert_eq!(at(3), Some(3.0));
+        assert_eq!(at(-2), Some(2.0));
+
+        // It's the same as a piecewise expression with two branches
+        let piecewise = expression
+            .downcast_ref::<ConditionalExpression>()
+            .unwrap()
+            .to_piecewise();
+        assert_eq!(
+            piecewise.to_string(),
+            "\\begin{cases} x & x \\ge 0 \\\\ -x & \\text{otherwise} \\end{cases}"
+        );
+    }
+}
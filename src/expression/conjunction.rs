@@ -0,0 +1,126 @@
+use std::ops::Range;
+
+use crate::{
+    format::FormatOptions,
+    trace,
+    value::{BooleanValue, IntervalUnionValue, UndefinedValue, Value},
+};
+
+use super::{Bindings, Expression, ValueExpression};
+
+/// An expression that holds when all of its operands hold
+#[derive(Debug, Clone)]
+pub struct ConjunctionExpression {
+    operands: Vec<Box<dyn Expression>>,
+    span: Option<Range<usize>>,
+}
+
+impl ConjunctionExpression {
+    pub fn new(operands: Vec<Box<dyn Expression>>) -> Self {
+        Self {
+            operands,
+            span: None,
+        }
+    }
+
+    pub fn get_operands(&self) -> &[Box<dyn Expression>] {
+        &self.operands
+    }
+}
+
+impl Expression for ConjunctionExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "conjunction")
+        )
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        // Drop operands that hold, and fold to false as soon as one doesn't
+        let mut operands = Vec::new();
+        for operand in &self.operands {
+            let operand = operand.simplified();
+            let value = operand
+                .downcast_ref::<ValueExpression>()
+                .and_then(|operand| operand.get_value().downcast_ref::<BooleanValue>())
+                .map(BooleanValue::get_value);
+            match value {
+                Some(false) => {
+                    trace::rule("conjunction", "fold false operand");
+                    return Box::new(ValueExpression::new(Box::new(BooleanValue::new(false))));
+                }
+                Some(true) => trace::rule("conjunction", "drop true operand"),
+                None => operands.push(operand),
+            }
+        }
+        match operands.len() {
+            0 => Box::new(ValueExpression::new(Box::new(BooleanValue::new(true)))),
+            1 => operands.pop().unwrap(),
+            _ => Box::new(ConjunctionExpression::new(operands)),
+        }
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        let mut result = true;
+        for operand in &self.operands {
+            match operand.evaluate(bindings).downcast_ref::<BooleanValue>() {
+                Some(value) => result &= value.get_value(),
+                None => return Box::new(UndefinedValue::new()),
+            }
+        }
+        Box::new(BooleanValue::new(result))
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        let mut domain = IntervalUnionValue::all();
+        for operand in &self.operands {
+            domain = domain.intersection(&operand.domain(variable)?);
+        }
+        Some(domain)
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        self.operands
+            .iter()
+            .map(|operand| operand.as_ref())
+            .collect()
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        self.operands.iter_mut().collect()
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        self.operands
+            .iter()
+            .map(|operand| operand.format(options))
+            .collect::<Vec<_>>()
+            .join(" \\land ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{parse, ParserOptions};
+
+    #[test]
+    fn chained_relations() {
+        let options = ParserOptions::default();
+        let simplified = |input: &str| parse(input, &options).unwrap().simplified().to_string();
+        assert_eq!(simplified("1 < 2 \\le 2 < 3"), "true");
+        assert_eq!(simplified("3 < 1 < x"), "false");
+        assert_eq!(simplified("1 = 1 < x"), "1 < x");
+        assert_eq!(simplified("0 < x < 1"), "0 < x \\land x < 1");
+    }
+}
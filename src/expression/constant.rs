@@ -0,0 +1,120 @@
+use std::ops::Range;
+
+use crate::{
+    format::FormatOptions,
+    value::{Constant, ConstantValue, FloatValue, IntervalUnionValue, Value},
+};
+
+use super::{Bindings, Expression, ValueExpression};
+
+/// An expression naming a constant, like `\pi` or `e`
+///
+/// Constants stay exact through simplification and evaluation, and are only approximated when
+/// asked for explicitly
+#[derive(Debug, Clone)]
+pub struct ConstantExpression {
+    constant: Constant,
+    span: Option<Range<usize>>,
+}
+
+impl ConstantExpression {
+    pub fn new(constant: Constant) -> Self {
+        Self {
+            constant,
+            span: None,
+        }
+    }
+
+    pub fn get_constant(&self) -> &Constant {
+        &self.constant
+    }
+
+    /// Get a floating point approximation of this constant
+    pub fn approximated(&self) -> FloatValue {
+        FloatValue::new(self.constant.approximate())
+    }
+}
+
+impl Expression for ConstantExpression {
+    fn simplified(&self) -> Box<dyn Expression> {
+        // The exact value takes part in value arithmetic, like `\pi + \pi` becoming `2\pi`
+        Box::new(ValueExpression::new(Box::new(ConstantValue::from(
+            self.constant.clone(),
+        ))))
+    }
+
+    fn evaluate(&self, _bindings: &Bindings) -> Box<dyn Value> {
+        Box::new(ConstantValue::from(self.constant.clone()))
+    }
+
+    fn domain(&self, _variable: &str) -> Option<IntervalUnionValue> {
+        Some(IntervalUnionValue::all())
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        Vec::new()
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        Vec::new()
+    }
+
+    fn format(&self, _options: &FormatOptions) -> String {
+        self.constant.latex().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::{Bindings, VariableExpression},
+        parse::{parse, ParserOptions},
+        value::{ConstantValue, FloatValue},
+    };
+
+    use super::ConstantExpression;
+
+    #[test]
+    fn constants() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap();
+        let pi = parsed("\\pi");
+        let constant = pi.downcast_ref::<ConstantExpression>().unwrap();
+        assert_eq!(constant.get_constant().latex(), "\\pi");
+        assert_eq!(pi.to_string(), "\\pi");
+        assert!(parsed("\\phi").is::<VariableExpression>());
+        let phi = parse("\\phi", &options.with_greek_constants(true)).unwrap();
+        assert_eq!(
+            phi.downcast_ref::<ConstantExpression>()
+                .unwrap()
+                .get_constant()
+                .latex(),
+            "\\phi"
+        );
+
+        // Simplifying and evaluating keep the constant exact
+        assert_eq!(parsed("\\pi + \\pi").simplified().to_string(), "2\\pi");
+        assert_eq!(parsed("\\frac{e}{2}").simplified().to_string(), "e/2");
+        let value = pi.evaluate(&Bindings::new());
+        assert!(value.downcast_ref::<ConstantValue>().is_some());
+
+        // Approximating has to be asked for
+        let approximated = constant.approximated();
+        assert_eq!(
+            FloatValue::approximate(&approximated),
+            Some(std::f64::consts::PI)
+        );
+        assert_eq!(
+            FloatValue::approximate(value.as_ref()),
+            Some(std::f64::consts::PI)
+        );
+    }
+}
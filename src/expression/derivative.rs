@@ -0,0 +1,167 @@
+use std::ops::Range;
+
+use crate::{
+    format::FormatOptions,
+    trace,
+    value::{IntervalUnionValue, UndefinedValue, Value},
+};
+
+use super::{Bindings, Expression};
+
+/// A derivative expression, like `\frac{d}{dx} x^2`
+#[derive(Debug, Clone)]
+pub struct DerivativeExpression {
+    body: Box<dyn Expression>,
+    variable: String,
+    order: u32,
+    span: Option<Range<usize>>,
+}
+
+impl DerivativeExpression {
+    /// Construct a new first derivative
+    pub fn new(body: Box<dyn Expression>, variable: impl Into<String>) -> Self {
+        Self {
+            body,
+            variable: variable.into(),
+            order: 1,
+            span: None,
+        }
+    }
+
+    /// Set how many times the body is differentiated
+    pub fn with_order(self, order: u32) -> Self {
+        Self { order, ..self }
+    }
+
+    pub fn get_body(&self) -> &dyn Expression {
+        self.body.as_ref()
+    }
+
+    pub fn get_variable(&self) -> &str {
+        &self.variable
+    }
+
+    pub fn get_order(&self) -> u32 {
+        self.order
+    }
+}
+
+impl Expression for DerivativeExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "derivative")
+        )
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        let body = self.body.simplified();
+
+        // Differentiating no times leaves the body alone
+        if self.order == 0 {
+            trace::rule("derivative", "zeroth order");
+            return body;
+        }
+
+        // Repeated derivatives with respect to the same variable add their orders
+        if let Some(inner) = body
+            .downcast_ref::<DerivativeExpression>()
+            .filter(|inner| inner.variable == self.variable)
+        {
+            trace::rule("derivative", "merge orders");
+            return Box::new(
+                DerivativeExpression::new(inner.body.clone(), self.variable.clone())
+                    .with_order(self.order + inner.order),
+            );
+        }
+        Box::new(DerivativeExpression::new(body, self.variable.clone()).with_order(self.order))
+    }
+
+    fn evaluate(&self, _bindings: &Bindings) -> Box<dyn Value> {
+        // Derivatives can't be evaluated until there's a differentiation engine
+        Box::new(UndefinedValue::new())
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        // A derivative is at most defined where its body is
+        self.body.domain(variable)
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        vec![self.body.as_ref()]
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        vec![&mut self.body]
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        let order = match self.order {
+            1 => String::new(),
+            order => format!("^{{{}}}", order),
+        };
+        format!(
+            "\\frac{{d{}}}{{d{}{}}}{{{}}}",
+            order,
+            self.variable,
+            order,
+            self.body.format(options)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{parse, ParserOptions};
+
+    use super::DerivativeExpression;
+
+    #[test]
+    fn derivatives() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap();
+        let derivative = parsed("\\frac{d}{dx} x^2 + 1");
+        assert_eq!(derivative.to_string(), "(\\frac{d}{dx}{(x ^ 2)} + 1)");
+        let derivative = parsed("\\frac{\\mathrm{d}^2}{\\mathrm{d}t^2}(t^3 + 2t)").simplified();
+        let derivative = derivative.downcast_ref::<DerivativeExpression>().unwrap();
+        assert_eq!(derivative.get_variable(), "t");
+        assert_eq!(derivative.get_order(), 2);
+
+        // Prime notation differentiates with respect to the argument
+        assert_eq!(parsed("f''(x)").to_string(), "\\frac{d^{2}}{dx^{2}}{f(x)}");
+        assert_eq!(
+            parsed("2\\sin'(y)").to_string(),
+            "(2 * \\frac{d}{dy}{\\sin{y}})"
+        );
+
+        // Leibniz notation round trips, and repeated derivatives merge
+        let derivative = parsed("\\frac{d}{dx} \\frac{d^2}{dx^2} (x^4 + y)").simplified();
+        assert_eq!(
+            derivative.to_string(),
+            "\\frac{d^{3}}{dx^{3}}{((x ^ 4) + y)}"
+        );
+        assert_eq!(
+            parsed(&derivative.to_string()).to_string(),
+            derivative.to_string()
+        );
+        assert_eq!(
+            parsed("\\frac{d}{dy} \\frac{d}{dx} x y")
+                .simplified()
+                .to_string(),
+            "\\frac{d}{dy}{\\frac{d}{dx}{(x * y)}}"
+        );
+
+        // A fraction of plain variables is still a fraction
+        assert_eq!(parsed("\\frac{d}{dx}").to_string(), "(d / (d * x))");
+    }
+}
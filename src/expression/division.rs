@@ -1,36 +1,126 @@
-use super::{Expression, ValueExpression};
+use std::ops::Range;
+
+use crate::{
+    format::{FormatOptions, FractionStyle},
+    solve::solve,
+    trace,
+    value::{IntervalUnionValue, RationalValue, Value},
+};
+
+use super::{
+    factors, is_exact_combination, is_zero, Bindings, Expression, Relation, RelationExpression,
+    ValueExpression,
+};
 
 /// A division expression
 #[derive(Debug, Clone)]
 pub struct DivisionExpression {
     lhs: Box<dyn Expression>,
     rhs: Box<dyn Expression>,
+    span: Option<Range<usize>>,
 }
 
 impl DivisionExpression {
     pub fn new(lhs: Box<dyn Expression>, rhs: Box<dyn Expression>) -> Self {
-        Self { lhs, rhs }
+        Self {
+            lhs,
+            rhs,
+            span: None,
+        }
+    }
+
+    pub fn get_lhs(&self) -> &dyn Expression {
+        self.lhs.as_ref()
+    }
+
+    pub fn get_rhs(&self) -> &dyn Expression {
+        self.rhs.as_ref()
     }
 }
 
 impl Expression for DivisionExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "division")
+        )
+    )]
     fn simplified(&self) -> Box<dyn Expression> {
         // Simplify both sides
         let lhs = self.lhs.simplified();
         let rhs = self.rhs.simplified();
 
-        // Combine if two values
-        if let (Some(lhs), Some(rhs)) = (
+        // Combine if two values with an exact result
+        if let (Some(lhs_value), Some(rhs_value)) = (
             lhs.downcast_ref::<ValueExpression>(),
             rhs.downcast_ref::<ValueExpression>(),
         ) {
-            Box::new(ValueExpression::new(lhs.get_value().div(rhs.get_value())))
-        } else {
-            Box::new(DivisionExpression::new(lhs, rhs))
+            let value = lhs_value.get_value().div(rhs_value.get_value());
+            if is_exact_combination(value.as_ref(), lhs_value.get_value(), rhs_value.get_value())
+                || is_zero(rhs_value.get_value())
+            {
+                trace::rule("division", "combine values");
+                return Box::new(ValueExpression::new(value));
+            }
+        }
+
+        // Flatten nested products and quotients, merging like factors
+        if !lhs.is::<ValueExpression>() || !rhs.is::<ValueExpression>() {
+            return factors::combine(vec![(lhs, false), (rhs, true)]);
         }
+        Box::new(DivisionExpression::new(lhs, rhs))
     }
 
-    fn to_string(&self) -> String {
-        format!("({} / {})", self.lhs.to_string(), self.rhs.to_string())
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        self.lhs
+            .evaluate(bindings)
+            .div(self.rhs.evaluate(bindings).as_ref())
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        let domain = self
+            .lhs
+            .domain(variable)?
+            .intersection(&self.rhs.domain(variable)?);
+
+        // Exclude the values where the denominator is zero
+        let restriction = RelationExpression::new(
+            self.rhs.clone(),
+            Relation::NotEqual,
+            Box::new(ValueExpression::new(Box::new(RationalValue::zero()))),
+        );
+        Some(domain.intersection(&solve(&restriction, variable)?))
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        vec![self.lhs.as_ref(), self.rhs.as_ref()]
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        vec![&mut self.lhs, &mut self.rhs]
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        match options.get_fraction_style() {
+            FractionStyle::Inline => format!(
+                "({} / {})",
+                self.lhs.format(options),
+                self.rhs.format(options)
+            ),
+            FractionStyle::Frac => {
+                options.fraction(&self.lhs.format(options), &self.rhs.format(options))
+            }
+        }
     }
 }
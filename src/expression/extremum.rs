@@ -0,0 +1,218 @@
+use std::{cmp::Ordering, ops::Range};
+
+use crate::{
+    format::FormatOptions,
+    trace,
+    value::{IntervalUnionValue, UndefinedValue, Value},
+};
+
+use super::{Bindings, Expression, TupleExpression, ValueExpression};
+
+/// Which end of its arguments an extremum picks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Extremum {
+    Min,
+    Max,
+}
+
+impl Extremum {
+    /// Check if a value comparing to another with an ordering should replace it
+    pub fn prefers(&self, ordering: Ordering) -> bool {
+        match self {
+            Extremum::Min => ordering == Ordering::Less,
+            Extremum::Max => ordering == Ordering::Greater,
+        }
+    }
+
+    /// Get the LaTeX command for this extremum
+    pub fn command(&self) -> &'static str {
+        match self {
+            Extremum::Min => "\\min",
+            Extremum::Max => "\\max",
+        }
+    }
+}
+
+/// The smallest or largest of any number of expressions, like `\max(a, b, c)`
+#[derive(Debug, Clone)]
+pub struct ExtremumExpression {
+    extremum: Extremum,
+    arguments: TupleExpression,
+    span: Option<Range<usize>>,
+}
+
+impl ExtremumExpression {
+    pub fn new(extremum: Extremum, arguments: Vec<Box<dyn Expression>>) -> Self {
+        Self {
+            extremum,
+            arguments: TupleExpression::new(arguments),
+            span: None,
+        }
+    }
+
+    /// Construct a new minimum of expressions
+    pub fn min(arguments: Vec<Box<dyn Expression>>) -> Self {
+        Self::new(Extremum::Min, arguments)
+    }
+
+    /// Construct a new maximum of expressions
+    pub fn max(arguments: Vec<Box<dyn Expression>>) -> Self {
+        Self::new(Extremum::Max, arguments)
+    }
+
+    pub fn get_extremum(&self) -> Extremum {
+        self.extremum
+    }
+
+    pub fn get_arguments(&self) -> &[Box<dyn Expression>] {
+        self.arguments.get_elements()
+    }
+}
+
+impl Expression for ExtremumExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "extremum")
+        )
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        // Flatten nested extrema of the same kind
+        let mut arguments = Vec::new();
+        for argument in self.arguments.simplified_elements().into_elements() {
+            match argument.downcast_ref::<ExtremumExpression>() {
+                Some(nested) if nested.extremum == self.extremum => {
+                    trace::rule("extremum", "flatten");
+                    arguments.extend(nested.get_arguments().iter().cloned());
+                }
+                _ => arguments.push(argument),
+            }
+        }
+
+        // Fold the comparable values into one, and drop repeated arguments
+        let mut values: Vec<Box<dyn Value>> = Vec::new();
+        let mut rest: Vec<Box<dyn Expression>> = Vec::new();
+        for argument in arguments {
+            if let Some(value) = argument.downcast_ref::<ValueExpression>() {
+                let value = value.get_value();
+                let folded = values
+                    .iter_mut()
+                    .any(|best| match value.cmp(best.as_ref()) {
+                        Some(ordering) => {
+                            if self.extremum.prefers(ordering) {
+                                *best = dyn_clone::clone_box(value);
+                            }
+                            true
+                        }
+                        None => false,
+                    });
+                if !folded {
+                    values.push(dyn_clone::clone_box(value));
+                }
+            } else if rest
+                .iter()
+                .all(|other| other.to_string() != argument.to_string())
+            {
+                rest.push(argument);
+            }
+        }
+        rest.extend(
+            values
+                .into_iter()
+                .map(|value| Box::new(ValueExpression::new(value)) as Box<dyn Expression>),
+        );
+
+        if rest.len() == 1 {
+            trace::rule("extremum", "single argument");
+            rest.pop().unwrap()
+        } else {
+            Box::new(ExtremumExpression::new(self.extremum, rest))
+        }
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        let mut arguments = self
+            .get_arguments()
+            .iter()
+            .map(|argument| argument.evaluate(bindings));
+        let Some(mut best) = arguments.next() else {
+            return Box::new(UndefinedValue::new());
+        };
+        for value in arguments {
+            match value.cmp(best.as_ref()) {
+                Some(ordering) => {
+                    if self.extremum.prefers(ordering) {
+                        best = value;
+                    }
+                }
+                None => return Box::new(UndefinedValue::new()),
+            }
+        }
+        best
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        self.arguments.domain(variable)
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        self.arguments.get_children()
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        self.arguments.get_children_mut()
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        format!(
+            "{}{}",
+            self.extremum.command(),
+            self.arguments.format(options)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::Bindings,
+        parse::{parse, ParserOptions},
+        value::{FloatValue, RationalValue},
+    };
+    use num::BigInt;
+
+    #[test]
+    fn extrema() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap();
+        let simplified = |input: &str| parsed(input).simplified().to_string();
+        assert_eq!(simplified("\\max(1, \\frac{7}{2}, 3)"), "7/2");
+        assert_eq!(simplified("\\min(2, -1, \\frac{1}{2})"), "-1");
+        assert_eq!(simplified("\\min(x, 2, 1 + 2)"), "\\min(x, 2)");
+        assert_eq!(simplified("\\max(x, \\max(y, 1), x)"), "\\max(x, y, 1)");
+        assert_eq!(simplified("\\max(x, x)"), "x");
+        assert_eq!(simplified("\\min(x, \\max(y, 2))"), "\\min(x, \\max(y, 2))");
+
+        let mut bindings = Bindings::new();
+        bindings.insert("x".to_string(), Box::new(FloatValue::new(0.5)));
+        bindings.insert(
+            "y".to_string(),
+            Box::new(RationalValue::from(BigInt::from(2))),
+        );
+        let value = parsed("\\max(x, y, 1)").evaluate(&bindings);
+        assert_eq!(FloatValue::approximate(value.as_ref()), Some(2.0));
+        let value = parsed("\\min(x, y, 1)").evaluate(&bindings);
+        assert_eq!(FloatValue::approximate(value.as_ref()), Some(0.5));
+    }
+}
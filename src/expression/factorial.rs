@@ -0,0 +1,195 @@
+use std::ops::Range;
+
+use crate::{
+    format::FormatOptions,
+    numeric, trace,
+    value::{FloatValue, IntervalUnionValue, RationalValue, UndefinedValue, Value},
+};
+
+use super::{
+    function::{factorial, MAX_EXACT_FACTORIAL},
+    is_natural, AbsExpression, AdditionExpression, Bindings, DivisionExpression, Expression,
+    FunctionCallExpression, FunctionExpression, MultiplicationExpression, PowerExpression,
+    RootExpression, SubtractionExpression, ValueExpression, VariableExpression,
+};
+
+/// A factorial expression
+#[derive(Debug, Clone)]
+pub struct FactorialExpression {
+    argument: Box<dyn Expression>,
+    span: Option<Range<usize>>,
+}
+
+impl FactorialExpression {
+    pub fn new(argument: Box<dyn Expression>) -> Self {
+        Self {
+            argument,
+            span: None,
+        }
+    }
+
+    pub fn get_argument(&self) -> &dyn Expression {
+        self.argument.as_ref()
+    }
+}
+
+/// Get the exact factorial of a rational value, if it isn't too large
+///
+/// Factorials are only defined for non-negative integers, so other rationals are undefined
+fn exact_factorial(value: &RationalValue) -> Option<Box<dyn Value>> {
+    let Some(n) = value.to_integer() else {
+        return Some(Box::new(UndefinedValue::new()));
+    };
+    if n.sign() == num::bigint::Sign::Minus {
+        return Some(Box::new(UndefinedValue::new()));
+    }
+    let n = i64::try_from(n)
+        .ok()
+        .filter(|&n| n <= MAX_EXACT_FACTORIAL)?;
+    Some(Box::new(factorial(n)))
+}
+
+impl Expression for FactorialExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "factorial")
+        )
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        let argument = self.argument.simplified();
+
+        // Fold the factorial of an integer
+        if let Some(value) = argument
+            .downcast_ref::<ValueExpression>()
+            .and_then(|argument| argument.get_value().downcast_ref::<RationalValue>())
+            .and_then(exact_factorial)
+        {
+            trace::rule("factorial", "combine values");
+            return Box::new(ValueExpression::new(value));
+        }
+        Box::new(FactorialExpression::new(argument))
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        let argument = self.argument.evaluate(bindings);
+
+        // Prefer the exact result, falling back to the gamma function for approximations
+        if let Some(value) = argument
+            .downcast_ref::<RationalValue>()
+            .and_then(exact_factorial)
+        {
+            return value;
+        }
+
+        // Approximate values are held to the same non-negative integers as exact ones
+        FloatValue::approximate(argument.as_ref())
+            .filter(|x| *x >= 0.0 && x.fract() == 0.0)
+            .and_then(|x| numeric::gamma(x + 1.0))
+            .filter(|value| value.is_finite())
+            .map(|value| Box::new(FloatValue::new(value)) as Box<dyn Value>)
+            .unwrap_or_else(|| Box::new(UndefinedValue::new()))
+    }
+
+    fn domain(&self, _variable: &str) -> Option<IntervalUnionValue> {
+        // The poles at the negative integers can't be excluded from an interval union
+        None
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        vec![self.argument.as_ref()]
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        vec![&mut self.argument]
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        // Arguments that aren't atoms or already delimited would otherwise bind looser than `!`
+        let argument = self.argument.as_ref();
+        let atom = is_natural(argument)
+            || argument.is::<VariableExpression>()
+            || argument.is::<AdditionExpression>()
+            || argument.is::<SubtractionExpression>()
+            || argument.is::<MultiplicationExpression>()
+            || argument.is::<DivisionExpression>()
+            || argument.is::<PowerExpression>()
+            || argument.is::<FunctionExpression>()
+            || argument.is::<FunctionCallExpression>()
+            || argument.is::<AbsExpression>()
+            || argument.is::<RootExpression>();
+        if atom {
+            format!("{}!", argument.format(options))
+        } else {
+            format!("({})!", argument.format(options))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::{Bindings, Expression, FactorialExpression, ValueExpression},
+        parse::{parse, ParserOptions},
+        value::{FloatValue, RationalValue, Sign, UndefinedValue},
+    };
+
+    #[test]
+    fn factorials() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap();
+        assert_eq!(parsed("5!").simplified().to_string(), "120");
+        assert_eq!(parsed("2^3!").simplified().to_string(), "64");
+        assert_eq!(parsed("(1 - 3)!").simplified().to_string(), "undefined");
+        assert_eq!(parsed("x!").simplified().to_string(), "x!");
+
+        assert_eq!(parsed("(1/2)!").simplified().to_string(), "undefined");
+
+        // Arguments that would bind looser than `!` are parenthesized, so they parse back the same
+        for (input, printed) in [
+            ("(-x)!", "(-x)!"),
+            ("(x!)!", "(x!)!"),
+            ("(x + 1)!", "(x + 1)!"),
+        ] {
+            assert_eq!(parsed(input).simplified().to_string(), printed);
+            assert_eq!(parsed(printed).simplified().to_string(), printed);
+        }
+        for (value, printed) in [
+            (RationalValue::new(Sign::Negative, 2_u32, 1_u32), "(-2)!"),
+            (RationalValue::new(Sign::Positive, 1_u32, 2_u32), "(1/2)!"),
+        ] {
+            let factorial =
+                FactorialExpression::new(Box::new(ValueExpression::new(Box::new(value))));
+            assert_eq!(factorial.to_string(), printed);
+            assert_eq!(parsed(printed).simplified().to_string(), "undefined");
+        }
+        assert_eq!(
+            parsed("20!").simplified().to_string(),
+            "2432902008176640000"
+        );
+
+        // Evaluating agrees with simplifying, for exact and approximate values alike
+        let value = parsed("(1/2)!").evaluate(&Bindings::new());
+        assert!(value.is::<UndefinedValue>());
+        let mut bindings = Bindings::new();
+        bindings.insert("x".to_string(), Box::new(FloatValue::new(0.5)));
+        assert!(parsed("x!").evaluate(&bindings).is::<UndefinedValue>());
+        bindings.insert("x".to_string(), Box::new(FloatValue::new(-2.0)));
+        assert!(parsed("x!").evaluate(&bindings).is::<UndefinedValue>());
+        bindings.insert("x".to_string(), Box::new(FloatValue::new(5.0)));
+        let value = parsed("x!").evaluate(&bindings);
+        let approximation = FloatValue::approximate(value.as_ref()).unwrap();
+        assert!((approximation - 120.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,321 @@
+use std::{cmp::Ordering, ops::Range};
+
+use num::{BigInt, BigUint};
+
+use crate::{
+    format::FormatOptions,
+    trace,
+    value::{IntervalUnionValue, RationalValue, Sign, Value},
+};
+
+use super::{
+    is_exact_combination, Bindings, DivisionExpression, Expression, MultiplicationExpression,
+    NegationExpression, PowerExpression, RootExpression, ValueExpression,
+};
+
+/// A product of any number of factors, like `2xy`
+#[derive(Debug, Clone)]
+pub struct FactorsExpression {
+    factors: Vec<Box<dyn Expression>>,
+    span: Option<Range<usize>>,
+}
+
+impl FactorsExpression {
+    pub fn new(factors: Vec<Box<dyn Expression>>) -> Self {
+        Self {
+            factors,
+            span: None,
+        }
+    }
+
+    pub fn get_factors(&self) -> &[Box<dyn Expression>] {
+        &self.factors
+    }
+}
+
+/// Add the factors of an expression to a list, flattening nested products and quotients
+///
+/// Each factor is paired with whether it divides instead of multiplies
+fn flatten(
+    expression: &dyn Expression,
+    inverted: bool,
+    factors: &mut Vec<(Box<dyn Expression>, bool)>,
+) {
+    if let Some(product) = expression.downcast_ref::<FactorsExpression>() {
+        for factor in &product.factors {
+            flatten(factor.as_ref(), inverted, factors);
+        }
+    } else if let Some(product) = expression.downcast_ref::<MultiplicationExpression>() {
+        flatten(product.get_lhs(), inverted, factors);
+        flatten(product.get_rhs(), inverted, factors);
+    } else if let Some(quotient) = expression.downcast_ref::<DivisionExpression>() {
+        flatten(quotient.get_lhs(), inverted, factors);
+        flatten(quotient.get_rhs(), !inverted, factors);
+    } else if let Some(negation) = expression.downcast_ref::<NegationExpression>() {
+        factors.push((rational(RationalValue::one().get_opposite()), false));
+        flatten(negation.get_argument(), inverted, factors);
+    } else {
+        factors.push((dyn_clone::clone_box(expression), inverted));
+    }
+}
+
+/// Wrap a rational value in an expression
+fn rational(value: RationalValue) -> Box<dyn Expression> {
+    Box::new(ValueExpression::new(Box::new(value)))
+}
+
+/// Split a factor into its base and rational exponent, like `x^2` into `x` and `2`
+fn split_exponent(factor: &dyn Expression) -> (RationalValue, &dyn Expression) {
+    if let Some(power) = factor.downcast_ref::<PowerExpression>() {
+        if let Some(exponent) = power
+            .get_exponent()
+            .downcast_ref::<ValueExpression>()
+            .and_then(|exponent| exponent.get_value().downcast_ref::<RationalValue>())
+        {
+            return (exponent.clone(), power.get_base());
+        }
+    }
+    (RationalValue::one(), factor)
+}
+
+/// Build a factor from its base and a positive exponent
+fn with_exponent(exponent: RationalValue, base: Box<dyn Expression>) -> Box<dyn Expression> {
+    if exponent.cmp(&RationalValue::one()) == Some(Ordering::Equal) {
+        base
+    } else {
+        Box::new(PowerExpression::new(base, rational(exponent)))
+    }
+}
+
+/// Raise a root to the merged power of its factors, if that folds it into a power of its radicand
+fn folded_root(base: &dyn Expression, exponent: &RationalValue) -> Option<Box<dyn Expression>> {
+    if !base.is::<RootExpression>() || exponent.is_zero() {
+        return None;
+    }
+    let power =
+        PowerExpression::new(dyn_clone::clone_box(base), rational(exponent.clone())).simplified();
+    power
+        .downcast_ref::<PowerExpression>()
+        .is_none_or(|power| !power.get_base().is::<RootExpression>())
+        .then_some(power)
+}
+
+/// Multiply a list of factors together, without simplifying them again
+fn product(mut factors: Vec<Box<dyn Expression>>) -> Box<dyn Expression> {
+    match factors.len() {
+        0 => rational(RationalValue::one()),
+        1 => factors.pop().unwrap(),
+        2 => {
+            let rhs = factors.pop().unwrap();
+            let lhs = factors.pop().unwrap();
+            Box::new(MultiplicationExpression::new(lhs, rhs))
+        }
+        _ => Box::new(FactorsExpression::new(factors)),
+    }
+}
+
+/// Combine simplified factors into a product, paired with whether they divide instead of multiply
+pub(super) fn combine(operands: Vec<(Box<dyn Expression>, bool)>) -> Box<dyn Expression> {
+    let mut factors = Vec::new();
+    for (operand, inverted) in operands {
+        flatten(operand.as_ref(), inverted, &mut factors);
+    }
+
+    // Combine the values into coefficients, and merge the powers of like bases
+    let mut values: Vec<Box<dyn Value>> = Vec::new();
+    let mut groups: Vec<(String, RationalValue, Box<dyn Expression>)> = Vec::new();
+    for (factor, inverted) in factors {
+        if let Some(value) = factor.downcast_ref::<ValueExpression>() {
+            let value = value.get_value();
+            let combined = values.iter_mut().any(|coefficient| {
+                let result = if inverted {
+                    coefficient.div(value)
+                } else {
+                    coefficient.mul(value)
+                };
+                let exact = is_exact_combination(result.as_ref(), coefficient.as_ref(), value);
+                if exact {
+                    *coefficient = result;
+                }
+                exact
+            });
+            if !combined {
+                values.push(if inverted {
+                    RationalValue::one().div(value)
+                } else {
+                    dyn_clone::clone_box(value)
+                });
+            }
+            continue;
+        }
+        let (exponent, base) = split_exponent(factor.as_ref());
+        let exponent = if inverted {
+            exponent.get_opposite()
+        } else {
+            exponent
+        };
+        let key = base.to_string();
+        match groups.iter_mut().find(|(other, _, _)| *other == key) {
+            Some((_, total, _)) => {
+                trace::rule("factors", "merge powers");
+                *total = total
+                    .add(&exponent)
+                    .downcast_ref::<RationalValue>()
+                    .expect("Unexpected error: a sum of rationals isn't rational!")
+                    .clone();
+            }
+            None => groups.push((key, exponent, dyn_clone::clone_box(base))),
+        }
+    }
+
+    // Merged powers of roots may fold into powers of their radicands, which combine again
+    let folded = groups
+        .iter()
+        .map(|(_, exponent, base)| folded_root(base.as_ref(), exponent))
+        .collect::<Vec<_>>();
+    if folded.iter().any(Option::is_some) {
+        trace::rule("factors", "fold powers of roots");
+        let operands = values
+            .into_iter()
+            .map(|value| Box::new(ValueExpression::new(value)) as Box<dyn Expression>)
+            .chain(
+                groups
+                    .into_iter()
+                    .zip(folded)
+                    .map(|((_, exponent, base), folded)| {
+                        folded.unwrap_or_else(|| {
+                            Box::new(PowerExpression::new(base, rational(exponent)))
+                        })
+                    }),
+            )
+            .map(|factor| (factor, false))
+            .collect();
+        return combine(operands);
+    }
+
+    // Split the rational coefficient between the numerator and the denominator
+    let mut sign = Sign::Positive;
+    let mut numerator = Vec::new();
+    let mut denominator = Vec::new();
+    for value in values {
+        let Some(coefficient) = value.downcast_ref::<RationalValue>() else {
+            numerator.push(Box::new(ValueExpression::new(value)) as Box<dyn Expression>);
+            continue;
+        };
+        if coefficient.is_zero() {
+            trace::rule("factors", "zero coefficient");
+            return rational(RationalValue::zero());
+        }
+        sign = sign ^ *coefficient.get_sign();
+        for (part, factors) in [
+            (coefficient.get_numerator(), &mut numerator),
+            (coefficient.get_denominator(), &mut denominator),
+        ] {
+            if *part != BigUint::from(1_u32) {
+                factors.insert(0, rational(RationalValue::from(BigInt::from(part.clone()))));
+            }
+        }
+    }
+
+    // Write the other factors in a canonical order, cancelling bases whose powers sum to zero
+    groups.sort_by(|(lhs, _, _), (rhs, _, _)| lhs.cmp(rhs));
+    for (_, exponent, base) in groups {
+        if exponent.is_zero() {
+            trace::rule("factors", "cancel");
+        } else if exponent.is_negative() {
+            denominator.push(with_exponent(exponent.get_opposite(), base));
+        } else {
+            numerator.push(with_exponent(exponent, base));
+        }
+    }
+
+    let mut result = product(numerator);
+    if !denominator.is_empty() {
+        result = Box::new(DivisionExpression::new(result, product(denominator)));
+    }
+    match sign {
+        Sign::Positive => result,
+        Sign::Negative => NegationExpression::new(result).simplified(),
+    }
+}
+
+impl Expression for FactorsExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "factors")
+        )
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        combine(
+            self.factors
+                .iter()
+                .map(|factor| (factor.simplified(), false))
+                .collect(),
+        )
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        self.factors.iter().fold(
+            Box::new(RationalValue::one()) as Box<dyn Value>,
+            |product, factor| product.mul(factor.evaluate(bindings).as_ref()),
+        )
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        let mut domain = IntervalUnionValue::all();
+        for factor in &self.factors {
+            domain = domain.intersection(&factor.domain(variable)?);
+        }
+        Some(domain)
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        self.factors.iter().map(|factor| factor.as_ref()).collect()
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        self.factors.iter_mut().collect()
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        format!(
+            "({})",
+            self.factors
+                .iter()
+                .map(|factor| factor.format(options))
+                .collect::<Vec<_>>()
+                .join(" * ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{parse, ParserOptions};
+
+    #[test]
+    fn factors() {
+        let options = ParserOptions::default();
+        let simplified = |input: &str| parse(input, &options).unwrap().simplified().to_string();
+        assert_eq!(simplified("2x \\cdot 3y"), "(6 * x * y)");
+        assert_eq!(simplified("y \\cdot x \\cdot z"), "(x * y * z)");
+        assert_eq!(simplified("x \\cdot x^2"), "(x ^ 3)");
+        assert_eq!(simplified("\\frac{xy}{x}"), "y");
+        assert_eq!(simplified("\\frac{2x}{4y}"), "(x / (2 * y))");
+        assert_eq!(simplified("\\frac{x^2}{x^3}"), "(1 / x)");
+        assert_eq!(simplified("-x \\cdot 3"), "(-3 * x)");
+        assert_eq!(simplified("0 \\cdot x \\cdot y"), "0");
+    }
+}
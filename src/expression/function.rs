@@ -0,0 +1,1016 @@
+use std::{cmp::Ordering, ops::Range};
+
+use num::{BigInt, BigUint, Integer as _};
+
+use crate::{
+    format::FormatOptions,
+    numeric,
+    solve::{linear_form, solve},
+    trace,
+    value::{
+        Bound, Constant, ConstantValue, FloatValue, IntervalUnionValue, IntervalValue,
+        RationalValue, Sign, UndefinedValue, Value,
+    },
+};
+
+use super::{
+    AbsExpression, AdditionExpression, Bindings, ConjunctionExpression, Expression,
+    FactorsExpression, FunctionDefinition, FunctionRegistry, MultiplicationExpression,
+    PowerExpression, Relation, RelationExpression, ValueExpression,
+};
+
+/// A function that can be applied to expressions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Function {
+    Sin,
+    Cos,
+    Tan,
+    Sinh,
+    Cosh,
+    Tanh,
+    Ln,
+    Log,
+    Arcsin,
+    Arccos,
+    Arctan,
+    Gamma,
+    Erf,
+    Beta,
+    Binomial,
+    Permutation,
+    Floor,
+    Ceil,
+    BitAnd,
+    BitOr,
+    BitXor,
+    BitNot,
+    ShiftLeft,
+    ShiftRight,
+}
+
+impl Function {
+    /// All the built-in functions
+    pub const ALL: [Function; 24] = [
+        Function::Sin,
+        Function::Cos,
+        Function::Tan,
+        Function::Sinh,
+        Function::Cosh,
+        Function::Tanh,
+        Function::Ln,
+        Function::Log,
+        Function::Arcsin,
+        Function::Arccos,
+        Function::Arctan,
+        Function::Gamma,
+        Function::Erf,
+        Function::Beta,
+        Function::Binomial,
+        Function::Permutation,
+        Function::Floor,
+        Function::Ceil,
+        Function::BitAnd,
+        Function::BitOr,
+        Function::BitXor,
+        Function::BitNot,
+        Function::ShiftLeft,
+        Function::ShiftRight,
+    ];
+
+    /// Get the name this function is registered under in the function registry
+    pub fn name(&self) -> &'static str {
+        match self {
+            Function::Sin => "sin",
+            Function::Cos => "cos",
+            Function::Tan => "tan",
+            Function::Sinh => "sinh",
+            Function::Cosh => "cosh",
+            Function::Tanh => "tanh",
+            Function::Ln => "ln",
+            Function::Log => "log",
+            Function::Arcsin => "arcsin",
+            Function::Arccos => "arccos",
+            Function::Arctan => "arctan",
+            Function::Gamma => "Gamma",
+            Function::Erf => "erf",
+            Function::Beta => "B",
+            Function::Binomial => "binom",
+            Function::Permutation => "P",
+            Function::Floor => "floor",
+            Function::Ceil => "ceil",
+            Function::BitAnd => "and",
+            Function::BitOr => "or",
+            Function::BitXor => "xor",
+            Function::BitNot => "not",
+            Function::ShiftLeft => "shl",
+            Function::ShiftRight => "shr",
+        }
+    }
+
+    /// Get the LaTeX command for this function
+    pub fn latex(&self) -> &'static str {
+        match self {
+            Function::Sin => "\\sin",
+            Function::Cos => "\\cos",
+            Function::Tan => "\\tan",
+            Function::Sinh => "\\sinh",
+            Function::Cosh => "\\cosh",
+            Function::Tanh => "\\tanh",
+            Function::Ln => "\\ln",
+            Function::Log => "\\log",
+            Function::Arcsin => "\\arcsin",
+            Function::Arccos => "\\arccos",
+            Function::Arctan => "\\arctan",
+            Function::Gamma => "\\Gamma",
+            Function::Erf => "\\operatorname{erf}",
+            Function::Beta => "\\operatorname{B}",
+            Function::Binomial => "\\binom",
+            Function::Permutation => "P",
+            Function::Floor => "\\lfloor",
+            Function::Ceil => "\\lceil",
+            Function::BitAnd => "\\operatorname{and}",
+            Function::BitOr => "\\operatorname{or}",
+            Function::BitXor => "\\operatorname{xor}",
+            Function::BitNot => "\\operatorname{not}",
+            Function::ShiftLeft => "\\operatorname{shl}",
+            Function::ShiftRight => "\\operatorname{shr}",
+        }
+    }
+
+    /// Get the number of arguments this function takes
+    pub fn arity(&self) -> usize {
+        match self {
+            Function::Log
+            | Function::Beta
+            | Function::Binomial
+            | Function::Permutation
+            | Function::BitAnd
+            | Function::BitOr
+            | Function::BitXor
+            | Function::ShiftLeft
+            | Function::ShiftRight => 2,
+            _ => 1,
+        }
+    }
+
+    /// Get the exact result of this function, if the arguments are special values
+    pub fn exact(&self, arguments: &[&dyn Value]) -> Option<Box<dyn Value>> {
+        if let (Function::Sin | Function::Cos | Function::Tan, [argument]) = (self, arguments) {
+            return self.exact_trig(*argument);
+        }
+        if let (Function::Ln, [argument]) = (self, arguments) {
+            // The natural logarithm of e is one
+            if let Some(argument) = argument.downcast_ref::<ConstantValue>() {
+                return (*argument.get_constant() == Constant::e()
+                    && argument.get_coefficient().cmp(&RationalValue::one())
+                        == Some(Ordering::Equal))
+                .then(|| Box::new(RationalValue::one()) as Box<dyn Value>);
+            }
+        }
+        let arguments = arguments
+            .iter()
+            .map(|argument| argument.downcast_ref::<RationalValue>())
+            .collect::<Option<Vec<_>>>()?;
+        match (self, arguments.as_slice()) {
+            (Function::Arcsin | Function::Arccos | Function::Arctan, [argument]) => {
+                // Arguments outside the accepted interval have no real result
+                if self
+                    .argument_interval()
+                    .is_some_and(|interval| !interval.contains(argument))
+                {
+                    return Some(Box::new(UndefinedValue::new()));
+                }
+                self.exact_inverse_trig(argument)
+            }
+            (Function::Gamma, [argument]) => {
+                // Gamma has poles at the non-positive integers and extends the factorial
+                let n = integer(argument)?;
+                if n <= 0 {
+                    Some(Box::new(UndefinedValue::new()))
+                } else if n - 1 > MAX_EXACT_FACTORIAL {
+                    None
+                } else {
+                    Some(Box::new(factorial(n - 1)))
+                }
+            }
+            (Function::Sinh | Function::Tanh, [argument]) if argument.is_zero() => {
+                Some(Box::new(RationalValue::zero()))
+            }
+            (Function::Cosh, [argument]) if argument.is_zero() => {
+                Some(Box::new(RationalValue::one()))
+            }
+            (Function::Ln, [argument]) => {
+                if argument.is_negative() || argument.is_zero() {
+                    Some(Box::new(UndefinedValue::new()))
+                } else if argument.cmp(&RationalValue::one()) == Some(Ordering::Equal) {
+                    Some(Box::new(RationalValue::zero()))
+                } else {
+                    None
+                }
+            }
+            (Function::Log, [base, argument]) => exact_log(base, argument),
+            (Function::Erf, [argument]) if argument.is_zero() => {
+                Some(Box::new(RationalValue::zero()))
+            }
+            (Function::Beta, [x, y]) => {
+                let (x, y) = (integer(x)?, integer(y)?);
+                if x <= 0 || y <= 0 {
+                    return None;
+                }
+                let sum = x
+                    .checked_add(y)?
+                    .checked_sub(1)
+                    .filter(|&sum| sum <= MAX_EXACT_FACTORIAL)?;
+                Some(factorial(x - 1).mul(&factorial(y - 1)).div(&factorial(sum)))
+            }
+            (Function::Binomial, [n, k]) => {
+                let (n, k) = (integer(n)?, integer(k)?);
+                Some(Box::new(binomial(n, k)?))
+            }
+            (Function::Permutation, [n, k]) => {
+                let (n, k) = (integer(n)?, integer(k)?);
+                Some(match permutations(n, k) {
+                    Some(value) => Box::new(value),
+                    None if k < 0 => Box::new(UndefinedValue::new()),
+                    None => return None,
+                })
+            }
+            (Function::Floor, [argument]) => Some(Box::new(RationalValue::from(argument.floor()))),
+            (Function::Ceil, [argument]) => Some(Box::new(RationalValue::from(argument.ceil()))),
+            (
+                Function::BitAnd
+                | Function::BitOr
+                | Function::BitXor
+                | Function::BitNot
+                | Function::ShiftLeft
+                | Function::ShiftRight,
+                arguments,
+            ) => Some(self.exact_bitwise(arguments)),
+            _ => None,
+        }
+    }
+
+    /// Get the result of a bitwise function, which is undefined for non-integers
+    fn exact_bitwise(&self, arguments: &[&RationalValue]) -> Box<dyn Value> {
+        let Some(arguments) = arguments
+            .iter()
+            .map(|argument| argument.to_integer())
+            .collect::<Option<Vec<_>>>()
+        else {
+            return Box::new(UndefinedValue::new());
+        };
+
+        // Negative integers behave as infinitely sign-extended two's complement
+        let result = match (self, arguments.as_slice()) {
+            (Function::BitAnd, [lhs, rhs]) => Some(lhs & rhs),
+            (Function::BitOr, [lhs, rhs]) => Some(lhs | rhs),
+            (Function::BitXor, [lhs, rhs]) => Some(lhs ^ rhs),
+            (Function::BitNot, [value]) => Some(!value),
+            (Function::ShiftLeft, [value, shift]) => {
+                shift_amount(shift).map(|shift| value << shift)
+            }
+            (Function::ShiftRight, [value, shift]) => {
+                shift_amount(shift).map(|shift| value >> shift)
+            }
+            _ => None,
+        };
+        match result {
+            Some(result) => Box::new(RationalValue::from(result)),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    /// Approximate the result of this function
+    pub fn approximate(&self, arguments: &[f64]) -> Option<f64> {
+        match (self, arguments) {
+            (Function::Sin, [x]) => Some(x.sin()),
+            (Function::Cos, [x]) => Some(x.cos()),
+            (Function::Tan, [x]) => Some(x.tan()),
+            (Function::Sinh, [x]) => Some(x.sinh()),
+            (Function::Cosh, [x]) => Some(x.cosh()),
+            (Function::Tanh, [x]) => Some(x.tanh()),
+            (Function::Ln, [x]) => (*x > 0.0).then(|| x.ln()),
+            (Function::Log, [base, x]) => {
+                (*base > 0.0 && *base != 1.0 && *x > 0.0).then(|| x.ln() / base.ln())
+            }
+            (Function::Arcsin, [x]) => (-1.0..=1.0).contains(x).then(|| x.asin()),
+            (Function::Arccos, [x]) => (-1.0..=1.0).contains(x).then(|| x.acos()),
+            (Function::Arctan, [x]) => Some(x.atan()),
+            (Function::Gamma, [x]) => numeric::gamma(*x),
+            (Function::Erf, [x]) => Some(numeric::erf(*x)),
+            (Function::Beta, [x, y]) => numeric::beta(*x, *y),
+            (Function::Permutation, [n, k]) => {
+                Some(numeric::gamma(n + 1.0)? / numeric::gamma(n - k + 1.0)?)
+            }
+            (Function::Floor, [x]) => Some(x.floor()),
+            (Function::Ceil, [x]) => Some(x.ceil()),
+            (Function::Binomial, [n, k]) => Some(
+                numeric::gamma(n + 1.0)?
+                    / (numeric::gamma(k + 1.0)? * numeric::gamma(n - k + 1.0)?),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Get the exact result of a trig function at an angle where it is rational
+    fn exact_trig(&self, argument: &dyn Value) -> Option<Box<dyn Value>> {
+        // Measure the angle in twelfths of pi, reduced to a single turn
+        let twelfths = if let Some(argument) = argument.downcast_ref::<RationalValue>() {
+            argument.is_zero().then_some(0)?
+        } else {
+            let argument = argument.downcast_ref::<ConstantValue>()?;
+            if *argument.get_constant() != Constant::pi() {
+                return None;
+            }
+            let twelfths =
+                argument
+                    .get_coefficient()
+                    .mul(&RationalValue::new(Sign::Positive, 12_u32, 1_u32));
+            integer(twelfths.downcast_ref::<RationalValue>()?)?.rem_euclid(24)
+        };
+
+        // Sine is rational at multiples of pi / 6, and cosine is sine shifted by pi / 2
+        let sine = |twelfths: i64| -> Option<Box<dyn Value>> {
+            let (sign, numerator, denominator) = match twelfths % 24 {
+                0 | 12 => (Sign::Positive, 0_u32, 1_u32),
+                2 | 10 => (Sign::Positive, 1, 2),
+                6 => (Sign::Positive, 1, 1),
+                14 | 22 => (Sign::Negative, 1, 2),
+                18 => (Sign::Negative, 1, 1),
+                _ => return None,
+            };
+            Some(Box::new(
+                RationalValue::new(sign, numerator, denominator).simplified(),
+            ))
+        };
+        match self {
+            Function::Sin => sine(twelfths),
+            Function::Cos => sine(twelfths + 6),
+            Function::Tan => match twelfths % 12 {
+                0 => Some(Box::new(RationalValue::zero())),
+                3 => Some(Box::new(RationalValue::one())),
+                6 => Some(Box::new(UndefinedValue::new())),
+                9 => Some(Box::new(RationalValue::new(Sign::Negative, 1_u32, 1_u32))),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Get the exact result of an inverse trig function at one of its special values
+    fn exact_inverse_trig(&self, argument: &RationalValue) -> Option<Box<dyn Value>> {
+        let argument = argument.simplified();
+        let sign = *argument.get_sign();
+        let numerator = u32::try_from(argument.get_numerator()).ok()?;
+        let denominator = u32::try_from(argument.get_denominator()).ok()?;
+
+        // Multiples of pi for the standard angles, by absolute argument
+        let angle = |numerator: u32, denominator: u32| -> Box<dyn Value> {
+            if numerator == 0 {
+                Box::new(RationalValue::zero())
+            } else {
+                Box::new(ConstantValue::new(
+                    RationalValue::new(sign, numerator, denominator),
+                    Constant::pi(),
+                ))
+            }
+        };
+        match self {
+            Function::Arcsin => match (numerator, denominator) {
+                (0, _) => Some(angle(0, 1)),
+                (1, 2) => Some(angle(1, 6)),
+                (1, 1) => Some(angle(1, 2)),
+                _ => None,
+            },
+            Function::Arctan => match (numerator, denominator) {
+                (0, _) => Some(angle(0, 1)),
+                (1, 1) => Some(angle(1, 4)),
+                _ => None,
+            },
+            // Arccos is not odd, so negative arguments reflect around pi / 2
+            Function::Arccos => {
+                let (numerator, denominator): (u32, u32) = match (sign, numerator, denominator) {
+                    (_, 0, _) => (1, 2),
+                    (Sign::Positive, 1, 2) => (1, 3),
+                    (Sign::Positive, 1, 1) => (0, 1),
+                    (Sign::Negative, 1, 2) => (2, 3),
+                    (Sign::Negative, 1, 1) => (1, 1),
+                    _ => return None,
+                };
+                if numerator == 0 {
+                    Some(Box::new(RationalValue::zero()))
+                } else {
+                    Some(Box::new(ConstantValue::new(
+                        RationalValue::new(Sign::Positive, numerator, denominator),
+                        Constant::pi(),
+                    )))
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the interval of arguments this function accepts, if it is restricted
+    fn argument_interval(&self) -> Option<IntervalValue> {
+        match self {
+            Function::Arcsin | Function::Arccos => Some(IntervalValue::new(
+                Bound::Closed(RationalValue::new(Sign::Negative, 1_u32, 1_u32)),
+                Bound::Closed(RationalValue::one()),
+            )),
+            Function::Ln | Function::Log => Some(IntervalValue::new(
+                Bound::Open(RationalValue::zero()),
+                Bound::Unbounded,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Check if this function has poles that a domain can't exclude
+    fn has_poles(&self) -> bool {
+        matches!(self, Function::Tan | Function::Gamma | Function::Beta)
+    }
+}
+
+/// Get a rational value as a small integer, if it is one
+fn integer(value: &RationalValue) -> Option<i64> {
+    if !value.is_integer() {
+        return None;
+    }
+    let value = value.simplified();
+    let magnitude = i64::try_from(value.get_numerator()).ok()?;
+    Some(if value.is_negative() {
+        -magnitude
+    } else {
+        magnitude
+    })
+}
+
+/// The largest denominator tried for an exact logarithm, so `\log_{8}{2}` is found to be `1/3`
+const MAX_LOG_DENOMINATOR: i64 = 12;
+
+/// The most bits the powers compared when finding an exact logarithm may have
+const MAX_LOG_BITS: u64 = 1 << 16;
+
+/// Get the exact logarithm of a rational value in a rational base, if it is rational
+fn exact_log(base: &RationalValue, argument: &RationalValue) -> Option<Box<dyn Value>> {
+    let one = RationalValue::one();
+    if base.is_negative()
+        || base.is_zero()
+        || base.cmp(&one) == Some(Ordering::Equal)
+        || argument.is_negative()
+        || argument.is_zero()
+    {
+        return Some(Box::new(UndefinedValue::new()));
+    }
+
+    // A rational logarithm p/q means the base to the p equals the argument to the q
+    let estimate = FloatValue::approximate(argument)?.ln() / FloatValue::approximate(base)?.ln();
+    let bits = |value: &RationalValue| {
+        value
+            .get_numerator()
+            .bits()
+            .max(value.get_denominator().bits())
+    };
+    for denominator in 1..=MAX_LOG_DENOMINATOR {
+        let numerator = (estimate * denominator as f64).round();
+        if !numerator.is_finite()
+            || (bits(base) as f64 * numerator.abs())
+                .max((bits(argument) * denominator as u64) as f64)
+                > MAX_LOG_BITS as f64
+        {
+            return None;
+        }
+        let numerator = numerator as i64;
+        let power = base.pow(i32::try_from(numerator).ok()?)?;
+        let root = argument.pow(i32::try_from(denominator).ok()?)?;
+        if power.cmp(&root) == Some(Ordering::Equal) {
+            return Some(Box::new(
+                RationalValue::new(
+                    Sign::from(numerator < 0),
+                    numerator.unsigned_abs(),
+                    denominator as u64,
+                )
+                .simplified(),
+            ));
+        }
+    }
+    None
+}
+
+/// The largest number of bits a value can be shifted by
+const MAX_SHIFT: u32 = 1 << 16;
+
+/// Get a shift amount, if it is non-negative and not too large
+fn shift_amount(shift: &BigInt) -> Option<u32> {
+    u32::try_from(shift)
+        .ok()
+        .filter(|shift| *shift <= MAX_SHIFT)
+}
+
+/// The largest integer whose factorial is computed exactly, so huge factorials stay symbolic
+pub(super) const MAX_EXACT_FACTORIAL: i64 = 10_000;
+
+/// Compute the factorial of a non-negative integer exactly
+pub(super) fn factorial(n: i64) -> RationalValue {
+    let product =
+        (1..=n.max(0) as u64).fold(BigUint::from(1_u32), |product, factor| product * factor);
+    RationalValue::new(Sign::Positive, product, 1_u32)
+}
+
+/// The most factors multiplied when computing a binomial coefficient exactly
+const MAX_BINOMIAL_FACTORS: i64 = 100_000;
+
+/// Compute a binomial coefficient of integers exactly, if it doesn't take too many factors
+fn binomial(n: i64, k: i64) -> Option<RationalValue> {
+    if k < 0 {
+        return Some(RationalValue::zero());
+    }
+
+    // Negative tops are reflected, as (n choose k) is (-1)^k (k - n - 1 choose k)
+    if n < 0 {
+        let value = binomial(k.checked_sub(n)?.checked_sub(1)?, k)?;
+        return Some(if k % 2 == 1 {
+            value.get_opposite()
+        } else {
+            value
+        });
+    }
+    if k > n {
+        return Some(RationalValue::zero());
+    }
+    let k = k.min(n - k);
+    if k > MAX_BINOMIAL_FACTORS {
+        return None;
+    }
+
+    // Each partial product is itself a binomial coefficient, so the division is exact
+    let product = (0..k).fold(BigUint::from(1_u32), |product, i| {
+        product * (n - i) as u64 / (i + 1) as u64
+    });
+    Some(RationalValue::new(Sign::Positive, product, 1_u32))
+}
+
+/// Count the ordered selections of k items from n exactly, as the falling factorial of n
+///
+/// Returns `None` for negative k, or if it would take too many factors
+fn permutations(n: i64, k: i64) -> Option<RationalValue> {
+    if !(0..=MAX_BINOMIAL_FACTORS).contains(&k) {
+        return None;
+    }
+    let product = (0..k).fold(BigInt::from(1), |product, i| product * (n - i));
+    Some(RationalValue::from(product))
+}
+
+/// An expression applying a function to its arguments
+#[derive(Debug, Clone)]
+pub struct FunctionExpression {
+    function: Function,
+    arguments: Vec<Box<dyn Expression>>,
+    span: Option<Range<usize>>,
+}
+
+impl FunctionExpression {
+    pub fn new(function: Function, arguments: Vec<Box<dyn Expression>>) -> Self {
+        Self {
+            function,
+            arguments,
+            span: None,
+        }
+    }
+
+    pub fn get_function(&self) -> Function {
+        self.function
+    }
+
+    pub fn get_arguments(&self) -> &[Box<dyn Expression>] {
+        &self.arguments
+    }
+
+    /// Get the definition of this function, which is built in unless the registry overrides it
+    fn definition(&self) -> FunctionDefinition {
+        FunctionRegistry::lookup(self.function.name())
+            .filter(|definition| definition.get_arity() == self.arguments.len())
+            .unwrap_or_else(|| FunctionDefinition::builtin(self.function))
+    }
+
+    /// Rewrite a built-in function applied to simplified arguments, if a rule applies
+    pub(super) fn rewrite(
+        function: Function,
+        arguments: &[Box<dyn Expression>],
+    ) -> Option<Box<dyn Expression>> {
+        // Use the exact value at special arguments
+        if let Some(exact) = arguments
+            .iter()
+            .map(|argument| {
+                argument
+                    .downcast_ref::<ValueExpression>()
+                    .map(|argument| argument.get_value())
+            })
+            .collect::<Option<Vec<_>>>()
+            .and_then(|values| function.exact(&values))
+        {
+            trace::rule("function", "exact value");
+            return Some(Box::new(ValueExpression::new(exact)));
+        }
+        FunctionExpression::new(function, arguments.to_vec()).expand_logarithm(arguments)
+    }
+
+    /// Build this logarithm with the same base of another argument
+    fn logarithm_of(&self, argument: Box<dyn Expression>) -> Box<dyn Expression> {
+        let mut arguments = self.arguments.clone();
+        *arguments.last_mut().unwrap() = argument;
+        Box::new(FunctionExpression::new(self.function, arguments))
+    }
+
+    /// Expand a logarithm of a power or of a product with a value, from its simplified arguments
+    ///
+    /// Products are only split when the logarithm of their coefficient is exact
+    fn expand_logarithm(&self, arguments: &[Box<dyn Expression>]) -> Option<Box<dyn Expression>> {
+        if !matches!(self.function, Function::Ln | Function::Log) {
+            return None;
+        }
+        let logarithm = FunctionExpression::new(self.function, arguments.to_vec());
+        let argument = arguments.last()?;
+
+        // The logarithm of a power is the exponent times the logarithm of the base
+        if let Some(power) = argument.downcast_ref::<PowerExpression>() {
+            let exponent = power
+                .get_exponent()
+                .downcast_ref::<ValueExpression>()
+                .filter(|exponent| exponent.get_value().is::<RationalValue>())?;
+            let even = exponent
+                .get_value()
+                .downcast_ref::<RationalValue>()
+                .and_then(|exponent| exponent.to_integer())
+                .is_some_and(|exponent| exponent.is_even());
+
+            // Even powers are positive for negative bases too, so only their magnitude counts
+            let base = dyn_clone::clone_box(power.get_base());
+            let base: Box<dyn Expression> = if even {
+                Box::new(AbsExpression::new(base))
+            } else {
+                base
+            };
+            trace::rule("function", "logarithm of a power");
+            return Some(
+                MultiplicationExpression::new(
+                    Box::new(exponent.clone()),
+                    logarithm.logarithm_of(base),
+                )
+                .simplified(),
+            );
+        }
+
+        // The logarithm of a product is the sum of the logarithms of its factors
+        let (coefficient, rest): (&dyn Expression, Box<dyn Expression>) =
+            if let Some(product) = argument.downcast_ref::<MultiplicationExpression>() {
+                (product.get_lhs(), dyn_clone::clone_box(product.get_rhs()))
+            } else if let Some(product) = argument.downcast_ref::<FactorsExpression>() {
+                let (first, rest) = product.get_factors().split_first()?;
+                (
+                    first.as_ref(),
+                    Box::new(FactorsExpression::new(rest.to_vec())),
+                )
+            } else {
+                return None;
+            };
+        let coefficient = coefficient.downcast_ref::<ValueExpression>()?;
+        if coefficient
+            .get_value()
+            .cmp(&RationalValue::zero())
+            .is_none_or(|ordering| ordering != Ordering::Greater)
+        {
+            return None;
+        }
+        let exact = logarithm
+            .logarithm_of(Box::new(coefficient.clone()))
+            .simplified();
+        if !exact.is::<ValueExpression>() {
+            return None;
+        }
+        trace::rule("function", "logarithm of a product");
+        Some(AdditionExpression::new(exact, logarithm.logarithm_of(rest)).simplified())
+    }
+}
+
+impl Expression for FunctionExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "function")
+        )
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        let arguments = self
+            .arguments
+            .iter()
+            .map(|argument| argument.simplified())
+            .collect::<Vec<_>>();
+
+        // Apply the rules of the registered definition
+        if let Some(result) = self.definition().simplify(&arguments) {
+            return result;
+        }
+        Box::new(FunctionExpression::new(self.function, arguments))
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        let values = self
+            .arguments
+            .iter()
+            .map(|argument| argument.evaluate(bindings))
+            .collect::<Vec<_>>();
+        self.definition().apply(&values)
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        let mut domain = IntervalUnionValue::all();
+        for argument in &self.arguments {
+            domain = domain.intersection(&argument.domain(variable)?);
+
+            // Poles can only be handled when the argument doesn't depend on the variable
+            if self.function.has_poles() && !linear_form(argument.as_ref(), variable)?.0.is_zero() {
+                return None;
+            }
+
+            // Restrict the argument to the accepted interval
+            if let Some(interval) = self.function.argument_interval() {
+                let bound = |bound: &Bound| match bound {
+                    Bound::Closed(value) => Some((Relation::LessEqual, value.clone())),
+                    Bound::Open(value) => Some((Relation::Less, value.clone())),
+                    Bound::Unbounded => None,
+                };
+                let mut relations: Vec<Box<dyn Expression>> = Vec::new();
+                if let Some((relation, lower)) = bound(interval.get_lower()) {
+                    relations.push(Box::new(RelationExpression::new(
+                        Box::new(ValueExpression::new(Box::new(lower))),
+                        relation,
+                        argument.clone(),
+                    )));
+                }
+                if let Some((relation, upper)) = bound(interval.get_upper()) {
+                    relations.push(Box::new(RelationExpression::new(
+                        argument.clone(),
+                        relation,
+                        Box::new(ValueExpression::new(Box::new(upper))),
+                    )));
+                }
+                let restriction = ConjunctionExpression::new(relations);
+                domain = domain.intersection(&solve(&restriction, variable)?);
+            }
+        }
+        Some(domain)
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        self.arguments
+            .iter()
+            .map(|argument| argument.as_ref())
+            .collect()
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        self.arguments.iter_mut().collect()
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        let arguments = self
+            .arguments
+            .iter()
+            .map(|argument| argument.format(options))
+            .collect::<Vec<_>>();
+        if let (Function::Log, [base, argument]) = (self.function, arguments.as_slice()) {
+            // Common logarithms leave out their base
+            return if base == "10" {
+                format!("\\log{{{}}}", argument)
+            } else {
+                format!("\\log_{{{}}}{{{}}}", base, argument)
+            };
+        }
+        if let (Function::Binomial, [n, k]) = (self.function, arguments.as_slice()) {
+            return format!("\\binom{{{}}}{{{}}}", n, k);
+        }
+        match (self.function, arguments.as_slice()) {
+            (Function::Floor, [argument]) => return format!("\\lfloor {} \\rfloor", argument),
+            (Function::Ceil, [argument]) => return format!("\\lceil {} \\rceil", argument),
+            _ => {}
+        }
+        if let [argument] = arguments.as_slice() {
+            format!("{}{{{}}}", self.function.latex(), argument)
+        } else {
+            format!(
+                "{}({})",
+                self.function.latex(),
+                arguments.join(&format!("{} ", options.get_locale().argument_separator()))
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::{
+            Bindings, Expression, Function, FunctionDefinition, FunctionExpression,
+            FunctionRegistry, NegationExpression,
+        },
+        parse::{parse_latex, parse_pairs},
+        value::{FloatValue, Value},
+    };
+
+    fn simplify(input: &str) -> String {
+        parse_pairs(parse_latex(input).unwrap())
+            .simplified()
+            .to_string()
+    }
+
+    #[test]
+    fn exact_inverse_trig_values() {
+        assert_eq!(simplify("\\arcsin{1/2}"), "\\pi/6");
+        assert_eq!(simplify("\\arctan{1}"), "\\pi/4");
+        assert_eq!(simplify("\\arccos(0) + \\arcsin{1}"), "\\pi");
+        assert_eq!(simplify("\\arctan{2}"), "\\arctan{2}");
+        assert_eq!(simplify("\\arcsin(2)"), "undefined");
+        assert_eq!(simplify("\\arccos{x}"), "\\arccos{x}");
+
+        let approximation = parse_pairs(parse_latex("\\arctan(2)").unwrap())
+            .evaluate(&Bindings::new())
+            .downcast_ref::<FloatValue>()
+            .unwrap()
+            .get_value();
+        assert!((approximation - 2_f64.atan()).abs() < 1e-15);
+        let value = parse_pairs(parse_latex("\\arccos(1.5)").unwrap()).evaluate(&Bindings::new());
+        assert_eq!(value.to_string(), "undefined");
+    }
+
+    #[test]
+    fn trig_functions() {
+        assert_eq!(simplify("\\sin(0) + \\cos{\\pi / 3}"), "1/2");
+        assert_eq!(simplify("\\tan{3\\pi / 4} + \\cos(\\pi)"), "-2");
+        assert_eq!(simplify("\\tan{\\pi / 2}"), "undefined");
+        assert_eq!(simplify("\\sin{x} + \\cos 1"), "(\\cos{1} + \\sin{x})");
+
+        let approximation = parse_pairs(parse_latex("\\sin(1)").unwrap())
+            .evaluate(&Bindings::new())
+            .downcast_ref::<FloatValue>()
+            .unwrap()
+            .get_value();
+        assert!((approximation - 1_f64.sin()).abs() < 1e-15);
+    }
+
+    #[test]
+    fn hyperbolic_functions() {
+        assert_eq!(simplify("\\cosh(0) + \\sinh{0} + \\tanh 0"), "1");
+        assert_eq!(simplify("\\sinh{x}"), "\\sinh{x}");
+
+        let approximation = parse_pairs(parse_latex("\\tanh(1/2)").unwrap())
+            .evaluate(&Bindings::new())
+            .downcast_ref::<FloatValue>()
+            .unwrap()
+            .get_value();
+        assert!((approximation - 0.5_f64.tanh()).abs() < 1e-15);
+    }
+
+    #[test]
+    fn registered_definitions() {
+        // Built-in functions are simplified by their definition in the registry, so it can be
+        // overridden, here to use that cosh is even
+        FunctionRegistry::register(
+            "cosh",
+            FunctionDefinition::new(1, |arguments| Some(arguments[0].cosh()))
+                .with_exact(|arguments| Function::Cosh.exact(arguments))
+                .with_simplify(|arguments| {
+                    match arguments[0].downcast_ref::<NegationExpression>() {
+                        Some(negation) => Some(
+                            FunctionExpression::new(
+                                Function::Cosh,
+                                vec![dyn_clone::clone_box(negation.get_argument())],
+                            )
+                            .simplified(),
+                        ),
+                        None => FunctionExpression::rewrite(Function::Cosh, arguments),
+                    }
+                }),
+        );
+        assert_eq!(simplify("\\cosh{-x}"), "\\cosh{x}");
+        assert_eq!(simplify("\\cosh(-0)"), "1");
+        assert_eq!(simplify("\\sinh{-x}"), "\\sinh{-x}");
+
+        let value = parse_pairs(parse_latex("\\cosh(-1)").unwrap()).evaluate(&Bindings::new());
+        assert_eq!(FloatValue::approximate(value.as_ref()), Some(1_f64.cosh()));
+        FunctionRegistry::register("cosh", FunctionDefinition::builtin(Function::Cosh));
+    }
+
+    #[test]
+    fn logarithms() {
+        assert_eq!(simplify("\\log_2 8 + \\log_{8}{2}"), "10/3");
+        assert_eq!(simplify("\\log(1/100) + \\ln(1) + \\ln{e}"), "-1");
+        assert_eq!(simplify("\\log_{3}{10}"), "\\log_{3}{10}");
+        assert_eq!(simplify("\\log{x}"), "\\log{x}");
+        assert_eq!(simplify("\\ln(0 - 1)"), "undefined");
+
+        // Powers and products with exact coefficients are expanded
+        assert_eq!(simplify("\\ln(x^3)"), "(3 * \\ln{x})");
+        assert_eq!(simplify("\\log_2(x^2)"), "(2 * \\log_{2}{|x|})");
+        assert_eq!(simplify("\\log_2(8x)"), "(\\log_{2}{x} + 3)");
+        assert_eq!(simplify("\\ln(e \\cdot x \\cdot y)"), "(\\ln{(x * y)} + 1)");
+        assert_eq!(simplify("\\log(3x)"), "\\log{(3 * x)}");
+
+        let approximation = parse_pairs(parse_latex("\\ln(2)").unwrap())
+            .evaluate(&Bindings::new())
+            .downcast_ref::<FloatValue>()
+            .unwrap()
+            .get_value();
+        assert!((approximation - std::f64::consts::LN_2).abs() < 1e-15);
+    }
+
+    #[test]
+    fn special_functions() {
+        assert_eq!(simplify("\\Gamma(5)"), "24");
+        assert_eq!(simplify("\\operatorname{B}(2, 3)"), "1/12");
+        assert_eq!(simplify("\\Gamma(1/2)"), "\\Gamma{1/2}");
+        assert_eq!(simplify("\\Gamma {3}"), "2");
+
+        // Without a bracketed argument `\Gamma` is the uppercase Greek variable
+        assert_eq!(simplify("\\Gamma + \\Gamma"), "(2 * \\Gamma)");
+        assert_eq!(simplify("\\Gamma x"), "(\\Gamma * x)");
+
+        // Huge arguments stay symbolic instead of computing enormous factorials
+        assert_eq!(simplify("\\Gamma(10000000)"), "\\Gamma{10000000}");
+        assert_eq!(
+            simplify("\\operatorname{B}(9223372036854775807, 2)"),
+            "\\operatorname{B}(9223372036854775807, 2)"
+        );
+
+        let approximation = parse_pairs(parse_latex("\\mathrm{erf}(1)").unwrap())
+            .evaluate(&Bindings::new())
+            .downcast_ref::<FloatValue>()
+            .unwrap()
+            .get_value();
+        assert!((approximation - 0.842_700_792_949_714_9).abs() < 1e-14);
+    }
+
+    #[test]
+    fn selections() {
+        assert_eq!(simplify("{}^{5}C_{2} + C(5, 3)"), "20");
+        assert_eq!(simplify("{}^5P_2"), "20");
+        assert_eq!(simplify("P(10, 3) - P(3, 4)"), "720");
+        assert_eq!(simplify("\\binom{40}{20}"), "137846528820");
+        assert_eq!(simplify("P(n, 2)"), "P(n, 2)");
+    }
+
+    #[test]
+    fn floor_and_ceiling() {
+        assert_eq!(simplify("\\lfloor 7/2 \\rfloor"), "3");
+        assert_eq!(
+            simplify("\\lfloor 0 - 7/2 \\rfloor + \\lceil 0 - 7/2 \\rceil"),
+            "-7"
+        );
+        assert_eq!(simplify("\\left\\lceil \\frac{1}{3} \\right\\rceil"), "1");
+        assert_eq!(
+            simplify("2\\lfloor x \\rfloor"),
+            "(2 * \\lfloor x \\rfloor)"
+        );
+
+        let value =
+            parse_pairs(parse_latex("\\lceil \\pi \\rceil").unwrap()).evaluate(&Bindings::new());
+        assert_eq!(FloatValue::approximate(value.as_ref()), Some(4.0));
+    }
+
+    #[test]
+    fn binomial_coefficients() {
+        assert_eq!(simplify("\\binom{5}{2}"), "10");
+        assert_eq!(simplify("\\binom{50}{25}"), "126410606437752");
+        assert_eq!(simplify("\\binom{3}{5}"), "0");
+        assert_eq!(simplify("\\binom{0 - 2}{3}"), "-4");
+        assert_eq!(simplify("\\binom{n}{2}"), "\\binom{n}{2}");
+        assert_eq!(simplify("\\dbinom{2 + 2}{1 + 1}"), "6");
+    }
+
+    #[test]
+    fn bitwise_operations() {
+        assert_eq!(simplify("12 \\& 10 \\oplus 1"), "9");
+        assert_eq!(simplify("\\lnot 5 \\mid 1 << 4"), "-6");
+        assert_eq!(simplify("\\operatorname{shr}(\\lnot 8, 1)"), "-5");
+        assert_eq!(simplify("\\operatorname{or}(1/2, 1)"), "undefined");
+    }
+
+    #[test]
+    fn restricted_domain() {
+        let expression = parse_pairs(parse_latex("\\arcsin{x / 2}").unwrap());
+        assert_eq!(expression.domain("x").unwrap().to_string(), "[-2, 2]");
+        let expression = parse_pairs(parse_latex("\\ln(x - 3)").unwrap());
+        assert_eq!(expression.domain("x").unwrap().to_string(), "(3, \\infty)");
+    }
+}
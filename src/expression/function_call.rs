@@ -0,0 +1,329 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    ops::Range,
+    sync::{Arc, RwLock},
+};
+
+use crate::{
+    format::FormatOptions,
+    trace,
+    value::{FloatValue, IntervalUnionValue, UndefinedValue, Value},
+};
+
+use super::{Bindings, Expression, Function, FunctionExpression, TupleExpression};
+
+lazy_static::lazy_static! {
+    static ref FUNCTIONS: RwLock<HashMap<String, FunctionDefinition>> = {
+        let mut functions = HashMap::new();
+        for function in Function::ALL {
+            functions.insert(function.name().to_string(), FunctionDefinition::builtin(function));
+        }
+        RwLock::new(functions)
+    };
+}
+
+/// A rule rewriting a call from its simplified arguments, or `None` if it doesn't apply
+pub type SimplifyRule =
+    Arc<dyn Fn(&[Box<dyn Expression>]) -> Option<Box<dyn Expression>> + Send + Sync>;
+
+/// A function approximating the result of a call from its arguments
+pub type NumericEvaluator = Arc<dyn Fn(&[f64]) -> Option<f64> + Send + Sync>;
+
+/// A function computing the exact result of a call from its arguments, or `None` if it isn't exact
+pub type ExactEvaluator = Arc<dyn Fn(&[&dyn Value]) -> Option<Box<dyn Value>> + Send + Sync>;
+
+/// How a function called by name is simplified and evaluated
+#[derive(Clone)]
+pub struct FunctionDefinition {
+    arity: usize,
+    simplify: Option<SimplifyRule>,
+    exact: Option<ExactEvaluator>,
+    evaluate: NumericEvaluator,
+}
+
+impl FunctionDefinition {
+    /// Construct a new function definition from its number of arguments and numeric evaluation
+    pub fn new(
+        arity: usize,
+        evaluate: impl Fn(&[f64]) -> Option<f64> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            arity,
+            simplify: None,
+            exact: None,
+            evaluate: Arc::new(evaluate),
+        }
+    }
+
+    /// Set the rule rewriting calls to this function when simplifying
+    pub fn with_simplify(
+        self,
+        simplify: impl Fn(&[Box<dyn Expression>]) -> Option<Box<dyn Expression>> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            simplify: Some(Arc::new(simplify)),
+            ..self
+        }
+    }
+
+    /// Set the exact evaluation of this function, which is preferred over its approximation
+    pub fn with_exact(
+        self,
+        exact: impl Fn(&[&dyn Value]) -> Option<Box<dyn Value>> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            exact: Some(Arc::new(exact)),
+            ..self
+        }
+    }
+
+    /// Construct the definition of a built-in function, with its exact values and rewrite rules
+    pub(super) fn builtin(function: Function) -> Self {
+        Self::new(function.arity(), move |arguments| {
+            function.approximate(arguments)
+        })
+        .with_exact(move |arguments| function.exact(arguments))
+        .with_simplify(move |arguments| FunctionExpression::rewrite(function, arguments))
+    }
+
+    /// Get the number of arguments this function takes
+    pub fn get_arity(&self) -> usize {
+        self.arity
+    }
+
+    /// Rewrite a call to this function from its simplified arguments, if a rule applies
+    pub fn simplify(&self, arguments: &[Box<dyn Expression>]) -> Option<Box<dyn Expression>> {
+        self.simplify.as_ref()?(arguments)
+    }
+
+    /// Approximate the result of this function
+    pub fn evaluate(&self, arguments: &[f64]) -> Option<f64> {
+        (self.evaluate)(arguments)
+    }
+
+    /// Apply this function to values, exactly if possible and approximately otherwise
+    pub(super) fn apply(&self, arguments: &[Box<dyn Value>]) -> Box<dyn Value> {
+        let arguments = arguments
+            .iter()
+            .map(|argument| argument.as_ref())
+            .collect::<Vec<_>>();
+        if let Some(exact) = self.exact.as_ref().and_then(|exact| exact(&arguments)) {
+            return exact;
+        }
+        arguments
+            .iter()
+            .map(|argument| FloatValue::approximate(*argument))
+            .collect::<Option<Vec<_>>>()
+            .and_then(|arguments| self.evaluate(&arguments))
+            .filter(|value| value.is_finite())
+            .map(|value| Box::new(FloatValue::new(value)) as Box<dyn Value>)
+            .unwrap_or_else(|| Box::new(UndefinedValue::new()))
+    }
+}
+
+impl fmt::Debug for FunctionDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FunctionDefinition")
+            .field("arity", &self.arity)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The functions that can be called by name, shared by built-in and embedder defined functions
+pub struct FunctionRegistry;
+
+impl FunctionRegistry {
+    /// Register a function under a name, replacing any function already registered with it
+    pub fn register(name: impl Into<String>, definition: FunctionDefinition) {
+        FUNCTIONS.write().unwrap().insert(name.into(), definition);
+    }
+
+    /// Look up a registered function by name
+    pub fn lookup(name: &str) -> Option<FunctionDefinition> {
+        FUNCTIONS.read().unwrap().get(name).cloned()
+    }
+}
+
+/// An expression calling a function by name, like `f(x)`
+///
+/// Calls to functions in the [`FunctionRegistry`] are simplified and evaluated by their
+/// definition, and other calls are left symbolic
+#[derive(Debug, Clone)]
+pub struct FunctionCallExpression {
+    name: String,
+    arguments: TupleExpression,
+    span: Option<Range<usize>>,
+}
+
+impl FunctionCallExpression {
+    pub fn new(name: impl Into<String>, arguments: Vec<Box<dyn Expression>>) -> Self {
+        Self {
+            name: name.into(),
+            arguments: TupleExpression::new(arguments),
+            span: None,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_arguments(&self) -> &[Box<dyn Expression>] {
+        self.arguments.get_elements()
+    }
+
+    /// Get the definition of the called function, if it is registered with a matching arity
+    fn definition(&self) -> Option<FunctionDefinition> {
+        FunctionRegistry::lookup(&self.name)
+            .filter(|definition| definition.get_arity() == self.get_arguments().len())
+    }
+}
+
+impl Expression for FunctionCallExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "function call")
+        )
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        let arguments = self.arguments.simplified_elements().into_elements();
+
+        // Apply the rule of a registered function
+        if let Some(result) = self
+            .definition()
+            .and_then(|definition| definition.simplify(&arguments))
+        {
+            trace::rule("function call", "registered rule");
+            return result;
+        }
+        Box::new(FunctionCallExpression::new(self.name.clone(), arguments))
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        // Only registered functions can be evaluated
+        let Some(definition) = self.definition() else {
+            return Box::new(UndefinedValue::new());
+        };
+        let values = self
+            .get_arguments()
+            .iter()
+            .map(|argument| argument.evaluate(bindings))
+            .collect::<Vec<_>>();
+        definition.apply(&values)
+    }
+
+    fn domain(&self, _variable: &str) -> Option<IntervalUnionValue> {
+        None
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        self.arguments.get_children()
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        self.arguments.get_children_mut()
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        // Names longer than a variable's are written upright
+        let name = if self.name.len() > 1 && self.name.chars().all(|c| c.is_ascii_alphabetic()) {
+            format!("\\operatorname{{{}}}", self.name)
+        } else {
+            self.name.clone()
+        };
+        format!("{}{}", name, self.arguments.format(options))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::{AbsExpression, Bindings, Expression},
+        parse::{parse, ParserOptions},
+        value::FloatValue,
+    };
+
+    use super::{FunctionCallExpression, FunctionDefinition, FunctionRegistry};
+
+    #[test]
+    fn function_calls() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap();
+        let call = parsed("g_1(x + 1, \\frac{4}{2})").simplified();
+        let call = call.downcast_ref::<FunctionCallExpression>().unwrap();
+        assert_eq!(call.get_name(), "g_1");
+        assert_eq!(call.to_string(), "g_1((x + 1), 2)");
+
+        // Round trips, and binds like any other primary
+        assert_eq!(parsed("f(x)^2").to_string(), "(f(x) ^ 2)");
+        assert_eq!(parsed("f(x)").to_string(), parsed("f(x)").to_string());
+        assert_eq!(parsed("2h(t)").to_string(), "(2 * h(t))");
+        assert_eq!(parsed("Q(n, k)").to_string(), "Q(n, k)");
+
+        // Names that aren't built in can be written upright
+        let call = parsed("\\operatorname{lcm}(4, 6)");
+        let call = call.downcast_ref::<FunctionCallExpression>().unwrap();
+        assert_eq!(call.get_name(), "lcm");
+        assert_eq!(call.to_string(), "\\operatorname{lcm}(4, 6)");
+        assert_eq!(
+            parsed("\\operatorname{erf}(x)").to_string(),
+            "\\operatorname{erf}{x}"
+        );
+
+        // Other letters before a single bracketed group still multiply
+        assert_eq!(parsed("x(y + 1)").to_string(), "(x * (y + 1))");
+        assert_eq!(parsed("k(x)").to_string(), "(k * x)");
+        assert_eq!(parsed("x(3)^2").to_string(), "(x * (3 ^ 2))");
+        assert_eq!(parsed("2^x(3)").to_string(), "((2 ^ x) * 3)");
+        assert_eq!(parsed("x(3)!").to_string(), "(x * 3!)");
+
+        // Registered functions are simplified and evaluated by their definition
+        FunctionRegistry::register(
+            "hypot",
+            FunctionDefinition::new(2, |arguments| Some(arguments[0].hypot(arguments[1])))
+                .with_simplify(|arguments| {
+                    (arguments[1].to_string() == "0").then(|| {
+                        Box::new(AbsExpression::new(arguments[0].clone())) as Box<dyn Expression>
+                    })
+                }),
+        );
+        assert_eq!(
+            parsed("\\operatorname{hypot}(x, 2 - 2)")
+                .simplified()
+                .to_string(),
+            "|x|"
+        );
+        let value = parsed("\\operatorname{hypot}(3, 4)").evaluate(&Bindings::new());
+        assert_eq!(FloatValue::approximate(value.as_ref()), Some(5.0));
+        assert_eq!(
+            parsed("\\operatorname{hypot}(3)").simplified().to_string(),
+            "\\operatorname{hypot}(3)"
+        );
+
+        // Built-in functions share the registry
+        let call = FunctionCallExpression::new("sin", vec![parsed("0")]);
+        assert_eq!(call.simplified().to_string(), "0");
+        assert!(FunctionRegistry::lookup("cosh").is_some());
+
+        // The letters that name functions can be chosen
+        let options = ParserOptions::default().with_function_letters("fk");
+        let parsed = |input: &str| parse(input, &options).unwrap();
+        assert_eq!(parsed("k(x)").to_string(), "k(x)");
+        assert_eq!(parsed("g(x)").to_string(), "(g * x)");
+        assert_eq!(parsed("g(x, y)").to_string(), "g(x, y)");
+        assert!(!options.is_function_letter('h'));
+    }
+}
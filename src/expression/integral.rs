@@ -0,0 +1,174 @@
+use std::ops::Range;
+
+use crate::{
+    format::FormatOptions,
+    value::{IntervalUnionValue, UndefinedValue, Value},
+};
+
+use super::{Bindings, Expression};
+
+/// An integral expression, like `\int_{0}^{1} x \, dx`
+#[derive(Debug, Clone)]
+pub struct IntegralExpression {
+    integrand: Box<dyn Expression>,
+    variable: String,
+    bounds: Option<(Box<dyn Expression>, Box<dyn Expression>)>,
+    span: Option<Range<usize>>,
+}
+
+impl IntegralExpression {
+    /// Construct a new indefinite integral
+    pub fn new(integrand: Box<dyn Expression>, variable: impl Into<String>) -> Self {
+        Self {
+            integrand,
+            variable: variable.into(),
+            bounds: None,
+            span: None,
+        }
+    }
+
+    /// Set the lower and upper bounds, making this a definite integral
+    pub fn with_bounds(self, lower: Box<dyn Expression>, upper: Box<dyn Expression>) -> Self {
+        Self {
+            bounds: Some((lower, upper)),
+            ..self
+        }
+    }
+
+    pub fn get_integrand(&self) -> &dyn Expression {
+        self.integrand.as_ref()
+    }
+
+    pub fn get_variable(&self) -> &str {
+        &self.variable
+    }
+
+    pub fn get_bounds(&self) -> Option<(&dyn Expression, &dyn Expression)> {
+        self.bounds
+            .as_ref()
+            .map(|(lower, upper)| (lower.as_ref(), upper.as_ref()))
+    }
+}
+
+impl Expression for IntegralExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "integral")
+        )
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        let integral = IntegralExpression::new(self.integrand.simplified(), self.variable.clone());
+        match &self.bounds {
+            Some((lower, upper)) => {
+                Box::new(integral.with_bounds(lower.simplified(), upper.simplified()))
+            }
+            None => Box::new(integral),
+        }
+    }
+
+    fn evaluate(&self, _bindings: &Bindings) -> Box<dyn Value> {
+        // Integrals can't be evaluated until there's an integration engine
+        Box::new(UndefinedValue::new())
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        // The integration variable isn't free outside of the integral
+        let (lower, upper) = self.bounds.as_ref()?;
+        if variable == self.variable {
+            return None;
+        }
+        Some(
+            lower
+                .domain(variable)?
+                .intersection(&upper.domain(variable)?)
+                .intersection(&self.integrand.domain(variable)?),
+        )
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        let mut children = vec![self.integrand.as_ref()];
+        if let Some((lower, upper)) = &self.bounds {
+            children.extend([lower.as_ref(), upper.as_ref()]);
+        }
+        children
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        let mut children = vec![&mut self.integrand];
+        if let Some((lower, upper)) = &mut self.bounds {
+            children.extend([lower, upper]);
+        }
+        children
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        let bounds = match &self.bounds {
+            Some((lower, upper)) => {
+                format!(
+                    "_{{{}}}^{{{}}}",
+                    lower.format(options),
+                    upper.format(options)
+                )
+            }
+            None => String::new(),
+        };
+        format!(
+            "\\int{}{{{}}} \\, d{}",
+            bounds,
+            self.integrand.format(options),
+            self.variable
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::Expression as _,
+        parse::{parse, ParserOptions},
+    };
+
+    use super::IntegralExpression;
+
+    #[test]
+    fn integrals() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap();
+        let integral = parsed("\\int_0^{1 + 1} x^2 + 3 \\,dx");
+        let integral = integral.downcast_ref::<IntegralExpression>().unwrap();
+        assert_eq!(integral.get_variable(), "x");
+        assert_eq!(integral.get_integrand().to_string(), "((x ^ 2) + 3)");
+        assert_eq!(
+            integral.simplified().to_string(),
+            "\\int_{0}^{2}{((x ^ 2) + 3)} \\, dx"
+        );
+
+        // Printed integrals parse back to the same integral
+        let printed = integral.simplified().to_string();
+        assert_eq!(parsed(&printed).to_string(), printed);
+        let printed = parsed("\\int_{a}^{b} \\int x y \\, dx \\, dy").to_string();
+        assert_eq!(parsed(&printed).to_string(), printed);
+
+        // Indefinite integrals have no bounds, and the differential can be upright
+        let integral = parsed("\\int \\sin{t} \\mathrm{d}t");
+        let integral = integral.downcast_ref::<IntegralExpression>().unwrap();
+        assert!(integral.get_bounds().is_none());
+        assert_eq!(integral.get_variable(), "t");
+        assert_eq!(
+            parsed("2\\int y dy + 1").to_string(),
+            "((2 * \\int{y} \\, dy) + 1)"
+        );
+    }
+}
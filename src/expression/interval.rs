@@ -0,0 +1,185 @@
+use std::ops::Range;
+
+use crate::{
+    format::FormatOptions,
+    trace,
+    value::{
+        Bound, InfinityValue, IntervalUnionValue, IntervalValue, RationalValue, Sign,
+        UndefinedValue, Value,
+    },
+};
+
+use super::{Bindings, Expression, ValueExpression};
+
+/// An interval written with bracket notation, like `[a, b)`
+#[derive(Debug, Clone)]
+pub struct IntervalExpression {
+    lower: Box<dyn Expression>,
+    upper: Box<dyn Expression>,
+    lower_closed: bool,
+    upper_closed: bool,
+    span: Option<Range<usize>>,
+}
+
+impl IntervalExpression {
+    /// Construct a new closed interval expression
+    pub fn new(lower: Box<dyn Expression>, upper: Box<dyn Expression>) -> Self {
+        Self {
+            lower,
+            upper,
+            lower_closed: true,
+            upper_closed: true,
+            span: None,
+        }
+    }
+
+    /// Set whether the lower and upper ends are included in the interval
+    pub fn with_closed(self, lower_closed: bool, upper_closed: bool) -> Self {
+        Self {
+            lower_closed,
+            upper_closed,
+            ..self
+        }
+    }
+
+    pub fn get_lower(&self) -> &dyn Expression {
+        self.lower.as_ref()
+    }
+
+    pub fn get_upper(&self) -> &dyn Expression {
+        self.upper.as_ref()
+    }
+
+    pub fn is_lower_closed(&self) -> bool {
+        self.lower_closed
+    }
+
+    pub fn is_upper_closed(&self) -> bool {
+        self.upper_closed
+    }
+
+    /// Build the interval value of the evaluated ends, if they are rational or infinite
+    fn interval(&self, lower: &dyn Value, upper: &dyn Value) -> Option<IntervalValue> {
+        Some(IntervalValue::new(
+            bound(lower, self.lower_closed, Sign::Negative)?,
+            bound(upper, self.upper_closed, Sign::Positive)?,
+        ))
+    }
+}
+
+/// Get the bound at one end of an interval, where only the infinity on that side is unbounded
+fn bound(value: &dyn Value, closed: bool, side: Sign) -> Option<Bound> {
+    if let Some(value) = value.downcast_ref::<RationalValue>() {
+        Some(if closed {
+            Bound::Closed(value.clone())
+        } else {
+            Bound::Open(value.clone())
+        })
+    } else {
+        value
+            .downcast_ref::<InfinityValue>()
+            .filter(|infinity| *infinity.get_sign() == side)
+            .map(|_| Bound::Unbounded)
+    }
+}
+
+impl Expression for IntervalExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "interval")
+        )
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        let lower = self.lower.simplified();
+        let upper = self.upper.simplified();
+
+        // Fold an interval between values into an interval value
+        if let (Some(lower_value), Some(upper_value)) = (
+            lower.downcast_ref::<ValueExpression>(),
+            upper.downcast_ref::<ValueExpression>(),
+        ) {
+            if let Some(interval) = self.interval(lower_value.get_value(), upper_value.get_value())
+            {
+                trace::rule("interval", "fold value");
+                return Box::new(ValueExpression::new(Box::new(interval)));
+            }
+        }
+        Box::new(
+            IntervalExpression::new(lower, upper).with_closed(self.lower_closed, self.upper_closed),
+        )
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        match self.interval(
+            self.lower.evaluate(bindings).as_ref(),
+            self.upper.evaluate(bindings).as_ref(),
+        ) {
+            Some(interval) => Box::new(interval),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        Some(
+            self.lower
+                .domain(variable)?
+                .intersection(&self.upper.domain(variable)?),
+        )
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        vec![self.lower.as_ref(), self.upper.as_ref()]
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        vec![&mut self.lower, &mut self.upper]
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        format!(
+            "{}{}{} {}{}",
+            if self.lower_closed { '[' } else { '(' },
+            self.lower.format(options),
+            options.get_locale().argument_separator(),
+            self.upper.format(options),
+            if self.upper_closed { ']' } else { ')' }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{parse, ParserOptions};
+
+    #[test]
+    fn intervals() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap();
+        assert_eq!(parsed("[1, x)").to_string(), "[1, x)");
+        assert_eq!(
+            parsed("(0, \\frac{1}{2}]").simplified().to_string(),
+            "(0, 1/2]"
+        );
+        assert_eq!(
+            parsed("(-\\infty, 3]").simplified().to_string(),
+            "(-\\infty, 3]"
+        );
+        assert_eq!(parsed("[1, 2] + [3, 4]").simplified().to_string(), "[4, 6]");
+        assert_eq!(parsed("[2, 1]").simplified().to_string(), "\\emptyset");
+
+        // Brackets around a single expression are still a group
+        assert_eq!(parsed("[1 + 2]").simplified().to_string(), "3");
+    }
+}
@@ -0,0 +1,193 @@
+use std::ops::Range;
+
+use crate::{
+    format::FormatOptions,
+    value::{IntervalUnionValue, UndefinedValue, Value},
+};
+
+use super::{Bindings, Expression};
+
+/// The point a limit's variable approaches
+#[derive(Debug, Clone)]
+pub enum LimitTarget {
+    /// A finite point, like `a`
+    Finite(Box<dyn Expression>),
+    /// `\infty`
+    PositiveInfinity,
+    /// `-\infty`
+    NegativeInfinity,
+}
+
+/// Which side a limit's variable approaches its point from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitDirection {
+    /// From both sides, like `a`
+    Both,
+    /// From below, like `a^-`
+    Below,
+    /// From above, like `a^+`
+    Above,
+}
+
+/// A limit expression, like `\lim_{x \to 0} \frac{\sin{x}}{x}`
+#[derive(Debug, Clone)]
+pub struct LimitExpression {
+    variable: String,
+    target: LimitTarget,
+    direction: LimitDirection,
+    body: Box<dyn Expression>,
+    span: Option<Range<usize>>,
+}
+
+impl LimitExpression {
+    /// Construct a new two-sided limit
+    pub fn new(
+        variable: impl Into<String>,
+        target: LimitTarget,
+        body: Box<dyn Expression>,
+    ) -> Self {
+        Self {
+            variable: variable.into(),
+            target,
+            direction: LimitDirection::Both,
+            body,
+            span: None,
+        }
+    }
+
+    /// Set the side the variable approaches its point from
+    pub fn with_direction(self, direction: LimitDirection) -> Self {
+        Self { direction, ..self }
+    }
+
+    pub fn get_variable(&self) -> &str {
+        &self.variable
+    }
+
+    pub fn get_target(&self) -> &LimitTarget {
+        &self.target
+    }
+
+    pub fn get_direction(&self) -> LimitDirection {
+        self.direction
+    }
+
+    pub fn get_body(&self) -> &dyn Expression {
+        self.body.as_ref()
+    }
+}
+
+impl Expression for LimitExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", name = "simplify", skip_all, fields(node = "limit"))
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        let target = match &self.target {
+            LimitTarget::Finite(point) => LimitTarget::Finite(point.simplified()),
+            target => target.clone(),
+        };
+        Box::new(
+            LimitExpression::new(self.variable.clone(), target, self.body.simplified())
+                .with_direction(self.direction),
+        )
+    }
+
+    fn evaluate(&self, _bindings: &Bindings) -> Box<dyn Value> {
+        // Limits can't be evaluated until there's a limit engine
+        Box::new(UndefinedValue::new())
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        // The limit variable isn't free outside of the limit
+        if variable == self.variable {
+            return None;
+        }
+        let domain = self.body.domain(variable)?;
+        match &self.target {
+            LimitTarget::Finite(point) => Some(domain.intersection(&point.domain(variable)?)),
+            _ => Some(domain),
+        }
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        match &self.target {
+            LimitTarget::Finite(point) => vec![point.as_ref(), self.body.as_ref()],
+            _ => vec![self.body.as_ref()],
+        }
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        match &mut self.target {
+            LimitTarget::Finite(point) => vec![point, &mut self.body],
+            _ => vec![&mut self.body],
+        }
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        let target = match &self.target {
+            LimitTarget::Finite(point) => point.format(options),
+            LimitTarget::PositiveInfinity => "\\infty".to_string(),
+            LimitTarget::NegativeInfinity => "-\\infty".to_string(),
+        };
+        let side = match self.direction {
+            LimitDirection::Both => "",
+            LimitDirection::Below => "^{-}",
+            LimitDirection::Above => "^{+}",
+        };
+        format!(
+            "\\lim_{{{} \\to {}{}}}{{{}}}",
+            self.variable,
+            target,
+            side,
+            self.body.format(options)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::Expression as _,
+        parse::{parse, ParserOptions},
+    };
+
+    use super::{LimitDirection, LimitExpression, LimitTarget};
+
+    #[test]
+    fn limits() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap();
+        let limit = parsed("\\lim_{x \\to 0^+} \\frac{1}{x}");
+        let limit = limit.downcast_ref::<LimitExpression>().unwrap();
+        assert_eq!(limit.get_variable(), "x");
+        assert_eq!(limit.get_direction(), LimitDirection::Above);
+        assert_eq!(limit.get_body().to_string(), "(1 / x)");
+        assert_eq!(limit.to_string(), "\\lim_{x \\to 0^{+}}{(1 / x)}");
+
+        let limit = parsed("\\lim_{n \\rightarrow -\\infty} 2^n + 1");
+        assert_eq!(limit.to_string(), "(\\lim_{n \\to -\\infty}{(2 ^ n)} + 1)");
+        assert_eq!(
+            parsed("\\lim\\limits_{n → ∞} \\frac{1}{n}").to_string(),
+            "\\lim_{n \\to \\infty}{(1 / n)}"
+        );
+
+        // Printed limits parse back to the same limit
+        let printed = parsed("\\lim_{x \\to 2^-} x^2").to_string();
+        assert_eq!(parsed(&printed).to_string(), printed);
+        let limit = parsed("\\lim_{t \\to 1 + 1^{-}} t").simplified();
+        let limit = limit.downcast_ref::<LimitExpression>().unwrap();
+        assert_eq!(limit.get_direction(), LimitDirection::Below);
+        assert!(
+            matches!(limit.get_target(), LimitTarget::Finite(point) if point.to_string() == "2")
+        );
+    }
+}
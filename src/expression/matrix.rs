@@ -0,0 +1,320 @@
+use std::ops::Range;
+
+use crate::{
+    format::FormatOptions,
+    value::{IntervalUnionValue, MatrixValue, RationalValue, UndefinedValue, Value},
+};
+
+use super::{Bindings, Expression, ValueExpression};
+
+/// The delimiters a matrix is written with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixDelimiter {
+    /// `pmatrix`, like `(a)`
+    Parentheses,
+    /// `bmatrix`, like `[a]`
+    Brackets,
+    /// `vmatrix`, like `|a|`
+    Bars,
+}
+
+impl MatrixDelimiter {
+    /// Get the name of the environment of this delimiter
+    pub fn get_environment(&self) -> &'static str {
+        match self {
+            MatrixDelimiter::Parentheses => "pmatrix",
+            MatrixDelimiter::Brackets => "bmatrix",
+            MatrixDelimiter::Bars => "vmatrix",
+        }
+    }
+}
+
+/// A matrix expression, with a rectangular grid of entries
+#[derive(Debug, Clone)]
+pub struct MatrixExpression {
+    rows: Vec<Vec<Box<dyn Expression>>>,
+    delimiter: MatrixDelimiter,
+    span: Option<Range<usize>>,
+}
+
+impl MatrixExpression {
+    /// Construct a new matrix from its rows, which must all have the same length
+    pub fn new(rows: Vec<Vec<Box<dyn Expression>>>, delimiter: MatrixDelimiter) -> Self {
+        assert!(
+            rows.windows(2).all(|rows| rows[0].len() == rows[1].len()),
+            "The rows of a matrix must all have the same length!"
+        );
+        Self {
+            rows,
+            delimiter,
+            span: None,
+        }
+    }
+
+    pub fn get_rows(&self) -> &[Vec<Box<dyn Expression>>] {
+        &self.rows
+    }
+
+    pub fn get_delimiter(&self) -> MatrixDelimiter {
+        self.delimiter
+    }
+
+    /// Get the number of rows and columns of this matrix
+    pub fn get_shape(&self) -> (usize, usize) {
+        (self.rows.len(), self.rows.first().map_or(0, Vec::len))
+    }
+}
+
+/// Get the values of a matrix's entries, if they are all values
+fn entry_values(matrix: &MatrixExpression) -> Option<Vec<Vec<&dyn Value>>> {
+    matrix
+        .rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|entry| {
+                    entry
+                        .downcast_ref::<ValueExpression>()
+                        .map(|entry| entry.get_value())
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Build a matrix of values, or `None` if an entry isn't exact
+fn value_matrix(
+    rows: Vec<Vec<Box<dyn Value>>>,
+    delimiter: MatrixDelimiter,
+) -> Option<Box<dyn Expression>> {
+    if rows
+        .iter()
+        .flatten()
+        .any(|entry| entry.is::<UndefinedValue>())
+    {
+        return None;
+    }
+    let rows = rows
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|entry| Box::new(ValueExpression::new(entry)) as Box<dyn Expression>)
+                .collect()
+        })
+        .collect();
+    Some(Box::new(MatrixExpression::new(rows, delimiter)))
+}
+
+/// An undefined result, for combining matrices of the wrong shapes
+fn undefined() -> Box<dyn Expression> {
+    Box::new(ValueExpression::new(Box::new(UndefinedValue::new())))
+}
+
+/// Add two matrices of values entry by entry, which is undefined if their shapes differ
+pub(super) fn fold_sum(
+    lhs: &MatrixExpression,
+    rhs: &MatrixExpression,
+) -> Option<Box<dyn Expression>> {
+    let (lhs_values, rhs_values) = (entry_values(lhs)?, entry_values(rhs)?);
+    if lhs.get_shape() != rhs.get_shape() {
+        return Some(undefined());
+    }
+    let rows = lhs_values
+        .iter()
+        .zip(&rhs_values)
+        .map(|(lhs, rhs)| {
+            lhs.iter()
+                .zip(rhs)
+                .map(|(lhs, rhs)| lhs.add(*rhs))
+                .collect()
+        })
+        .collect();
+    value_matrix(rows, lhs.delimiter)
+}
+
+/// Multiply two matrices of values, which is undefined unless the columns of the first match the
+/// rows of the second
+pub(super) fn fold_product(
+    lhs: &MatrixExpression,
+    rhs: &MatrixExpression,
+) -> Option<Box<dyn Expression>> {
+    let (lhs_values, rhs_values) = (entry_values(lhs)?, entry_values(rhs)?);
+    let ((rows, inner), (rhs_rows, columns)) = (lhs.get_shape(), rhs.get_shape());
+    if inner != rhs_rows || inner == 0 {
+        return Some(undefined());
+    }
+    let rows = (0..rows)
+        .map(|row| {
+            (0..columns)
+                .map(|column| {
+                    (1..inner).fold(lhs_values[row][0].mul(rhs_values[0][column]), |sum, k| {
+                        sum.add(lhs_values[row][k].mul(rhs_values[k][column]).as_ref())
+                    })
+                })
+                .collect()
+        })
+        .collect();
+    value_matrix(rows, lhs.delimiter)
+}
+
+/// Multiply each entry of a matrix of values by a value
+pub(super) fn fold_scale(
+    scalar: &dyn Value,
+    matrix: &MatrixExpression,
+) -> Option<Box<dyn Expression>> {
+    let rows = entry_values(matrix)?
+        .iter()
+        .map(|row| row.iter().map(|entry| scalar.mul(*entry)).collect())
+        .collect();
+    value_matrix(rows, matrix.delimiter)
+}
+
+impl Expression for MatrixExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", name = "simplify", skip_all, fields(node = "matrix"))
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        // Simplify each entry on its own
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(|entry| entry.simplified()).collect())
+            .collect();
+        Box::new(MatrixExpression::new(rows, self.delimiter))
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        // Only matrices of exact rational entries have a value
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|entry| {
+                        entry
+                            .evaluate(bindings)
+                            .downcast_ref::<RationalValue>()
+                            .cloned()
+                    })
+                    .collect()
+            })
+            .collect::<Option<_>>();
+        match rows {
+            Some(rows) => Box::new(MatrixValue::new(rows)),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        self.rows
+            .iter()
+            .flatten()
+            .map(|entry| entry.domain(variable))
+            .reduce(|domain, entry| Some(domain?.intersection(&entry?)))
+            .flatten()
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        self.rows
+            .iter()
+            .flatten()
+            .map(|entry| entry.as_ref())
+            .collect()
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        self.rows.iter_mut().flatten().collect()
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|entry| entry.format(options))
+                    .collect::<Vec<_>>()
+                    .join(" & ")
+            })
+            .collect::<Vec<_>>()
+            .join(" \\\\ ");
+        let environment = self.delimiter.get_environment();
+        format!(
+            "\\begin{{{}}} {} \\end{{{}}}",
+            environment, rows, environment
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::Expression as _,
+        parse::{parse, ParseError, ParserOptions},
+    };
+
+    use super::{MatrixDelimiter, MatrixExpression};
+
+    #[test]
+    fn matrices() {
+        let options = ParserOptions::default();
+        let matrix = parse(
+            "\\begin{bmatrix} 1 + 1 & x \\\\ \\frac{6}{4} & 2x - x \\\\ \\end{bmatrix}",
+            &options,
+        )
+        .unwrap()
+        .simplified();
+        let matrix = matrix.downcast_ref::<MatrixExpression>().unwrap();
+        assert_eq!(matrix.get_shape(), (2, 2));
+        assert_eq!(matrix.get_delimiter(), MatrixDelimiter::Brackets);
+        assert_eq!(
+            matrix.to_string(),
+            "\\begin{bmatrix} 2 & x \\\\ 3/2 & x \\end{bmatrix}"
+        );
+
+        // Rows must have the same length, and the environments must match
+        assert!(matches!(
+            parse("\\begin{pmatrix} 1 & 2 \\\\ 3 \\end{pmatrix}", &options),
+            Err(ParseError::RaggedMatrix {
+                position: 25,
+                columns: 1,
+                expected: 2
+            })
+        ));
+        assert!(parse("\\begin{vmatrix} 1 \\end{pmatrix}", &options).is_err());
+
+        // Matrices of values are added and multiplied when their shapes fit
+        let simplified = |input: &str| parse(input, &options).unwrap().simplified().to_string();
+        assert_eq!(
+            simplified(
+                "\\begin{pmatrix} 1 & 2 \\end{pmatrix} + \\begin{pmatrix} 3 & 1/2 \\end{pmatrix}"
+            ),
+            "\\begin{pmatrix} 4 & 5/2 \\end{pmatrix}"
+        );
+        assert_eq!(
+            simplified("\\begin{bmatrix} 1 & 2 \\\\ 3 & 4 \\end{bmatrix} \\cdot \\begin{bmatrix} 0 \\\\ 1 \\end{bmatrix}"),
+            "\\begin{bmatrix} 2 \\\\ 4 \\end{bmatrix}"
+        );
+        assert_eq!(
+            simplified("2 \\begin{pmatrix} 1 & -3 \\end{pmatrix}"),
+            "\\begin{pmatrix} 2 & -6 \\end{pmatrix}"
+        );
+        assert_eq!(
+            simplified("\\begin{pmatrix} 1 & 2 \\end{pmatrix} \\cdot \\begin{pmatrix} 1 & 2 \\end{pmatrix}"),
+            "undefined"
+        );
+        assert_eq!(
+            simplified("\\begin{pmatrix} x \\end{pmatrix} + \\begin{pmatrix} 1 \\end{pmatrix}"),
+            "(\\begin{pmatrix} 1 \\end{pmatrix} + \\begin{pmatrix} x \\end{pmatrix})"
+        );
+    }
+}
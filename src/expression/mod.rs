@@ -1,27 +1,212 @@
-use std::fmt::Debug;
+use std::{any::Any, collections::HashMap, fmt::Debug, ops::Range};
 
 use downcast_rs::{impl_downcast, Downcast};
 use dyn_clone::{clone_trait_object, DynClone};
 
+use crate::{
+    format::FormatOptions,
+    value::{IntervalUnionValue, IntervalValue, RationalValue, UndefinedValue, Value},
+};
+
+pub use abs::AbsExpression;
 pub use addition::AdditionExpression;
+pub use conditional::ConditionalExpression;
+pub use conjunction::ConjunctionExpression;
+pub use constant::ConstantExpression;
+pub use derivative::DerivativeExpression;
 pub use division::DivisionExpression;
+pub use extremum::{Extremum, ExtremumExpression};
+pub use factorial::FactorialExpression;
+pub use factors::FactorsExpression;
+pub use function::{Function, FunctionExpression};
+pub use function_call::{
+    ExactEvaluator, FunctionCallExpression, FunctionDefinition, FunctionRegistry, NumericEvaluator,
+    SimplifyRule,
+};
+pub use integral::IntegralExpression;
+pub use interval::IntervalExpression;
+pub use limit::{LimitDirection, LimitExpression, LimitTarget};
+pub use matrix::{MatrixDelimiter, MatrixExpression};
+pub use modulo::{ModuloExpression, ModuloSemantics};
 pub use multiplication::MultiplicationExpression;
+pub use negation::NegationExpression;
+pub use piecewise::{PiecewiseBranch, PiecewiseExpression};
+pub(crate) use plus_minus::expand_plus_minus;
+pub use plus_minus::PlusMinusExpression;
+pub use power::PowerExpression;
+pub use product::ProductExpression;
+pub use program::ProgramExpression;
+pub use relation::{Relation, RelationExpression};
+pub use result_set::ResultSetExpression;
+pub use root::RootExpression;
+pub use set::SetExpression;
 pub use subtraction::SubtractionExpression;
+pub use sum::SumExpression;
+pub use summation::SummationExpression;
+pub use tuple::TupleExpression;
 pub use value::ValueExpression;
+pub use variable::VariableExpression;
 
+mod abs;
 mod addition;
+mod conditional;
+mod conjunction;
+mod constant;
+mod derivative;
 mod division;
+mod extremum;
+mod factorial;
+mod factors;
+mod function;
+mod function_call;
+mod integral;
+mod interval;
+mod limit;
+mod matrix;
+mod modulo;
 mod multiplication;
+mod negation;
+mod piecewise;
+mod plus_minus;
+mod power;
+mod product;
+mod program;
+mod relation;
+mod result_set;
+mod root;
+mod set;
 mod subtraction;
+mod sum;
+mod summation;
+mod tuple;
 mod value;
+mod variable;
+
+/// Values bound to variables by name
+pub type Bindings = HashMap<String, Box<dyn Value>>;
 
 /// A mathematical expression
 pub trait Expression: Downcast + DynClone + Debug {
     /// Returns the simplified version of this expression
     fn simplified(&self) -> Box<dyn Expression>;
 
+    /// Evaluate this expression with the given variable bindings
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value>;
+
+    /// Get the values of a variable for which this expression is defined
+    ///
+    /// Returns `None` if the domain cannot be determined
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue>;
+
+    /// Get an interval guaranteed to contain this expression's values as a variable ranges over an interval
+    ///
+    /// Returns `None` if no enclosure can be computed
+    fn bounds(&self, variable: &str, interval: &IntervalValue) -> Option<IntervalValue> {
+        self.subdivided_bounds(variable, interval, 1)
+    }
+
+    /// Like `bounds`, but split the interval into pieces to tighten the enclosure
+    fn subdivided_bounds(
+        &self,
+        variable: &str,
+        interval: &IntervalValue,
+        pieces: usize,
+    ) -> Option<IntervalValue> {
+        let mut enclosure = IntervalValue::empty();
+        for piece in interval.subdivided(pieces) {
+            let mut bindings = Bindings::new();
+            bindings.insert(variable.to_string(), Box::new(piece));
+            let value = self.evaluate(&bindings);
+            if let Some(value) = value.downcast_ref::<IntervalValue>() {
+                enclosure = enclosure.hull(value);
+            } else if let Some(value) = value.downcast_ref::<RationalValue>() {
+                enclosure = enclosure.hull(&IntervalValue::point(value.clone()));
+            } else {
+                return None;
+            }
+        }
+        Some(enclosure)
+    }
+
+    /// Get the byte range of the input this expression was parsed from
+    ///
+    /// Returns `None` if the expression wasn't parsed, like the results of simplification
+    fn get_span(&self) -> Option<Range<usize>>;
+
+    /// Set the byte range of the input this expression was parsed from
+    fn set_span(&mut self, span: Range<usize>);
+
+    /// Get the subexpressions of this expression
+    fn get_children(&self) -> Vec<&dyn Expression>;
+
+    /// Get mutable references to the subexpressions of this expression
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>>;
+
+    /// Count the nodes of this expression's tree, including itself
+    fn node_count(&self) -> usize {
+        1 + self
+            .get_children()
+            .iter()
+            .map(|child| child.node_count())
+            .sum::<usize>()
+    }
+
+    /// Move the spans of this expression and its subexpressions from one starting position to another
+    fn relocate(&mut self, from: usize, to: usize) {
+        if let Some(span) = self.get_span() {
+            self.set_span(span.start - from + to..span.end - from + to);
+        }
+        for child in self.get_children_mut() {
+            child.relocate(from, to);
+        }
+    }
+
+    /// Get a string representation of this expression, written according to some format options
+    fn format(&self, options: &FormatOptions) -> String;
+
     /// Get a string representation of this expression
-    fn to_string(&self) -> String;
+    fn to_string(&self) -> String {
+        self.format(&FormatOptions::default())
+    }
 }
 impl_downcast!(Expression);
 clone_trait_object!(Expression);
+
+/// Check if the result of combining two values can replace the expression that produced it
+///
+/// Values that have no exact combination, like a constant and a rational, produce an undefined
+/// value and are left symbolic instead
+fn is_exact_combination(result: &dyn Value, lhs: &dyn Value, rhs: &dyn Value) -> bool {
+    !result.is::<UndefinedValue>() || lhs.is::<UndefinedValue>() || rhs.is::<UndefinedValue>()
+}
+
+/// Check if two expressions have the same tree
+///
+/// Both trees must have the same kinds of nodes in the same places, and be written the same way
+pub(crate) fn is_same_tree(lhs: &dyn Expression, rhs: &dyn Expression) -> bool {
+    fn same_shape(lhs: &dyn Expression, rhs: &dyn Expression) -> bool {
+        let (lhs_children, rhs_children) = (lhs.get_children(), rhs.get_children());
+        Any::type_id(lhs.as_any()) == Any::type_id(rhs.as_any())
+            && lhs_children.len() == rhs_children.len()
+            && lhs_children
+                .into_iter()
+                .zip(rhs_children)
+                .all(|(lhs, rhs)| same_shape(lhs, rhs))
+    }
+    same_shape(lhs, rhs) && lhs.to_string() == rhs.to_string()
+}
+
+/// Check if an expression is a non-negative integer value, which is written as plain digits
+fn is_natural(expression: &dyn Expression) -> bool {
+    expression
+        .downcast_ref::<ValueExpression>()
+        .and_then(|expression| expression.get_value().downcast_ref::<RationalValue>())
+        .is_some_and(|value| !value.is_negative() && value.to_integer().is_some())
+}
+
+/// Check if a value is exactly zero
+fn is_zero(value: &dyn Value) -> bool {
+    value
+        .downcast_ref::<RationalValue>()
+        .is_some_and(|value| value.is_zero())
+}
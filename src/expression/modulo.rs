@@ -0,0 +1,246 @@
+use std::ops::Range;
+
+use num::BigInt;
+
+use crate::{
+    format::FormatOptions,
+    trace,
+    value::{FloatValue, IntervalUnionValue, RationalValue, UndefinedValue, Value},
+};
+
+use super::{Bindings, Expression, ValueExpression};
+
+/// How the quotient of a modulo expression is rounded, which decides the sign of the remainder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ModuloSemantics {
+    /// The quotient is rounded down, so the remainder takes the sign of the modulus
+    #[default]
+    Floored,
+    /// The quotient is rounded towards zero, so the remainder takes the sign of the value
+    Truncated,
+    /// The remainder is never negative
+    Euclidean,
+}
+
+impl ModuloSemantics {
+    /// Round the quotient of a value and a modulus
+    fn round(&self, quotient: &RationalValue, modulus_negative: bool) -> BigInt {
+        let towards_ceiling = match self {
+            ModuloSemantics::Floored => false,
+            ModuloSemantics::Truncated => quotient.is_negative(),
+            ModuloSemantics::Euclidean => modulus_negative,
+        };
+        if towards_ceiling {
+            quotient.ceil()
+        } else {
+            quotient.floor()
+        }
+    }
+
+    /// Round the approximate quotient of a value and a modulus
+    fn round_float(&self, quotient: f64, modulus: f64) -> f64 {
+        match self {
+            ModuloSemantics::Floored => quotient.floor(),
+            ModuloSemantics::Truncated => quotient.trunc(),
+            ModuloSemantics::Euclidean if modulus < 0.0 => quotient.ceil(),
+            ModuloSemantics::Euclidean => quotient.floor(),
+        }
+    }
+}
+
+/// A modulo expression, whose result takes the sign of the modulus unless other semantics are chosen
+#[derive(Debug, Clone)]
+pub struct ModuloExpression {
+    lhs: Box<dyn Expression>,
+    rhs: Box<dyn Expression>,
+    semantics: ModuloSemantics,
+    span: Option<Range<usize>>,
+}
+
+impl ModuloExpression {
+    pub fn new(lhs: Box<dyn Expression>, rhs: Box<dyn Expression>) -> Self {
+        Self {
+            lhs,
+            rhs,
+            semantics: ModuloSemantics::default(),
+            span: None,
+        }
+    }
+
+    pub fn with_semantics(self, semantics: ModuloSemantics) -> Self {
+        Self { semantics, ..self }
+    }
+
+    pub fn get_semantics(&self) -> ModuloSemantics {
+        self.semantics
+    }
+
+    pub fn get_lhs(&self) -> &dyn Expression {
+        self.lhs.as_ref()
+    }
+
+    pub fn get_rhs(&self) -> &dyn Expression {
+        self.rhs.as_ref()
+    }
+}
+
+/// Get the exact remainder of two rational values, which is undefined for a modulus of zero
+fn exact_modulo(
+    lhs: &RationalValue,
+    rhs: &RationalValue,
+    semantics: ModuloSemantics,
+) -> Box<dyn Value> {
+    if rhs.is_zero() {
+        return Box::new(UndefinedValue::new());
+    }
+
+    // Subtract the multiple of the modulus given by the rounded quotient
+    let quotient = lhs.div(rhs);
+    let Some(quotient) = quotient.downcast_ref::<RationalValue>() else {
+        return Box::new(UndefinedValue::new());
+    };
+    let quotient = semantics.round(quotient, rhs.is_negative());
+    lhs.sub(rhs.mul(&RationalValue::from(quotient)).as_ref())
+}
+
+/// Get the remainder of two values, if they are numeric
+fn modulo(lhs: &dyn Value, rhs: &dyn Value, semantics: ModuloSemantics) -> Option<Box<dyn Value>> {
+    if let (Some(lhs), Some(rhs)) = (
+        lhs.downcast_ref::<RationalValue>(),
+        rhs.downcast_ref::<RationalValue>(),
+    ) {
+        return Some(exact_modulo(lhs, rhs, semantics));
+    }
+    let (lhs, rhs) = (FloatValue::approximate(lhs)?, FloatValue::approximate(rhs)?);
+    let remainder = lhs - rhs * semantics.round_float(lhs / rhs, rhs);
+    Some(if remainder.is_finite() {
+        Box::new(FloatValue::new(remainder))
+    } else {
+        Box::new(UndefinedValue::new())
+    })
+}
+
+impl Expression for ModuloExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", name = "simplify", skip_all, fields(node = "modulo"))
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        // Simplify both sides
+        let lhs = self.lhs.simplified();
+        let rhs = self.rhs.simplified();
+
+        // Fold the remainder of two rational values
+        let values = (
+            lhs.downcast_ref::<ValueExpression>()
+                .and_then(|lhs| lhs.get_value().downcast_ref::<RationalValue>()),
+            rhs.downcast_ref::<ValueExpression>()
+                .and_then(|rhs| rhs.get_value().downcast_ref::<RationalValue>()),
+        );
+        if let (Some(lhs_value), Some(rhs_value)) = values {
+            trace::rule("modulo", "combine values");
+            return Box::new(ValueExpression::new(exact_modulo(
+                lhs_value,
+                rhs_value,
+                self.semantics,
+            )));
+        }
+        Box::new(ModuloExpression::new(lhs, rhs).with_semantics(self.semantics))
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        modulo(
+            self.lhs.evaluate(bindings).as_ref(),
+            self.rhs.evaluate(bindings).as_ref(),
+            self.semantics,
+        )
+        .unwrap_or_else(|| Box::new(UndefinedValue::new()))
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        // A modulus depending on the variable may be zero anywhere it is defined
+        let rhs = self.rhs.simplified();
+        let modulus = rhs
+            .downcast_ref::<ValueExpression>()?
+            .get_value()
+            .downcast_ref::<RationalValue>()?;
+        if modulus.is_zero() {
+            return Some(IntervalUnionValue::empty());
+        }
+        self.lhs.domain(variable)
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        vec![self.lhs.as_ref(), self.rhs.as_ref()]
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        vec![&mut self.lhs, &mut self.rhs]
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        format!(
+            "({} \\bmod {})",
+            self.lhs.format(options),
+            self.rhs.format(options)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::{Bindings, Expression},
+        parse::{parse, ParserOptions},
+        value::FloatValue,
+    };
+
+    use super::{ModuloExpression, ModuloSemantics};
+
+    #[test]
+    fn modulo() {
+        let options = ParserOptions::default();
+        let simplified = |input: &str| parse(input, &options).unwrap().simplified().to_string();
+        assert_eq!(simplified("17 \\bmod 5"), "2");
+        assert_eq!(simplified("(0 - 7) \\mod 3"), "2");
+        assert_eq!(simplified("7/2 \\bmod 1 + 1"), "3/2");
+        assert_eq!(simplified("2x \\bmod n"), "((2 * x) \\bmod n)");
+        assert_eq!(simplified("5 \\bmod 0"), "undefined");
+
+        let value = parse("\\pi \\bmod 3", &options)
+            .unwrap()
+            .evaluate(&Bindings::new());
+        let value = FloatValue::approximate(value.as_ref()).unwrap();
+        assert!((value - (std::f64::consts::PI - 3.0)).abs() < 1e-15);
+
+        // The sign of the remainder depends on the semantics
+        let remainder = |lhs: &str, rhs: &str, semantics| {
+            ModuloExpression::new(parse(lhs, &options).unwrap(), parse(rhs, &options).unwrap())
+                .with_semantics(semantics)
+                .simplified()
+                .to_string()
+        };
+        assert_eq!(remainder("-7", "3", ModuloSemantics::Floored), "2");
+        assert_eq!(remainder("-7", "3", ModuloSemantics::Truncated), "-1");
+        assert_eq!(remainder("7", "-3", ModuloSemantics::Floored), "-2");
+        assert_eq!(remainder("7", "-3", ModuloSemantics::Truncated), "1");
+        assert_eq!(remainder("-7", "-3", ModuloSemantics::Euclidean), "2");
+        assert_eq!(remainder("7", "0", ModuloSemantics::Euclidean), "undefined");
+        let value = ModuloExpression::new(
+            parse("-\\pi", &options).unwrap(),
+            parse("3", &options).unwrap(),
+        )
+        .with_semantics(ModuloSemantics::Truncated)
+        .evaluate(&Bindings::new());
+        let value = FloatValue::approximate(value.as_ref()).unwrap();
+        assert!((value - (3.0 - std::f64::consts::PI)).abs() < 1e-15);
+    }
+}
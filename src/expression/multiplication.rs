@@ -1,36 +1,125 @@
-use super::{Expression, ValueExpression};
+use std::ops::Range;
+
+use crate::{
+    format::FormatOptions,
+    trace,
+    value::{IntervalUnionValue, Value},
+};
+
+use super::{
+    factors, is_exact_combination, matrix, Bindings, Expression, MatrixExpression, ValueExpression,
+};
 
 /// A multiplication expression
 #[derive(Debug, Clone)]
 pub struct MultiplicationExpression {
     lhs: Box<dyn Expression>,
     rhs: Box<dyn Expression>,
+    span: Option<Range<usize>>,
 }
 
 impl MultiplicationExpression {
     pub fn new(lhs: Box<dyn Expression>, rhs: Box<dyn Expression>) -> Self {
-        Self { lhs, rhs }
+        Self {
+            lhs,
+            rhs,
+            span: None,
+        }
+    }
+
+    pub fn get_lhs(&self) -> &dyn Expression {
+        self.lhs.as_ref()
+    }
+
+    pub fn get_rhs(&self) -> &dyn Expression {
+        self.rhs.as_ref()
     }
 }
 
 impl Expression for MultiplicationExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "multiplication")
+        )
+    )]
     fn simplified(&self) -> Box<dyn Expression> {
         // Simplify both sides
         let lhs = self.lhs.simplified();
         let rhs = self.rhs.simplified();
 
-        // Combine if two values
-        if let (Some(lhs), Some(rhs)) = (
+        // Combine if two values with an exact result
+        if let (Some(lhs_value), Some(rhs_value)) = (
             lhs.downcast_ref::<ValueExpression>(),
             rhs.downcast_ref::<ValueExpression>(),
         ) {
-            Box::new(ValueExpression::new(lhs.get_value().mul(rhs.get_value())))
-        } else {
-            Box::new(MultiplicationExpression::new(lhs, rhs))
+            let value = lhs_value.get_value().mul(rhs_value.get_value());
+            if is_exact_combination(value.as_ref(), lhs_value.get_value(), rhs_value.get_value()) {
+                trace::rule("multiplication", "combine values");
+                return Box::new(ValueExpression::new(value));
+            }
+        }
+
+        // Multiply matrices of values, or scale one by a value
+        if let Some(rhs_matrix) = rhs.downcast_ref::<MatrixExpression>() {
+            let product = if let Some(lhs_matrix) = lhs.downcast_ref::<MatrixExpression>() {
+                matrix::fold_product(lhs_matrix, rhs_matrix)
+            } else if let Some(scalar) = lhs.downcast_ref::<ValueExpression>() {
+                matrix::fold_scale(scalar.get_value(), rhs_matrix)
+            } else {
+                None
+            };
+            if let Some(product) = product {
+                trace::rule("multiplication", "multiply matrices");
+                return product;
+            }
         }
+
+        // Flatten nested products and quotients, merging like factors
+        if !lhs.is::<ValueExpression>() || !rhs.is::<ValueExpression>() {
+            return factors::combine(vec![(lhs, false), (rhs, false)]);
+        }
+        Box::new(MultiplicationExpression::new(lhs, rhs))
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        self.lhs
+            .evaluate(bindings)
+            .mul(self.rhs.evaluate(bindings).as_ref())
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        Some(
+            self.lhs
+                .domain(variable)?
+                .intersection(&self.rhs.domain(variable)?),
+        )
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        vec![self.lhs.as_ref(), self.rhs.as_ref()]
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        vec![&mut self.lhs, &mut self.rhs]
     }
 
-    fn to_string(&self) -> String {
-        format!("({} * {})", self.lhs.to_string(), self.rhs.to_string())
+    fn format(&self, options: &FormatOptions) -> String {
+        format!(
+            "({} * {})",
+            self.lhs.format(options),
+            self.rhs.format(options)
+        )
     }
 }
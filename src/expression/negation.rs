@@ -0,0 +1,177 @@
+use std::ops::Range;
+
+use crate::{
+    format::FormatOptions,
+    trace,
+    value::{IntervalUnionValue, RationalValue, Sign, Value},
+};
+
+use super::{
+    is_exact_combination, AdditionExpression, Bindings, Expression, FactorsExpression,
+    MultiplicationExpression, SubtractionExpression, ValueExpression,
+};
+
+/// A negation expression
+#[derive(Debug, Clone)]
+pub struct NegationExpression {
+    argument: Box<dyn Expression>,
+    span: Option<Range<usize>>,
+}
+
+impl NegationExpression {
+    pub fn new(argument: Box<dyn Expression>) -> Self {
+        Self {
+            argument,
+            span: None,
+        }
+    }
+
+    pub fn get_argument(&self) -> &dyn Expression {
+        self.argument.as_ref()
+    }
+}
+
+/// Negate a value by multiplying it by negative one
+fn negated(value: &dyn Value) -> Box<dyn Value> {
+    if let Some(value) = value.downcast_ref::<RationalValue>() {
+        return Box::new(value.get_opposite());
+    }
+    RationalValue::new(Sign::Negative, 1_u32, 1_u32).mul(value)
+}
+
+impl Expression for NegationExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "negation")
+        )
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        let argument = self.argument.simplified();
+
+        // Fold the negation into the sign of a value
+        if let Some(value) = argument.downcast_ref::<ValueExpression>() {
+            let result = negated(value.get_value());
+            if is_exact_combination(result.as_ref(), value.get_value(), value.get_value()) {
+                trace::rule("negation", "fold value");
+                return Box::new(ValueExpression::new(result));
+            }
+        }
+
+        // Negating twice does nothing
+        if let Some(negation) = argument.downcast_ref::<NegationExpression>() {
+            trace::rule("negation", "double negation");
+            return dyn_clone::clone_box(negation.get_argument());
+        }
+
+        // Fold the negation into the coefficient of a product
+        if let Some(product) = argument.downcast_ref::<MultiplicationExpression>() {
+            if let Some(value) = product.get_lhs().downcast_ref::<ValueExpression>() {
+                let result = negated(value.get_value());
+                if is_exact_combination(result.as_ref(), value.get_value(), value.get_value()) {
+                    trace::rule("negation", "fold coefficient");
+                    return Box::new(MultiplicationExpression::new(
+                        Box::new(ValueExpression::new(result)),
+                        dyn_clone::clone_box(product.get_rhs()),
+                    ));
+                }
+            }
+        }
+
+        if let Some(product) = argument.downcast_ref::<FactorsExpression>() {
+            if let Some(value) = product.get_factors()[0].downcast_ref::<ValueExpression>() {
+                let result = negated(value.get_value());
+                if is_exact_combination(result.as_ref(), value.get_value(), value.get_value()) {
+                    trace::rule("negation", "fold coefficient");
+                    let mut factors = product.get_factors().to_vec();
+                    factors[0] = Box::new(ValueExpression::new(result));
+                    return Box::new(FactorsExpression::new(factors));
+                }
+            }
+        }
+
+        // Swap the sides of a difference
+        if let Some(difference) = argument.downcast_ref::<SubtractionExpression>() {
+            trace::rule("negation", "swap difference");
+            return SubtractionExpression::new(
+                dyn_clone::clone_box(difference.get_rhs()),
+                dyn_clone::clone_box(difference.get_lhs()),
+            )
+            .simplified();
+        }
+
+        // Distribute over a sum if negating its terms makes the tree smaller
+        if let Some(sum) = argument.downcast_ref::<AdditionExpression>() {
+            let distributed = AdditionExpression::new(
+                Box::new(NegationExpression::new(dyn_clone::clone_box(sum.get_lhs()))),
+                Box::new(NegationExpression::new(dyn_clone::clone_box(sum.get_rhs()))),
+            )
+            .simplified();
+            if distributed.node_count() <= argument.node_count() {
+                trace::rule("negation", "distribute over sum");
+                return distributed;
+            }
+        }
+        Box::new(NegationExpression::new(argument))
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        negated(self.argument.evaluate(bindings).as_ref())
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        self.argument.domain(variable)
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        vec![self.argument.as_ref()]
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        vec![&mut self.argument]
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        format!("-{}", self.argument.format(options))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parse::{parse, ParserOptions},
+        solve::solve,
+    };
+
+    #[test]
+    fn negation() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap();
+        assert_eq!(parsed("-3 + 5").simplified().to_string(), "2");
+        assert_eq!(parsed("-2^2").simplified().to_string(), "-4");
+        assert_eq!(parsed("- -x").simplified().to_string(), "x");
+        assert_eq!(parsed("-\\pi + 2\\pi").simplified().to_string(), "\\pi");
+        assert_eq!(parsed("2 \\cdot -x").to_string(), "(2 * -x)");
+        assert_eq!(parsed("-(2x)").simplified().to_string(), "(-2 * x)");
+        assert_eq!(parsed("-(x - 3)").simplified().to_string(), "(3 - x)");
+        assert_eq!(parsed("-(-x + 3)").simplified().to_string(), "(x - 3)");
+        assert_eq!(parsed("-(x + y)").simplified().to_string(), "-(x + y)");
+        assert_eq!(parsed("-(2xy)").simplified().to_string(), "(-2 * x * y)");
+        assert_eq!(parsed("-(xyz)").simplified().to_string(), "-(x * y * z)");
+
+        let solution = solve(parsed("-x < 2").as_ref(), "x").unwrap();
+        assert!(!solution.contains(&"-2".parse().unwrap()));
+        assert!(solution.contains(&"-1".parse().unwrap()));
+    }
+}
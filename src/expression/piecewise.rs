@@ -0,0 +1,196 @@
+use std::ops::Range;
+
+use crate::{
+    format::FormatOptions,
+    trace,
+    value::{BooleanValue, IntervalUnionValue, UndefinedValue, Value},
+};
+
+use super::{Bindings, Expression, ValueExpression};
+
+/// A branch of a piecewise expression, taken when its condition holds or always if it has none
+pub type PiecewiseBranch = (Box<dyn Expression>, Option<Box<dyn Expression>>);
+
+/// A piecewise expression, taking the first branch whose condition holds
+#[derive(Debug, Clone)]
+pub struct PiecewiseExpression {
+    branches: Vec<PiecewiseBranch>,
+    span: Option<Range<usize>>,
+}
+
+impl PiecewiseExpression {
+    pub fn new(branches: Vec<PiecewiseBranch>) -> Self {
+        Self {
+            branches,
+            span: None,
+        }
+    }
+
+    pub fn get_branches(&self) -> &[PiecewiseBranch] {
+        &self.branches
+    }
+}
+
+impl Expression for PiecewiseExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "piecewise")
+        )
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        let mut branches: Vec<PiecewiseBranch> = Vec::new();
+        for (expression, condition) in &self.branches {
+            let condition = condition.as_ref().map(|condition| condition.simplified());
+            let holds = condition.as_ref().and_then(|condition| {
+                condition
+                    .downcast_ref::<ValueExpression>()
+                    .and_then(|condition| condition.get_value().downcast_ref::<BooleanValue>())
+                    .map(BooleanValue::get_value)
+            });
+            match holds {
+                // Branches that are never taken are dropped
+                Some(false) => trace::rule("piecewise", "drop false branch"),
+
+                // Branches that are always taken hide the ones after them
+                Some(true) => {
+                    trace::rule("piecewise", "drop hidden branches");
+                    branches.push((expression.simplified(), None));
+                    break;
+                }
+                None => {
+                    let always = condition.is_none();
+                    branches.push((expression.simplified(), condition));
+                    if always {
+                        break;
+                    }
+                }
+            }
+        }
+
+        // The first branch is the whole expression if it is always taken
+        match branches.as_slice() {
+            [] => Box::new(ValueExpression::new(Box::new(UndefinedValue::new()))),
+            [(_, None), ..] => branches.swap_remove(0).0,
+            _ => Box::new(PiecewiseExpression::new(branches)),
+        }
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        for (expression, condition) in &self.branches {
+            let Some(condition) = condition else {
+                return expression.evaluate(bindings);
+            };
+
+            // A condition that can't be decided leaves the whole expression undefined
+            match condition.evaluate(bindings).downcast_ref::<BooleanValue>() {
+                Some(holds) if holds.get_value() => return expression.evaluate(bindings),
+                Some(_) => {}
+                None => break,
+            }
+        }
+        Box::new(UndefinedValue::new())
+    }
+
+    fn domain(&self, _variable: &str) -> Option<IntervalUnionValue> {
+        // Earlier branches hide later ones, which can't be worked out without complementing domains
+        None
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        self.branches
+            .iter()
+            .flat_map(|(expression, condition)| {
+                std::iter::once(expression.as_ref()).chain(condition.as_deref())
+            })
+            .collect()
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        self.branches
+            .iter_mut()
+            .flat_map(|(expression, condition)| std::iter::once(expression).chain(condition))
+            .collect()
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        let branches = self
+            .branches
+            .iter()
+            .map(|(expression, condition)| {
+                let condition = match condition {
+                    Some(condition) => condition.format(options),
+                    None => "\\text{otherwise}".to_string(),
+                };
+                format!("{} & {}", expression.format(options), condition)
+            })
+            .collect::<Vec<_>>()
+            .join(" \\\\ ");
+        format!("\\begin{{cases}} {} \\end{{cases}}", branches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::Bindings,
+        parse::{parse, ParserOptions},
+        value::{FloatValue, RationalValue},
+    };
+
+    #[test]
+    fn piecewise() {
+        let options = ParserOptions::default();
+        let expression = parse(
+            "\\begin{cases} -x & x < 0 \\\\ x^2 & \\text{if } x \\le 2 \\\\ 4 & \\text{otherwise} \\end{cases}",
+            &options,
+        )
+        .unwrap();
+        let at = |x: i32| {
+            let mut bindings = Bindings::new();
+            bindings.insert(
+                "x".to_string(),
+                Box::new(RationalValue::from(num::BigInt::from(x))),
+            );
+            FloatValue::approximate(expression.evaluate(&bindings).as_ref())
+        };
+        assert_eq!(at(-3), Some(3.0));
+        assert_eq!(at(1), Some(1.0));
+        assert_eq!(at(5), Some(4.0));
+        assert_eq!(
+            expression.simplified().to_string(),
+            "\\begin{cases} -x & x < 0 \\\\ (x ^ 2) & x \\le 2 \\\\ 4 & \\text{otherwise} \\end{cases}"
+        );
+
+        // Branches whose conditions are decided are dropped
+        let simplified = |input: &str| parse(input, &options).unwrap().simplified().to_string();
+        assert_eq!(
+            simplified("\\begin{cases} 1 & 2 < 1 \\\\ x & y > 0 \\\\ 3 & 1 < 2 \\\\ 4 & \\text{otherwise} \\end{cases}"),
+            "\\begin{cases} x & y > 0 \\\\ 3 & \\text{otherwise} \\end{cases}"
+        );
+        assert_eq!(
+            simplified("\\begin{cases} 1 & 2 < 1 \\\\ x + x & 1 < 2 \\end{cases}"),
+            "(2 * x)"
+        );
+        assert_eq!(
+            simplified("\\begin{cases} 1 & 2 < 1 \\end{cases}"),
+            "undefined"
+        );
+
+        // Without a matching branch or an undecidable condition, it's undefined
+        let expression = parse("\\begin{cases} 1 & y > 0 \\end{cases}", &options).unwrap();
+        let value = expression.evaluate(&Bindings::new());
+        assert_eq!(FloatValue::approximate(value.as_ref()), None);
+    }
+}
@@ -0,0 +1,185 @@
+use std::ops::Range;
+
+use crate::{
+    format::FormatOptions,
+    value::{IntervalUnionValue, UndefinedValue, Value},
+};
+
+use super::{AdditionExpression, Bindings, Expression, ResultSetExpression, SubtractionExpression};
+
+/// A plus-minus expression, like `a \pm b` or `a \mp b`, standing for both of its branches
+#[derive(Debug, Clone)]
+pub struct PlusMinusExpression {
+    lhs: Box<dyn Expression>,
+    rhs: Box<dyn Expression>,
+    minus_plus: bool,
+    span: Option<Range<usize>>,
+}
+
+impl PlusMinusExpression {
+    pub fn new(lhs: Box<dyn Expression>, rhs: Box<dyn Expression>) -> Self {
+        Self {
+            lhs,
+            rhs,
+            minus_plus: false,
+            span: None,
+        }
+    }
+
+    /// Construct a new minus-plus expression, whose branches come in the opposite order
+    pub fn minus_plus(lhs: Box<dyn Expression>, rhs: Box<dyn Expression>) -> Self {
+        Self {
+            minus_plus: true,
+            ..Self::new(lhs, rhs)
+        }
+    }
+
+    pub fn get_lhs(&self) -> &dyn Expression {
+        self.lhs.as_ref()
+    }
+
+    pub fn get_rhs(&self) -> &dyn Expression {
+        self.rhs.as_ref()
+    }
+
+    pub fn is_minus_plus(&self) -> bool {
+        self.minus_plus
+    }
+
+    /// Get the upper or lower branch, where the upper branch of `\pm` adds and of `\mp` subtracts
+    pub fn branch(&self, upper: bool) -> Box<dyn Expression> {
+        let (lhs, rhs) = (self.lhs.clone(), self.rhs.clone());
+        if upper != self.minus_plus {
+            Box::new(AdditionExpression::new(lhs, rhs))
+        } else {
+            Box::new(SubtractionExpression::new(lhs, rhs))
+        }
+    }
+}
+
+/// Check if an expression contains a plus-minus anywhere
+fn contains_plus_minus(expression: &dyn Expression) -> bool {
+    expression.is::<PlusMinusExpression>()
+        || expression
+            .get_children()
+            .into_iter()
+            .any(contains_plus_minus)
+}
+
+/// Replace every plus-minus in an expression with its upper or lower branch
+fn choose_branch(expression: &mut Box<dyn Expression>, upper: bool) {
+    for child in expression.get_children_mut() {
+        choose_branch(child, upper);
+    }
+    if let Some(plus_minus) = expression.downcast_ref::<PlusMinusExpression>() {
+        let mut branch = plus_minus.branch(upper);
+        if let Some(span) = expression.get_span() {
+            branch.set_span(span);
+        }
+        *expression = branch;
+    }
+}
+
+/// Expand the plus-minuses in an expression into a set of both results
+///
+/// Every `\pm` takes the same branch and every `\mp` the other, as in `a \pm b \mp c`
+pub(crate) fn expand_plus_minus(expression: Box<dyn Expression>) -> Box<dyn Expression> {
+    if !contains_plus_minus(expression.as_ref()) {
+        return expression;
+    }
+    let span = expression.get_span();
+    let branches = [true, false]
+        .into_iter()
+        .map(|upper| {
+            let mut branch = expression.clone();
+            choose_branch(&mut branch, upper);
+            branch
+        })
+        .collect();
+    let mut expanded: Box<dyn Expression> = Box::new(ResultSetExpression::new(branches));
+    if let Some(span) = span {
+        expanded.set_span(span);
+    }
+    expanded
+}
+
+impl Expression for PlusMinusExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "plus minus")
+        )
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        expand_plus_minus(Box::new(self.clone())).simplified()
+    }
+
+    fn evaluate(&self, _bindings: &Bindings) -> Box<dyn Value> {
+        // A single value can't hold both branches
+        Box::new(UndefinedValue::new())
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        Some(
+            self.lhs
+                .domain(variable)?
+                .intersection(&self.rhs.domain(variable)?),
+        )
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        vec![self.lhs.as_ref(), self.rhs.as_ref()]
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        vec![&mut self.lhs, &mut self.rhs]
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        format!(
+            "({} {} {})",
+            self.lhs.format(options),
+            if self.minus_plus { "\\mp" } else { "\\pm" },
+            self.rhs.format(options)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::ResultSetExpression,
+        parse::{parse, ParserOptions},
+    };
+
+    #[test]
+    fn plus_minus() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap();
+        assert_eq!(parsed("2 \\pm 3").to_string(), "\\{(2 + 3), (2 - 3)\\}");
+        assert_eq!(parsed("2 \\pm 3").simplified().to_string(), "\\{5, -1\\}");
+        assert_eq!(parsed("2 \\pm (3 - 3)").simplified().to_string(), "2");
+
+        // Both branches of the quadratic formula for x^2 - 5x + 6
+        let roots = parsed("\\frac{5 \\pm \\sqrt{25 - 4 \\cdot 6}}{2}").simplified();
+        assert!(roots.is::<ResultSetExpression>());
+        assert_eq!(roots.to_string(), "\\{3, 2\\}");
+
+        // Minus-plus takes the opposite branch to plus-minus
+        assert_eq!(
+            parsed("1 \\pm 2 \\mp 4").simplified().to_string(),
+            "\\{-1, 3\\}"
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use std::{cmp::Ordering, ops::Range};
+
+use num::{Integer as _, One as _};
+
+use crate::{
+    format::FormatOptions,
+    trace,
+    value::{ComplexValue, FloatValue, IntervalUnionValue, RationalValue, UndefinedValue, Value},
+};
+
+use super::{
+    AbsExpression, Bindings, Expression, NegationExpression, RootExpression, ValueExpression,
+};
+
+/// The most bits a folded power may have, so huge powers like `2^{10^9}` stay symbolic
+const MAX_FOLDED_BITS: u64 = 1 << 16;
+
+/// A power expression
+#[derive(Debug, Clone)]
+pub struct PowerExpression {
+    base: Box<dyn Expression>,
+    exponent: Box<dyn Expression>,
+    span: Option<Range<usize>>,
+}
+
+impl PowerExpression {
+    pub fn new(base: Box<dyn Expression>, exponent: Box<dyn Expression>) -> Self {
+        Self {
+            base,
+            exponent,
+            span: None,
+        }
+    }
+
+    pub fn get_base(&self) -> &dyn Expression {
+        self.base.as_ref()
+    }
+
+    pub fn get_exponent(&self) -> &dyn Expression {
+        self.exponent.as_ref()
+    }
+}
+
+/// Raise a rational value to a rational exponent exactly, if the exponent is a small enough integer
+fn exact_power(base: &RationalValue, exponent: &RationalValue) -> Option<RationalValue> {
+    let exponent = i32::try_from(exponent.to_integer()?).ok()?;
+    let bits = base
+        .get_numerator()
+        .bits()
+        .max(base.get_denominator().bits());
+    if bits.saturating_mul(u64::from(exponent.unsigned_abs())) > MAX_FOLDED_BITS {
+        return None;
+    }
+    base.pow(exponent)
+}
+
+/// Raise a complex base to an integer exponent, if the result isn't too large to fold
+fn exact_complex_power(base: &ComplexValue, exponent: &RationalValue) -> Option<Box<dyn Value>> {
+    let exponent = i32::try_from(exponent.to_integer()?).ok()?;
+    let bits = [base.get_real(), base.get_imaginary()]
+        .into_iter()
+        .map(|part| {
+            part.get_numerator()
+                .bits()
+                .max(part.get_denominator().bits())
+        })
+        .max()
+        .unwrap_or(0);
+    if bits.saturating_mul(u64::from(exponent.unsigned_abs())) > MAX_FOLDED_BITS {
+        return None;
+    }
+    base.pow(exponent)
+}
+
+impl Expression for PowerExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", name = "simplify", skip_all, fields(node = "power"))
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        // Simplify both sides
+        let base = self.base.simplified();
+        let exponent = self.exponent.simplified();
+
+        // Fold a rational base raised to an integer exponent
+        let values = (
+            base.downcast_ref::<ValueExpression>()
+                .and_then(|base| base.get_value().downcast_ref::<RationalValue>()),
+            exponent
+                .downcast_ref::<ValueExpression>()
+                .and_then(|exponent| exponent.get_value().downcast_ref::<RationalValue>()),
+        );
+        if let (Some(base_value), Some(exponent_value)) = values {
+            if let Some(value) = exact_power(base_value, exponent_value) {
+                trace::rule("power", "combine values");
+                return Box::new(ValueExpression::new(Box::new(value)));
+            }
+        }
+
+        // Fold a complex base raised to an integer exponent
+        if let (Some(base_value), Some(exponent_value)) = (
+            base.downcast_ref::<ValueExpression>()
+                .and_then(|base| base.get_value().downcast_ref::<ComplexValue>()),
+            values.1,
+        ) {
+            if let Some(value) = exact_complex_power(base_value, exponent_value) {
+                trace::rule("power", "combine complex values");
+                return Box::new(ValueExpression::new(value));
+            }
+        }
+
+        // Even powers are never negative, so the absolute value of the base can be dropped
+        if let (Some(abs), Some(exponent_value)) = (base.downcast_ref::<AbsExpression>(), values.1)
+        {
+            if exponent_value
+                .to_integer()
+                .is_some_and(|exponent| exponent.is_even())
+            {
+                trace::rule("power", "even power of an absolute value");
+                return Box::new(PowerExpression::new(
+                    dyn_clone::clone_box(abs.get_argument()),
+                    exponent,
+                ));
+            }
+        }
+
+        // A root raised to a multiple of its index is a power of its radicand, as long as an even
+        // root's radicand can't be negative, where the power would be defined but the root isn't
+        if let (Some(root), Some(exponent_value)) =
+            (base.downcast_ref::<RootExpression>(), values.1)
+        {
+            let index = root
+                .get_index()
+                .downcast_ref::<ValueExpression>()
+                .and_then(|index| index.get_value().downcast_ref::<RationalValue>())
+                .and_then(RationalValue::to_integer)
+                .filter(|index| index.sign() == num::bigint::Sign::Plus);
+            let non_negative = root
+                .get_radicand()
+                .downcast_ref::<ValueExpression>()
+                .and_then(|radicand| radicand.get_value().cmp(&RationalValue::zero()))
+                .is_some_and(|ordering| ordering != Ordering::Less);
+            if let (Some(index), Some(exponent_integer)) = (index, exponent_value.to_integer()) {
+                if exponent_integer.is_multiple_of(&index) && (index.is_odd() || non_negative) {
+                    trace::rule("power", "power of a root");
+                    let radicand = dyn_clone::clone_box(root.get_radicand());
+                    let quotient = exponent_integer / index;
+                    if quotient.is_one() {
+                        return radicand;
+                    }
+                    return PowerExpression::new(
+                        radicand,
+                        Box::new(ValueExpression::new(Box::new(RationalValue::from(
+                            quotient,
+                        )))),
+                    )
+                    .simplified();
+                }
+            }
+        }
+        Box::new(PowerExpression::new(base, exponent))
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        let base = self.base.evaluate(bindings);
+        let exponent = self.exponent.evaluate(bindings);
+
+        // Prefer the exact result, falling back to an approximation
+        if let (Some(base), Some(exponent)) = (
+            base.downcast_ref::<RationalValue>(),
+            exponent.downcast_ref::<RationalValue>(),
+        ) {
+            if let Some(value) = exact_power(base, exponent) {
+                return Box::new(value);
+            }
+        }
+        if let (Some(base), Some(exponent)) = (
+            base.downcast_ref::<ComplexValue>(),
+            exponent.downcast_ref::<RationalValue>(),
+        ) {
+            return exact_complex_power(base, exponent)
+                .unwrap_or_else(|| Box::new(UndefinedValue::new()));
+        }
+        match (
+            FloatValue::approximate(base.as_ref()),
+            FloatValue::approximate(exponent.as_ref()),
+        ) {
+            (Some(base), Some(exponent)) if base.powf(exponent).is_finite() => {
+                Box::new(FloatValue::new(base.powf(exponent)))
+            }
+            _ => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        let domain = self
+            .base
+            .domain(variable)?
+            .intersection(&self.exponent.domain(variable)?);
+
+        // Only a constant natural exponent is defined for every base
+        let exponent = self.exponent.simplified();
+        exponent
+            .downcast_ref::<ValueExpression>()
+            .and_then(|exponent| exponent.get_value().downcast_ref::<RationalValue>())
+            .filter(|exponent| exponent.is_integer() && !exponent.is_negative())
+            .map(|_| domain)
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        vec![self.base.as_ref(), self.exponent.as_ref()]
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        vec![&mut self.base, &mut self.exponent]
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        format!(
+            "({} ^ {})",
+            format_operand(self.base.as_ref(), options),
+            format_operand(self.exponent.as_ref(), options)
+        )
+    }
+}
+
+/// Format the base or exponent of a power, parenthesizing it if it would bind looser than the power
+///
+/// Negations and values written with a sign, a fraction or a coefficient, like `-1`, `2/3` or
+/// `2\\pi`, are parenthesized, while numbers and names like `2`, `e` and `\\pi` aren't
+fn format_operand(operand: &dyn Expression, options: &FormatOptions) -> String {
+    let formatted = operand.format(options);
+    let atomic = || {
+        let name = formatted.strip_prefix('\\').unwrap_or(&formatted);
+        (!name.is_empty() && name.chars().all(|c| c.is_ascii_alphabetic()))
+            || (!formatted.is_empty() && formatted.chars().all(|c| c.is_ascii_digit() || c == '.'))
+    };
+    if operand.is::<NegationExpression>() || (operand.is::<ValueExpression>() && !atomic()) {
+        format!("({})", formatted)
+    } else {
+        formatted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::{Bindings, Expression, PowerExpression, ValueExpression, VariableExpression},
+        parse::{parse, ParserOptions},
+        value::{FloatValue, RationalValue},
+    };
+
+    #[test]
+    fn power() {
+        let options = ParserOptions::default();
+        let simplified = |input: &str| parse(input, &options).unwrap().simplified().to_string();
+        assert_eq!(simplified("2^3^2"), "512");
+        assert_eq!(simplified("(2 / 3)^{2 + 1}"), "8/27");
+        assert_eq!(simplified("2^x"), "(2 ^ x)");
+        assert_eq!(simplified("0^{0 - 1}"), "(0 ^ (-1))");
+
+        // Roots raised to a multiple of their index fold into powers of their radicand
+        assert_eq!(simplified("\\sqrt{2} \\cdot \\sqrt{2}"), "2");
+        assert_eq!(simplified("3\\sqrt{2} x \\sqrt{2}"), "(6 * x)");
+        assert_eq!(simplified("\\sqrt{2}^{4}"), "4");
+        assert_eq!(simplified("\\sqrt{2}^{0 - 2}"), "1/2");
+        assert_eq!(simplified("\\sqrt[3]{x}^{6}"), "(x ^ 2)");
+        assert_eq!(simplified("\\sqrt{2}^{3}"), "(\\sqrt{2} ^ 3)");
+        assert_eq!(simplified("\\sqrt{x}^{2}"), "(\\sqrt{x} ^ 2)");
+
+        // Bases that bind looser than the power are parenthesized, so they parse back the same
+        assert_eq!(simplified("(2/3)^x"), "((2/3) ^ x)");
+        assert_eq!(simplified("(-x)^2"), "((-x) ^ 2)");
+        let power = PowerExpression::new(
+            Box::new(ValueExpression::new(Box::new(
+                "-2".parse::<RationalValue>().unwrap(),
+            ))),
+            Box::new(VariableExpression::new("x")),
+        );
+        assert_eq!(power.to_string(), "((-2) ^ x)");
+        assert_eq!(simplified(&power.to_string()), "((-2) ^ x)");
+
+        // Names and plain numbers aren't, and neither are exponents that bind tighter
+        assert_eq!(simplified("e^2"), "(e ^ 2)");
+        assert_eq!(simplified("\\pi^x"), "(\\pi ^ x)");
+        assert_eq!(simplified("x^{1/2}"), "(x ^ (1/2))");
+        assert_eq!(parse("i^2", &options).unwrap().to_string(), "(i ^ 2)");
+        assert_eq!(simplified("x^{-y}"), "(x ^ (-y))");
+        assert_eq!(simplified("(2\\pi)^x"), "((2\\pi) ^ x)");
+        for input in ["(2/3)^x", "(-x)^2", "x^{1/2}", "0^{-1}", "(2\\pi)^x"] {
+            let printed = simplified(input);
+            assert_eq!(simplified(&printed), printed);
+        }
+
+        let value = parse("4^{1 / 2}", &options)
+            .unwrap()
+            .evaluate(&Bindings::new());
+        assert_eq!(FloatValue::approximate(value.as_ref()), Some(2.0));
+    }
+}
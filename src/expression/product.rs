@@ -0,0 +1,187 @@
+use std::ops::Range;
+
+use crate::{
+    format::FormatOptions,
+    trace,
+    value::{IntervalUnionValue, RationalValue, UndefinedValue, Value},
+};
+
+use super::{
+    summation::{integer_bounds, substituted},
+    Bindings, Expression, MultiplicationExpression, ValueExpression,
+};
+
+/// A product expression, like `\prod_{i=1}^{n} i`
+#[derive(Debug, Clone)]
+pub struct ProductExpression {
+    index: String,
+    lower: Box<dyn Expression>,
+    upper: Box<dyn Expression>,
+    body: Box<dyn Expression>,
+    span: Option<Range<usize>>,
+}
+
+impl ProductExpression {
+    pub fn new(
+        index: impl Into<String>,
+        lower: Box<dyn Expression>,
+        upper: Box<dyn Expression>,
+        body: Box<dyn Expression>,
+    ) -> Self {
+        Self {
+            index: index.into(),
+            lower,
+            upper,
+            body,
+            span: None,
+        }
+    }
+
+    pub fn get_index(&self) -> &str {
+        &self.index
+    }
+
+    pub fn get_lower(&self) -> &dyn Expression {
+        self.lower.as_ref()
+    }
+
+    pub fn get_upper(&self) -> &dyn Expression {
+        self.upper.as_ref()
+    }
+
+    pub fn get_body(&self) -> &dyn Expression {
+        self.body.as_ref()
+    }
+}
+
+impl Expression for ProductExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "product")
+        )
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        let lower = self.lower.simplified();
+        let upper = self.upper.simplified();
+
+        // Expand a product with integer bounds into its factors
+        if let (Some(lower_value), Some(upper_value)) = (
+            lower.downcast_ref::<ValueExpression>(),
+            upper.downcast_ref::<ValueExpression>(),
+        ) {
+            if let Some((start, end)) =
+                integer_bounds(lower_value.get_value(), upper_value.get_value())
+            {
+                trace::rule("product", "expand factors");
+                let product = (start..=end)
+                    .map(|index| substituted(self.body.as_ref(), &self.index, index))
+                    .reduce(|product, factor| {
+                        Box::new(MultiplicationExpression::new(product, factor))
+                    })
+                    .unwrap_or_else(|| {
+                        Box::new(ValueExpression::new(Box::new(RationalValue::one())))
+                    });
+                return product.simplified();
+            }
+        }
+        Box::new(ProductExpression::new(
+            self.index.clone(),
+            lower,
+            upper,
+            self.body.simplified(),
+        ))
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        let lower = self.lower.evaluate(bindings);
+        let upper = self.upper.evaluate(bindings);
+        let Some((start, end)) = integer_bounds(lower.as_ref(), upper.as_ref()) else {
+            return Box::new(UndefinedValue::new());
+        };
+
+        // Multiply the body at each index, staying exact while the factors are rational
+        let mut bindings = bindings.clone();
+        let mut product: Box<dyn Value> = Box::new(RationalValue::one());
+        for index in start..=end {
+            bindings.insert(
+                self.index.clone(),
+                Box::new(RationalValue::from(num::BigInt::from(index))),
+            );
+            product = product.mul(self.body.evaluate(&bindings).as_ref());
+        }
+        product
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        // The index isn't free outside of the product
+        if variable == self.index {
+            return None;
+        }
+        Some(
+            self.lower
+                .domain(variable)?
+                .intersection(&self.upper.domain(variable)?)
+                .intersection(&self.body.domain(variable)?),
+        )
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        vec![self.lower.as_ref(), self.upper.as_ref(), self.body.as_ref()]
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        vec![&mut self.lower, &mut self.upper, &mut self.body]
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        format!(
+            "\\prod_{{{}={}}}^{{{}}}{{{}}}",
+            self.index,
+            self.lower.format(options),
+            self.upper.format(options),
+            self.body.format(options)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::Bindings,
+        parse::{parse, ParserOptions},
+    };
+
+    #[test]
+    fn product() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap();
+        assert_eq!(parsed("\\prod_{i=1}^{5} i").simplified().to_string(), "120");
+        assert_eq!(
+            parsed("\\prod_{k=2}^{4}{\\frac{k - 1}{k}}")
+                .simplified()
+                .to_string(),
+            "1/4"
+        );
+        assert_eq!(parsed("\\prod_{i=1}^{0} i").simplified().to_string(), "1");
+        assert_eq!(
+            parsed("\\prod_{i=1}^{n}{2i}").simplified().to_string(),
+            "\\prod_{i=1}^{n}{(2 * i)}"
+        );
+
+        // Rational factors multiply exactly
+        let value = parsed("\\prod_{k=2}^{30}{\\frac{k - 1}{k}}").evaluate(&Bindings::new());
+        assert_eq!(value.to_string(), "1/30");
+    }
+}
@@ -0,0 +1,133 @@
+use std::ops::Range;
+
+use crate::{
+    format::FormatOptions,
+    value::{IntervalUnionValue, UndefinedValue, Value},
+};
+
+use super::{Bindings, Expression, Relation, RelationExpression, VariableExpression};
+
+/// A sequence of expressions separated by semicolons, like `x = 3; 2x + 1`
+#[derive(Debug, Clone)]
+pub struct ProgramExpression {
+    statements: Vec<Box<dyn Expression>>,
+    span: Option<Range<usize>>,
+}
+
+impl ProgramExpression {
+    pub fn new(statements: Vec<Box<dyn Expression>>) -> Self {
+        Self {
+            statements,
+            span: None,
+        }
+    }
+
+    pub fn get_statements(&self) -> &[Box<dyn Expression>] {
+        &self.statements
+    }
+}
+
+/// Get the variable and value of a statement assigning a variable, like `x = 3`
+fn assignment(statement: &dyn Expression) -> Option<(&str, &dyn Expression)> {
+    let relation = statement.downcast_ref::<RelationExpression>()?;
+    let variable = relation.get_lhs().downcast_ref::<VariableExpression>()?;
+    (relation.get_relation() == Relation::Equal).then(|| (variable.get_name(), relation.get_rhs()))
+}
+
+impl Expression for ProgramExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "program")
+        )
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        Box::new(ProgramExpression::new(
+            self.statements
+                .iter()
+                .map(|statement| statement.simplified())
+                .collect(),
+        ))
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        // Assignments bind their variable for the statements after them
+        let mut bindings = bindings.clone();
+        let mut result: Box<dyn Value> = Box::new(UndefinedValue::new());
+        for statement in &self.statements {
+            result = match assignment(statement.as_ref()) {
+                Some((variable, value)) => {
+                    let value = value.evaluate(&bindings);
+                    bindings.insert(variable.to_string(), value.clone());
+                    value
+                }
+                None => statement.evaluate(&bindings),
+            };
+        }
+        result
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        let mut domain = IntervalUnionValue::all();
+        for statement in &self.statements {
+            domain = domain.intersection(&statement.domain(variable)?);
+        }
+        Some(domain)
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        self.statements
+            .iter()
+            .map(|statement| statement.as_ref())
+            .collect()
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        self.statements.iter_mut().collect()
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        self.statements
+            .iter()
+            .map(|statement| statement.format(options))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::Bindings,
+        parse::{parse, ParserOptions},
+        value::FloatValue,
+    };
+
+    #[test]
+    fn programs() {
+        let options = ParserOptions::default();
+        let expression = parse("x = 3; y = x^2; 2y + x", &options).unwrap();
+        assert_eq!(expression.to_string(), "x = 3; y = (x ^ 2); ((2 * y) + x)");
+        assert_eq!(expression.get_children()[1].get_span(), Some(7..14));
+        let value = expression.evaluate(&Bindings::new());
+        assert_eq!(FloatValue::approximate(value.as_ref()), Some(21.0));
+
+        // A single statement is just an expression
+        assert_eq!(
+            parse("2x + 1;", &options).unwrap().to_string(),
+            "((2 * x) + 1)"
+        );
+        assert!(parse("x = 3;; x", &options).is_err());
+    }
+}
@@ -0,0 +1,240 @@
+use std::{cmp::Ordering, ops::Range};
+
+use crate::{
+    format::FormatOptions,
+    trace,
+    value::{BooleanValue, IntervalUnionValue, UndefinedValue, Value},
+};
+
+use super::{is_same_tree, Bindings, Expression, ValueExpression, VariableExpression};
+
+/// A relation between two expressions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relation {
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+}
+
+impl Relation {
+    /// Check if an ordering satisfies this relation
+    pub fn holds(&self, ordering: Ordering) -> bool {
+        match self {
+            Relation::Equal => ordering == Ordering::Equal,
+            Relation::NotEqual => ordering != Ordering::Equal,
+            Relation::Less => ordering == Ordering::Less,
+            Relation::LessEqual => ordering != Ordering::Greater,
+            Relation::Greater => ordering == Ordering::Greater,
+            Relation::GreaterEqual => ordering != Ordering::Less,
+        }
+    }
+
+    /// Return the relation that holds when both sides are swapped
+    pub fn flipped(&self) -> Self {
+        match self {
+            Relation::Equal => Relation::Equal,
+            Relation::NotEqual => Relation::NotEqual,
+            Relation::Less => Relation::Greater,
+            Relation::LessEqual => Relation::GreaterEqual,
+            Relation::Greater => Relation::Less,
+            Relation::GreaterEqual => Relation::LessEqual,
+        }
+    }
+
+    /// Get the LaTeX symbol for this relation
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Relation::Equal => "=",
+            Relation::NotEqual => "\\ne",
+            Relation::Less => "<",
+            Relation::LessEqual => "\\le",
+            Relation::Greater => ">",
+            Relation::GreaterEqual => "\\ge",
+        }
+    }
+}
+
+/// An expression relating two expressions
+#[derive(Debug, Clone)]
+pub struct RelationExpression {
+    lhs: Box<dyn Expression>,
+    relation: Relation,
+    rhs: Box<dyn Expression>,
+    span: Option<Range<usize>>,
+}
+
+impl RelationExpression {
+    pub fn new(lhs: Box<dyn Expression>, relation: Relation, rhs: Box<dyn Expression>) -> Self {
+        Self {
+            lhs,
+            relation,
+            rhs,
+            span: None,
+        }
+    }
+
+    pub fn get_lhs(&self) -> &dyn Expression {
+        self.lhs.as_ref()
+    }
+
+    pub fn get_relation(&self) -> Relation {
+        self.relation
+    }
+
+    pub fn get_rhs(&self) -> &dyn Expression {
+        self.rhs.as_ref()
+    }
+}
+
+/// Check if an expression is known to be defined for every value of its variables
+fn is_defined(expression: &dyn Expression) -> bool {
+    fn walk<'a>(expression: &'a dyn Expression, variables: &mut Vec<&'a str>) -> bool {
+        if let Some(value) = expression.downcast_ref::<ValueExpression>() {
+            return !value.get_value().is::<UndefinedValue>();
+        }
+        if let Some(variable) = expression.downcast_ref::<VariableExpression>() {
+            variables.push(variable.get_name());
+        }
+        expression
+            .get_children()
+            .into_iter()
+            .all(|child| walk(child, variables))
+    }
+
+    // Expressions without variables are defined if they don't simplify to an undefined value
+    let mut variables = Vec::new();
+    walk(expression, &mut variables)
+        && variables.into_iter().all(|variable| {
+            expression
+                .domain(variable)
+                .is_some_and(|domain| domain.is_all())
+        })
+}
+
+impl Expression for RelationExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "relation")
+        )
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        let lhs = self.lhs.simplified();
+        let rhs = self.rhs.simplified();
+
+        // Relations are only decided where both sides are defined, before and after simplifying
+        let defined = [
+            self.lhs.as_ref(),
+            self.rhs.as_ref(),
+            lhs.as_ref(),
+            rhs.as_ref(),
+        ]
+        .into_iter()
+        .all(is_defined);
+        if !defined {
+            return Box::new(RelationExpression::new(lhs, self.relation, rhs));
+        }
+
+        // Decide relations between comparable values
+        if let (Some(lhs_value), Some(rhs_value)) = (
+            lhs.downcast_ref::<ValueExpression>(),
+            rhs.downcast_ref::<ValueExpression>(),
+        ) {
+            if let Some(ordering) = lhs_value.get_value().cmp(rhs_value.get_value()) {
+                trace::rule("relation", "compare values");
+                return Box::new(ValueExpression::new(Box::new(BooleanValue::new(
+                    self.relation.holds(ordering),
+                ))));
+            }
+        }
+
+        // Identical sides are always equal
+        if is_same_tree(lhs.as_ref(), rhs.as_ref()) {
+            trace::rule("relation", "identical sides");
+            return Box::new(ValueExpression::new(Box::new(BooleanValue::new(
+                self.relation.holds(Ordering::Equal),
+            ))));
+        }
+        Box::new(RelationExpression::new(lhs, self.relation, rhs))
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        let lhs = self.lhs.evaluate(bindings);
+        let rhs = self.rhs.evaluate(bindings);
+        match lhs.cmp(rhs.as_ref()) {
+            Some(ordering) => Box::new(BooleanValue::new(self.relation.holds(ordering))),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        Some(
+            self.lhs
+                .domain(variable)?
+                .intersection(&self.rhs.domain(variable)?),
+        )
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        vec![self.lhs.as_ref(), self.rhs.as_ref()]
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        vec![&mut self.lhs, &mut self.rhs]
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        format!(
+            "{} {} {}",
+            self.lhs.format(options),
+            self.relation.symbol(),
+            self.rhs.format(options)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{parse, ParserOptions};
+
+    #[test]
+    fn numeric_relations() {
+        let options = ParserOptions::default();
+        let simplified = |input: &str| parse(input, &options).unwrap().simplified().to_string();
+        assert_eq!(simplified("\\frac{3}{4} < \\frac{5}{6}"), "true");
+        assert_eq!(simplified("\\pi \\ge 4"), "false");
+        assert_eq!(simplified("2 + 2 \\ne 4"), "false");
+        assert_eq!(simplified("1 \\neq 2"), "true");
+        assert_eq!(simplified("x > 1 + 1"), "x > 2");
+        assert_eq!(simplified("2x \\le x + x"), "true");
+        assert_eq!(simplified("x + 1 < 1 + x"), "false");
+
+        // Sides that are or may be undefined aren't decided
+        assert_eq!(
+            simplified("\\frac{1}{0} = \\frac{1}{0}"),
+            "undefined = undefined"
+        );
+        assert_eq!(simplified("x/x = x/x"), "1 = 1");
+        assert_eq!(
+            simplified("\\frac{1}{x - 1} \\le \\frac{1}{x - 1}"),
+            "(1 / (x - 1)) \\le (1 / (x - 1))"
+        );
+
+        // `\neg` is still negation rather than `\ne` followed by `g`
+        assert!(parse("\\neg 5", &options).is_ok());
+    }
+}
@@ -0,0 +1,112 @@
+use std::{cmp::Ordering, ops::Range};
+
+use crate::{
+    format::FormatOptions,
+    value::{IntervalUnionValue, UndefinedValue, Value},
+};
+
+use super::{Bindings, Expression};
+
+/// An expression with several possible results, like both branches of `2 \pm 3`
+#[derive(Debug, Clone)]
+pub struct ResultSetExpression {
+    elements: Vec<Box<dyn Expression>>,
+    span: Option<Range<usize>>,
+}
+
+impl ResultSetExpression {
+    pub fn new(elements: Vec<Box<dyn Expression>>) -> Self {
+        Self {
+            elements,
+            span: None,
+        }
+    }
+
+    pub fn get_elements(&self) -> &[Box<dyn Expression>] {
+        &self.elements
+    }
+}
+
+impl Expression for ResultSetExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "result set")
+        )
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        // Simplify each result, dropping any that turn out the same as an earlier one
+        let mut elements: Vec<Box<dyn Expression>> = Vec::new();
+        for element in &self.elements {
+            let element = element.simplified();
+            if !elements
+                .iter()
+                .any(|previous| previous.to_string() == element.to_string())
+            {
+                elements.push(element);
+            }
+        }
+        if elements.len() == 1 {
+            return elements.pop().unwrap();
+        }
+        Box::new(ResultSetExpression::new(elements))
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        // Values can only hold a single result
+        let mut values = self
+            .elements
+            .iter()
+            .map(|element| element.evaluate(bindings));
+        let Some(first) = values.next() else {
+            return Box::new(UndefinedValue::new());
+        };
+        if values.all(|value| value.cmp(first.as_ref()) == Some(Ordering::Equal)) {
+            first
+        } else {
+            Box::new(UndefinedValue::new())
+        }
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        let mut domain = IntervalUnionValue::all();
+        for element in &self.elements {
+            domain = domain.intersection(&element.domain(variable)?);
+        }
+        Some(domain)
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        self.elements
+            .iter()
+            .map(|element| element.as_ref())
+            .collect()
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        self.elements.iter_mut().collect()
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        let elements = self
+            .elements
+            .iter()
+            .map(|element| element.format(options))
+            .collect::<Vec<_>>();
+        format!(
+            "\\{{{}\\}}",
+            elements.join(&format!("{} ", options.get_locale().argument_separator()))
+        )
+    }
+}
@@ -0,0 +1,349 @@
+use std::ops::Range;
+
+use num::{BigInt, Integer as _, One as _, Zero as _};
+
+use crate::{
+    format::FormatOptions,
+    numeric::extract_nth_power,
+    solve::solve,
+    trace,
+    value::{
+        FloatValue, IntervalUnionValue, RationalValue, Sign, SurdValue, UndefinedValue, Value,
+    },
+};
+
+use super::{
+    AbsExpression, Bindings, Expression, MultiplicationExpression, PowerExpression, Relation,
+    RelationExpression, ValueExpression,
+};
+
+/// The most bits a radicand may have after moving its denominator under the root
+const MAX_RADICAND_BITS: u64 = 1 << 16;
+
+/// An nth root expression
+#[derive(Debug, Clone)]
+pub struct RootExpression {
+    radicand: Box<dyn Expression>,
+    index: Box<dyn Expression>,
+    span: Option<Range<usize>>,
+}
+
+impl RootExpression {
+    pub fn new(radicand: Box<dyn Expression>, index: Box<dyn Expression>) -> Self {
+        Self {
+            radicand,
+            index,
+            span: None,
+        }
+    }
+
+    /// Construct a new square root expression
+    pub fn square(radicand: Box<dyn Expression>) -> Self {
+        Self::new(
+            radicand,
+            Box::new(ValueExpression::new(Box::new(RationalValue::new(
+                Sign::Positive,
+                2_u32,
+                1_u32,
+            )))),
+        )
+    }
+
+    pub fn get_radicand(&self) -> &dyn Expression {
+        self.radicand.as_ref()
+    }
+
+    pub fn get_index(&self) -> &dyn Expression {
+        self.index.as_ref()
+    }
+}
+
+/// Get the index of a root as an integer of at least two
+fn integer_index(value: &dyn Value) -> Option<u32> {
+    let index = value.downcast_ref::<RationalValue>()?.to_integer()?;
+    u32::try_from(index).ok().filter(|&index| index >= 2)
+}
+
+/// Take the nth root of a rational value, pulling perfect powers out in front of the root
+///
+/// Returns the coefficient and the radicand left under the root, or `None` if the root isn't real
+fn extract(value: &RationalValue, n: u32) -> Option<(RationalValue, RationalValue)> {
+    if value.is_negative() && n.is_multiple_of(2) {
+        return None;
+    }
+
+    // Move the denominator under the root, so only an integer is left there
+    let value = value.simplified();
+    let denominator = value.get_denominator();
+    if denominator.bits().saturating_mul(u64::from(n - 1)) > MAX_RADICAND_BITS {
+        return None;
+    }
+    let (outside, inside) = extract_nth_power(&(value.get_numerator() * denominator.pow(n - 1)), n);
+    Some((
+        RationalValue::new(*value.get_sign(), outside, denominator.clone()).simplified(),
+        RationalValue::new(Sign::Positive, inside, 1_u32),
+    ))
+}
+
+impl Expression for RootExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", name = "simplify", skip_all, fields(node = "root"))
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        // Simplify both the radicand and the index
+        let radicand = self.radicand.simplified();
+        let index = self.index.simplified();
+
+        // Pull perfect powers out of a rational radicand
+        let values = (
+            radicand
+                .downcast_ref::<ValueExpression>()
+                .and_then(|radicand| radicand.get_value().downcast_ref::<RationalValue>()),
+            index
+                .downcast_ref::<ValueExpression>()
+                .and_then(|index| integer_index(index.get_value())),
+        );
+        if let (Some(value), Some(n)) = values {
+            if let Some((coefficient, inside)) = extract(value, n) {
+                if inside.get_numerator().is_one() {
+                    trace::rule("root", "extract perfect powers");
+                    return Box::new(ValueExpression::new(Box::new(coefficient)));
+                }
+                let root = Box::new(RootExpression::new(
+                    Box::new(ValueExpression::new(Box::new(inside))),
+                    index,
+                ));
+                if coefficient.get_numerator().is_one()
+                    && coefficient.get_denominator().is_one()
+                    && !coefficient.is_negative()
+                {
+                    return root;
+                }
+                trace::rule("root", "extract perfect powers");
+                return Box::new(MultiplicationExpression::new(
+                    Box::new(ValueExpression::new(Box::new(coefficient))),
+                    root,
+                ));
+            }
+        }
+        let Some(n) = index
+            .downcast_ref::<ValueExpression>()
+            .and_then(|index| integer_index(index.get_value()))
+        else {
+            return Box::new(RootExpression::new(radicand, index));
+        };
+
+        // Nested roots multiply their indices
+        if let Some(m) = radicand.downcast_ref::<RootExpression>().and_then(|root| {
+            root.index
+                .downcast_ref::<ValueExpression>()
+                .and_then(|index| integer_index(index.get_value()))
+        }) {
+            trace::rule("root", "nested roots");
+            let inner = radicand.downcast_ref::<RootExpression>().unwrap();
+            return RootExpression::new(
+                inner.radicand.clone(),
+                Box::new(ValueExpression::new(Box::new(RationalValue::from(
+                    BigInt::from(n) * m,
+                )))),
+            )
+            .simplified();
+        }
+
+        // Divide the exponent of a power by the index when that leaves an integer exponent
+        if let Some((base, exponent)) =
+            radicand
+                .downcast_ref::<PowerExpression>()
+                .and_then(|power| {
+                    let exponent = power
+                        .get_exponent()
+                        .downcast_ref::<ValueExpression>()?
+                        .get_value()
+                        .downcast_ref::<RationalValue>()?
+                        .to_integer()?;
+                    Some((power.get_base(), exponent))
+                })
+        {
+            let n = BigInt::from(n);
+            if (&exponent % &n).is_zero() {
+                trace::rule("root", "rational exponent");
+                let exponent = exponent / &n;
+
+                // Even roots are never negative, so an odd power of the base needs its magnitude
+                let base = if n.is_even() && exponent.is_odd() {
+                    Box::new(AbsExpression::new(dyn_clone::clone_box(base)))
+                } else {
+                    dyn_clone::clone_box(base)
+                };
+                if exponent.is_one() {
+                    return base.simplified();
+                }
+                return PowerExpression::new(
+                    base,
+                    Box::new(ValueExpression::new(Box::new(RationalValue::from(
+                        exponent,
+                    )))),
+                )
+                .simplified();
+            }
+        }
+        Box::new(RootExpression::new(radicand, index))
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        let radicand = self.radicand.evaluate(bindings);
+        let index = self.index.evaluate(bindings);
+
+        // Prefer the exact result, falling back to an approximation
+        if let (Some(value), Some(n)) = (
+            radicand.downcast_ref::<RationalValue>(),
+            integer_index(index.as_ref()),
+        ) {
+            match extract(value, n) {
+                Some((coefficient, inside)) if inside.get_numerator().is_one() => {
+                    return Box::new(coefficient)
+                }
+
+                // Square roots stay exact as surds
+                Some(_) if n == 2 => {
+                    if let Some(root) = SurdValue::sqrt(value) {
+                        return root;
+                    }
+                }
+                None => return Box::new(UndefinedValue::new()),
+                _ => {}
+            }
+        }
+        let (Some(radicand), Some(index)) = (
+            FloatValue::approximate(radicand.as_ref()),
+            FloatValue::approximate(index.as_ref()),
+        ) else {
+            return Box::new(UndefinedValue::new());
+        };
+
+        // Odd roots of negative values are negative
+        let root = if radicand < 0.0 && index.fract() == 0.0 && index % 2.0 != 0.0 {
+            -(-radicand).powf(index.recip())
+        } else {
+            radicand.powf(index.recip())
+        };
+        if root.is_finite() {
+            Box::new(FloatValue::new(root))
+        } else {
+            Box::new(UndefinedValue::new())
+        }
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        let domain = self
+            .radicand
+            .domain(variable)?
+            .intersection(&self.index.domain(variable)?);
+
+        // Even roots are only defined for non-negative radicands
+        let index = self.index.simplified();
+        let n = integer_index(index.downcast_ref::<ValueExpression>()?.get_value())?;
+        if n % 2 == 1 {
+            return Some(domain);
+        }
+        let restriction = RelationExpression::new(
+            self.radicand.clone(),
+            Relation::GreaterEqual,
+            Box::new(ValueExpression::new(Box::new(RationalValue::zero()))),
+        );
+        Some(domain.intersection(&solve(&restriction, variable)?))
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        vec![self.radicand.as_ref(), self.index.as_ref()]
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        vec![&mut self.radicand, &mut self.index]
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        let index = self
+            .index
+            .downcast_ref::<ValueExpression>()
+            .and_then(|index| integer_index(index.get_value()));
+        match index {
+            Some(2) => format!("\\sqrt{{{}}}", self.radicand.format(options)),
+            _ => format!(
+                "\\sqrt[{}]{{{}}}",
+                self.index.format(options),
+                self.radicand.format(options)
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::Bindings,
+        numeric::approximate,
+        parse::{parse, ParserOptions},
+        value::{FloatValue, RationalValue},
+    };
+
+    #[test]
+    fn roots() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap();
+        assert_eq!(
+            parsed("\\sqrt{8}").simplified().to_string(),
+            "(2 * \\sqrt{2})"
+        );
+        assert_eq!(
+            parsed("\\sqrt[3]{0 - 54}").simplified().to_string(),
+            "(-3 * \\sqrt[3]{2})"
+        );
+        assert_eq!(
+            parsed("\\sqrt{\\frac{9}{4}}").simplified().to_string(),
+            "3/2"
+        );
+        assert_eq!(
+            parsed("\\sqrt{\\frac{1}{2}}").simplified().to_string(),
+            "(1/2 * \\sqrt{2})"
+        );
+        assert_eq!(parsed("\\sqrt{x}").simplified().to_string(), "\\sqrt{x}");
+        assert_eq!(
+            parsed("\\sqrt{50}").simplified().to_string(),
+            "(5 * \\sqrt{2})"
+        );
+
+        // Powers under a root divide their exponent by the index
+        assert_eq!(parsed("\\sqrt{x^4}").simplified().to_string(), "(x ^ 2)");
+        assert_eq!(parsed("\\sqrt{x^2}").simplified().to_string(), "|x|");
+        assert_eq!(parsed("\\sqrt[3]{y^6}").simplified().to_string(), "(y ^ 2)");
+        assert_eq!(
+            parsed("\\sqrt{x^3}").simplified().to_string(),
+            "\\sqrt{(x ^ 3)}"
+        );
+        assert_eq!(
+            parsed("\\sqrt{\\sqrt[3]{x}}").simplified().to_string(),
+            "\\sqrt[6]{x}"
+        );
+
+        // Even roots of negative values aren't real
+        let value = parsed("\\sqrt{0 - 4}").evaluate(&Bindings::new());
+        assert_eq!(FloatValue::approximate(value.as_ref()), None);
+        let domain = parsed("\\sqrt{x - 1}").domain("x").unwrap();
+        assert!(domain.contains(&RationalValue::one()));
+        assert!(!domain.contains(&RationalValue::zero()));
+        assert_eq!(
+            approximate(parsed("\\sqrt{8}").as_ref(), 10).unwrap(),
+            "2.8284271247"
+        );
+    }
+}
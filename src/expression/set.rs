@@ -0,0 +1,152 @@
+use std::{cmp::Ordering, ops::Range};
+
+use crate::{
+    format::FormatOptions,
+    trace,
+    value::{IntervalUnionValue, UndefinedValue, Value},
+};
+
+use super::{Bindings, Expression, ValueExpression};
+
+/// A finite set of expressions, like `\{1, 2, 3\}`
+#[derive(Debug, Clone)]
+pub struct SetExpression {
+    elements: Vec<Box<dyn Expression>>,
+    span: Option<Range<usize>>,
+}
+
+impl SetExpression {
+    pub fn new(elements: Vec<Box<dyn Expression>>) -> Self {
+        Self {
+            elements,
+            span: None,
+        }
+    }
+
+    pub fn get_elements(&self) -> &[Box<dyn Expression>] {
+        &self.elements
+    }
+
+    /// Check if an expression is an element of this set, or `None` if it can't be told yet
+    pub fn contains(&self, element: &dyn Expression) -> Option<bool> {
+        let mut known = true;
+        for member in &self.elements {
+            match same(member.as_ref(), element) {
+                Some(true) => return Some(true),
+                Some(false) => {}
+                None => known = false,
+            }
+        }
+        known.then_some(false)
+    }
+}
+
+/// Check if two expressions are the same element, or `None` if it can't be told yet
+fn same(lhs: &dyn Expression, rhs: &dyn Expression) -> Option<bool> {
+    if lhs.to_string() == rhs.to_string() {
+        return Some(true);
+    }
+    let lhs = lhs.downcast_ref::<ValueExpression>()?.get_value();
+    let rhs = rhs.downcast_ref::<ValueExpression>()?.get_value();
+    Some(lhs.cmp(rhs) == Some(Ordering::Equal))
+}
+
+impl Expression for SetExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", name = "simplify", skip_all, fields(node = "set"))
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        // Simplify each element, dropping any that are already in the set
+        let mut set = SetExpression::new(Vec::new());
+        for element in &self.elements {
+            let element = element.simplified();
+            if set.contains(element.as_ref()) == Some(true) {
+                trace::rule("set", "remove duplicate");
+            } else {
+                set.elements.push(element);
+            }
+        }
+        Box::new(set)
+    }
+
+    fn evaluate(&self, _bindings: &Bindings) -> Box<dyn Value> {
+        // Values can only hold a single element
+        Box::new(UndefinedValue::new())
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        let mut domain = IntervalUnionValue::all();
+        for element in &self.elements {
+            domain = domain.intersection(&element.domain(variable)?);
+        }
+        Some(domain)
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        self.elements
+            .iter()
+            .map(|element| element.as_ref())
+            .collect()
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        self.elements.iter_mut().collect()
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        let elements = self
+            .elements
+            .iter()
+            .map(|element| element.format(options))
+            .collect::<Vec<_>>();
+        format!(
+            "\\{{{}\\}}",
+            elements.join(&format!("{} ", options.get_locale().argument_separator()))
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{parse, ParserOptions};
+
+    use super::SetExpression;
+
+    #[test]
+    fn sets() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap();
+        assert_eq!(
+            parsed("\\{1, x, 2 + 1\\}").to_string(),
+            "\\{1, x, (2 + 1)\\}"
+        );
+        assert_eq!(
+            parsed("\\left\\{3, 1 + 2, \\frac{6}{2}, x\\right\\}")
+                .simplified()
+                .to_string(),
+            "\\{3, x\\}"
+        );
+
+        // Membership is only known when every element can be compared
+        let set = parsed("\\{1, 2, x\\}");
+        let set = set.downcast_ref::<SetExpression>().unwrap();
+        assert_eq!(set.contains(parsed("2").as_ref()), Some(true));
+        assert_eq!(set.contains(parsed("x").as_ref()), Some(true));
+        assert_eq!(set.contains(parsed("3").as_ref()), None);
+        let set = parsed("\\{1, 2\\}");
+        let set = set.downcast_ref::<SetExpression>().unwrap();
+        assert_eq!(set.contains(parsed("3").as_ref()), Some(false));
+
+        // Braces around a single expression are still a group
+        assert_eq!(parsed("\\{1 + 2\\}").simplified().to_string(), "3");
+    }
+}
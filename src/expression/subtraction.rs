@@ -1,36 +1,108 @@
-use super::{Expression, ValueExpression};
+use std::ops::Range;
+
+use crate::{
+    format::FormatOptions,
+    trace,
+    value::{IntervalUnionValue, Value},
+};
+
+use super::{is_exact_combination, sum, Bindings, Expression, ValueExpression};
 
 /// A subtraction expression
 #[derive(Debug, Clone)]
 pub struct SubtractionExpression {
     lhs: Box<dyn Expression>,
     rhs: Box<dyn Expression>,
+    span: Option<Range<usize>>,
 }
 
 impl SubtractionExpression {
     pub fn new(lhs: Box<dyn Expression>, rhs: Box<dyn Expression>) -> Self {
-        Self { lhs, rhs }
+        Self {
+            lhs,
+            rhs,
+            span: None,
+        }
+    }
+
+    pub fn get_lhs(&self) -> &dyn Expression {
+        self.lhs.as_ref()
+    }
+
+    pub fn get_rhs(&self) -> &dyn Expression {
+        self.rhs.as_ref()
     }
 }
 
 impl Expression for SubtractionExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "subtraction")
+        )
+    )]
     fn simplified(&self) -> Box<dyn Expression> {
         // Simplify both sides
         let lhs = self.lhs.simplified();
         let rhs = self.rhs.simplified();
 
-        // Combine if two values
-        if let (Some(lhs), Some(rhs)) = (
+        // Combine if two values with an exact result
+        if let (Some(lhs_value), Some(rhs_value)) = (
             lhs.downcast_ref::<ValueExpression>(),
             rhs.downcast_ref::<ValueExpression>(),
         ) {
-            Box::new(ValueExpression::new(lhs.get_value().sub(rhs.get_value())))
-        } else {
-            Box::new(SubtractionExpression::new(lhs, rhs))
+            let value = lhs_value.get_value().sub(rhs_value.get_value());
+            if is_exact_combination(value.as_ref(), lhs_value.get_value(), rhs_value.get_value()) {
+                trace::rule("subtraction", "combine values");
+                return Box::new(ValueExpression::new(value));
+            }
+        }
+
+        // Flatten into a sum of the negated right side, collecting like terms
+        if !lhs.is::<ValueExpression>() || !rhs.is::<ValueExpression>() {
+            return sum::combine(vec![(lhs, false), (rhs, true)]);
         }
+        Box::new(SubtractionExpression::new(lhs, rhs))
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        self.lhs
+            .evaluate(bindings)
+            .sub(self.rhs.evaluate(bindings).as_ref())
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        Some(
+            self.lhs
+                .domain(variable)?
+                .intersection(&self.rhs.domain(variable)?),
+        )
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        vec![self.lhs.as_ref(), self.rhs.as_ref()]
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        vec![&mut self.lhs, &mut self.rhs]
     }
 
-    fn to_string(&self) -> String {
-        format!("({} - {})", self.lhs.to_string(), self.rhs.to_string())
+    fn format(&self, options: &FormatOptions) -> String {
+        format!(
+            "({} - {})",
+            self.lhs.format(options),
+            self.rhs.format(options)
+        )
     }
 }
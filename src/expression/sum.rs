@@ -0,0 +1,300 @@
+use std::{cmp::Ordering, ops::Range};
+
+use crate::{
+    format::FormatOptions,
+    trace,
+    value::{IntervalUnionValue, RationalValue, Value},
+};
+
+use super::{
+    is_exact_combination, is_zero, AdditionExpression, Bindings, Expression, FactorsExpression,
+    MultiplicationExpression, NegationExpression, SubtractionExpression, ValueExpression,
+};
+
+/// A sum of any number of terms, like `a + b + c`
+#[derive(Debug, Clone)]
+pub struct SumExpression {
+    terms: Vec<Box<dyn Expression>>,
+    span: Option<Range<usize>>,
+}
+
+impl SumExpression {
+    pub fn new(terms: Vec<Box<dyn Expression>>) -> Self {
+        Self { terms, span: None }
+    }
+
+    pub fn get_terms(&self) -> &[Box<dyn Expression>] {
+        &self.terms
+    }
+}
+
+/// Add the terms of an expression to a list, flattening nested sums and differences
+///
+/// The terms are negated if the expression is subtracted
+fn flatten(expression: &dyn Expression, negative: bool, terms: &mut Vec<Box<dyn Expression>>) {
+    if let Some(sum) = expression.downcast_ref::<SumExpression>() {
+        for term in &sum.terms {
+            flatten(term.as_ref(), negative, terms);
+        }
+    } else if let Some(addition) = expression.downcast_ref::<AdditionExpression>() {
+        flatten(addition.get_lhs(), negative, terms);
+        flatten(addition.get_rhs(), negative, terms);
+    } else if let Some(difference) = expression.downcast_ref::<SubtractionExpression>() {
+        flatten(difference.get_lhs(), negative, terms);
+        flatten(difference.get_rhs(), !negative, terms);
+    } else if let Some(negation) = expression.downcast_ref::<NegationExpression>() {
+        flatten(negation.get_argument(), !negative, terms);
+    } else if negative {
+        terms.push(NegationExpression::new(dyn_clone::clone_box(expression)).simplified());
+    } else {
+        terms.push(dyn_clone::clone_box(expression));
+    }
+}
+
+/// Split a term into its rational coefficient and the rest of the term, like `3x` into `3` and `x`
+fn split_coefficient(term: &dyn Expression) -> (RationalValue, Box<dyn Expression>) {
+    if let Some(negation) = term.downcast_ref::<NegationExpression>() {
+        let (coefficient, rest) = split_coefficient(negation.get_argument());
+        return (coefficient.get_opposite(), rest);
+    }
+    let rational = |factor: &dyn Expression| {
+        factor
+            .downcast_ref::<ValueExpression>()
+            .and_then(|factor| factor.get_value().downcast_ref::<RationalValue>())
+            .cloned()
+    };
+    if let Some(product) = term.downcast_ref::<MultiplicationExpression>() {
+        if let Some(coefficient) = rational(product.get_lhs()) {
+            return (coefficient, dyn_clone::clone_box(product.get_rhs()));
+        }
+    }
+    if let Some(product) = term.downcast_ref::<FactorsExpression>() {
+        if let Some(coefficient) = rational(product.get_factors()[0].as_ref()) {
+            let rest = FactorsExpression::new(product.get_factors()[1..].to_vec());
+            return (coefficient, Box::new(rest));
+        }
+    }
+    (RationalValue::one(), dyn_clone::clone_box(term))
+}
+
+/// Get the positive term that a negative term subtracts, like `2x` from `-2x`
+fn subtracted(term: &dyn Expression) -> Option<Box<dyn Expression>> {
+    if let Some(negation) = term.downcast_ref::<NegationExpression>() {
+        return Some(dyn_clone::clone_box(negation.get_argument()));
+    }
+    let opposite = |factor: &dyn Expression| {
+        factor
+            .downcast_ref::<ValueExpression>()
+            .map(ValueExpression::get_value)
+            .filter(|value| value.cmp(&RationalValue::zero()) == Some(Ordering::Less))
+            .map(|value| {
+                let opposite = RationalValue::one().get_opposite().mul(value);
+                Box::new(ValueExpression::new(opposite)) as Box<dyn Expression>
+            })
+    };
+    if let Some(product) = term.downcast_ref::<MultiplicationExpression>() {
+        let coefficient = opposite(product.get_lhs())?;
+        return Some(Box::new(MultiplicationExpression::new(
+            coefficient,
+            dyn_clone::clone_box(product.get_rhs()),
+        )));
+    }
+    if let Some(product) = term.downcast_ref::<FactorsExpression>() {
+        let mut factors = product.get_factors().to_vec();
+        factors[0] = opposite(factors[0].as_ref())?;
+        return Some(Box::new(FactorsExpression::new(factors)));
+    }
+    opposite(term)
+}
+
+/// Build a term from its coefficient and the rest of the term
+fn with_coefficient(coefficient: RationalValue, rest: Box<dyn Expression>) -> Box<dyn Expression> {
+    if coefficient.cmp(&RationalValue::one()) == Some(Ordering::Equal) {
+        rest
+    } else if coefficient.cmp(&RationalValue::one().get_opposite()) == Some(Ordering::Equal) {
+        Box::new(NegationExpression::new(rest))
+    } else {
+        Box::new(MultiplicationExpression::new(
+            Box::new(ValueExpression::new(Box::new(coefficient))),
+            rest,
+        ))
+    }
+}
+
+/// Combine simplified terms into a sum, paired with whether they are subtracted instead of added
+pub(super) fn combine(operands: Vec<(Box<dyn Expression>, bool)>) -> Box<dyn Expression> {
+    let mut terms = Vec::new();
+    for (operand, negative) in operands {
+        flatten(operand.as_ref(), negative, &mut terms);
+    }
+
+    // Combine the values with exact sums, and collect like terms by their coefficients
+    let mut values: Vec<Box<dyn Value>> = Vec::new();
+    let mut groups: Vec<(String, RationalValue, Box<dyn Expression>)> = Vec::new();
+    for term in terms {
+        if let Some(value) = term.downcast_ref::<ValueExpression>() {
+            let value = value.get_value();
+            let combined = values.iter_mut().any(|sum| {
+                let result = sum.add(value);
+                let exact = is_exact_combination(result.as_ref(), sum.as_ref(), value);
+                if exact {
+                    *sum = result;
+                }
+                exact
+            });
+            if !combined {
+                values.push(dyn_clone::clone_box(value));
+            }
+            continue;
+        }
+        let (coefficient, rest) = split_coefficient(term.as_ref());
+        let key = rest.to_string();
+        match groups.iter_mut().find(|(other, _, _)| *other == key) {
+            Some((_, total, _)) => {
+                trace::rule("sum", "collect like terms");
+                *total = total
+                    .add(&coefficient)
+                    .downcast_ref::<RationalValue>()
+                    .expect("Unexpected error: a sum of rationals isn't rational!")
+                    .clone();
+            }
+            None => groups.push((key, coefficient, rest)),
+        }
+    }
+
+    // Write the terms in a canonical order, with the values last
+    groups.sort_by(|(lhs, _, _), (rhs, _, _)| lhs.cmp(rhs));
+    let mut terms = groups
+        .into_iter()
+        .filter(|(_, coefficient, _)| !coefficient.is_zero())
+        .map(|(_, coefficient, rest)| with_coefficient(coefficient, rest).simplified())
+        .collect::<Vec<_>>();
+    terms.extend(
+        values
+            .into_iter()
+            .filter(|value| !is_zero(value.as_ref()))
+            .map(|value| Box::new(ValueExpression::new(value)) as Box<dyn Expression>),
+    );
+
+    match terms.len() {
+        0 => Box::new(ValueExpression::new(Box::new(RationalValue::zero()))),
+        1 => terms.pop().unwrap(),
+        2 => {
+            let rhs = terms.pop().unwrap();
+            let lhs = terms.pop().unwrap();
+            // A negative term is subtracted, from the other term if that one isn't negative
+            match (subtracted(lhs.as_ref()), subtracted(rhs.as_ref())) {
+                (_, Some(rhs)) => Box::new(SubtractionExpression::new(lhs, rhs)),
+                (Some(lhs), None) => Box::new(SubtractionExpression::new(rhs, lhs)),
+                (None, None) => Box::new(AdditionExpression::new(lhs, rhs)),
+            }
+        }
+        _ => Box::new(SumExpression::new(terms)),
+    }
+}
+
+impl Expression for SumExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", name = "simplify", skip_all, fields(node = "sum"))
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        combine(
+            self.terms
+                .iter()
+                .map(|term| (term.simplified(), false))
+                .collect(),
+        )
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        let mut terms = self.terms.iter();
+        let first = terms.next().map_or_else(
+            || Box::new(RationalValue::zero()) as Box<dyn Value>,
+            |term| term.evaluate(bindings),
+        );
+        terms.fold(first, |sum, term| sum.add(term.evaluate(bindings).as_ref()))
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        let mut domain = IntervalUnionValue::all();
+        for term in &self.terms {
+            domain = domain.intersection(&term.domain(variable)?);
+        }
+        Some(domain)
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        self.terms.iter().map(|term| term.as_ref()).collect()
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        self.terms.iter_mut().collect()
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        // Negative terms after the first are written as differences
+        let mut terms = self.terms.iter();
+        let mut formatted = terms
+            .next()
+            .map_or_else(String::new, |term| term.format(options));
+        for term in terms {
+            match subtracted(term.as_ref()) {
+                Some(term) => formatted.push_str(&format!(" - {}", term.format(options))),
+                None => formatted.push_str(&format!(" + {}", term.format(options))),
+            }
+        }
+        format!("({})", formatted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::Bindings,
+        parse::{parse, ParserOptions},
+        value::{FloatValue, RationalValue},
+    };
+    use num::BigInt;
+
+    #[test]
+    fn sums() {
+        let options = ParserOptions::default();
+        let simplified = |input: &str| parse(input, &options).unwrap().simplified().to_string();
+        assert_eq!(simplified("1 + x + 2 + y"), "(x + y + 3)");
+        assert_eq!(simplified("x + 2x + (y + 3x)"), "((6 * x) + y)");
+        assert_eq!(simplified("z + (y + x)"), "(x + y + z)");
+        assert_eq!(simplified("x + 1 + -x"), "1");
+        assert_eq!(simplified("2x + -(2x) + y"), "y");
+        assert_eq!(simplified("2xy + y \\cdot 3x"), "(5 * x * y)");
+        assert_eq!(simplified("\\pi + 1 + 2 + x"), "(x + \\pi + 3)");
+
+        // Differences are sums of negated terms, so like terms cancel across them
+        assert_eq!(simplified("x - x"), "0");
+        assert_eq!(simplified("x - 2x"), "-x");
+        assert_eq!(simplified("(x + 1) - (x + 1)"), "0");
+        assert_eq!(simplified("1 + \\pi - \\pi"), "1");
+        assert_eq!(simplified("x - (y - x)"), "((2 * x) - y)");
+        assert_eq!(simplified("3 - x"), "(3 - x)");
+        assert_eq!(simplified("x - \\pi"), "(x - \\pi)");
+        assert_eq!(simplified("x - 2y + 1"), "(x - (2 * y) + 1)");
+
+        let expression = parse("1 + x + 2 + y", &options).unwrap().simplified();
+        let mut bindings = Bindings::new();
+        bindings.insert("x".to_string(), Box::new(FloatValue::new(0.5)));
+        bindings.insert(
+            "y".to_string(),
+            Box::new(RationalValue::from(BigInt::from(2))),
+        );
+        let value = expression.evaluate(&bindings);
+        assert_eq!(FloatValue::approximate(value.as_ref()), Some(5.5));
+    }
+}
@@ -0,0 +1,269 @@
+use std::ops::Range;
+
+use num::ToPrimitive as _;
+
+use crate::{
+    format::FormatOptions,
+    trace,
+    value::{IntervalUnionValue, RationalValue, UndefinedValue, Value},
+};
+
+use super::{
+    Bindings, Expression, ProductExpression, SumExpression, ValueExpression, VariableExpression,
+};
+
+/// The most terms a summation is expanded into
+const MAX_EXPANDED_TERMS: i64 = 10_000;
+
+/// A summation expression, like `\sum_{i=1}^{n} i`
+#[derive(Debug, Clone)]
+pub struct SummationExpression {
+    index: String,
+    lower: Box<dyn Expression>,
+    upper: Box<dyn Expression>,
+    body: Box<dyn Expression>,
+    span: Option<Range<usize>>,
+}
+
+impl SummationExpression {
+    pub fn new(
+        index: impl Into<String>,
+        lower: Box<dyn Expression>,
+        upper: Box<dyn Expression>,
+        body: Box<dyn Expression>,
+    ) -> Self {
+        Self {
+            index: index.into(),
+            lower,
+            upper,
+            body,
+            span: None,
+        }
+    }
+
+    pub fn get_index(&self) -> &str {
+        &self.index
+    }
+
+    pub fn get_lower(&self) -> &dyn Expression {
+        self.lower.as_ref()
+    }
+
+    pub fn get_upper(&self) -> &dyn Expression {
+        self.upper.as_ref()
+    }
+
+    pub fn get_body(&self) -> &dyn Expression {
+        self.body.as_ref()
+    }
+}
+
+/// Get the integer range of a summation or product's bounds, if it's small enough to expand
+pub(super) fn integer_bounds(lower: &dyn Value, upper: &dyn Value) -> Option<(i64, i64)> {
+    let lower = lower
+        .downcast_ref::<RationalValue>()?
+        .to_integer()?
+        .to_i64()?;
+    let upper = upper
+        .downcast_ref::<RationalValue>()?
+        .to_integer()?
+        .to_i64()?;
+    if upper.saturating_sub(lower) >= MAX_EXPANDED_TERMS {
+        return None;
+    }
+    Some((lower, upper))
+}
+
+/// Replace every occurrence of a variable in an expression with an integer
+pub(super) fn substituted(
+    expression: &dyn Expression,
+    variable: &str,
+    value: i64,
+) -> Box<dyn Expression> {
+    if let Some(expression) = expression.downcast_ref::<VariableExpression>() {
+        if expression.get_name() == variable {
+            return Box::new(ValueExpression::new(Box::new(RationalValue::from(
+                num::BigInt::from(value),
+            ))));
+        }
+    }
+
+    // An inner summation or product over the same index hides the variable in its body
+    if let Some(summation) = expression.downcast_ref::<SummationExpression>() {
+        if summation.get_index() == variable {
+            return Box::new(SummationExpression::new(
+                summation.index.clone(),
+                substituted(summation.get_lower(), variable, value),
+                substituted(summation.get_upper(), variable, value),
+                summation.body.clone(),
+            ));
+        }
+    }
+
+    if let Some(product) = expression.downcast_ref::<ProductExpression>() {
+        if product.get_index() == variable {
+            return Box::new(ProductExpression::new(
+                product.get_index(),
+                substituted(product.get_lower(), variable, value),
+                substituted(product.get_upper(), variable, value),
+                dyn_clone::clone_box(product.get_body()),
+            ));
+        }
+    }
+
+    let mut expression = dyn_clone::clone_box(expression);
+    for child in expression.get_children_mut() {
+        *child = substituted(child.as_ref(), variable, value);
+    }
+    expression
+}
+
+impl Expression for SummationExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "simplify",
+            skip_all,
+            fields(node = "summation")
+        )
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        let lower = self.lower.simplified();
+        let upper = self.upper.simplified();
+
+        // Expand a sum with integer bounds into its terms
+        if let (Some(lower_value), Some(upper_value)) = (
+            lower.downcast_ref::<ValueExpression>(),
+            upper.downcast_ref::<ValueExpression>(),
+        ) {
+            if let Some((start, end)) =
+                integer_bounds(lower_value.get_value(), upper_value.get_value())
+            {
+                trace::rule("summation", "expand terms");
+                let terms = (start..=end)
+                    .map(|index| substituted(self.body.as_ref(), &self.index, index))
+                    .collect();
+                return SumExpression::new(terms).simplified();
+            }
+        }
+        Box::new(SummationExpression::new(
+            self.index.clone(),
+            lower,
+            upper,
+            self.body.simplified(),
+        ))
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        let lower = self.lower.evaluate(bindings);
+        let upper = self.upper.evaluate(bindings);
+        let Some((start, end)) = integer_bounds(lower.as_ref(), upper.as_ref()) else {
+            return Box::new(UndefinedValue::new());
+        };
+
+        // Add up the body at each index
+        let mut bindings = bindings.clone();
+        let mut sum: Box<dyn Value> = Box::new(RationalValue::zero());
+        for index in start..=end {
+            bindings.insert(
+                self.index.clone(),
+                Box::new(RationalValue::from(num::BigInt::from(index))),
+            );
+            sum = sum.add(self.body.evaluate(&bindings).as_ref());
+        }
+        sum
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        // The index isn't free outside of the summation
+        if variable == self.index {
+            return None;
+        }
+        Some(
+            self.lower
+                .domain(variable)?
+                .intersection(&self.upper.domain(variable)?)
+                .intersection(&self.body.domain(variable)?),
+        )
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        vec![self.lower.as_ref(), self.upper.as_ref(), self.body.as_ref()]
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        vec![&mut self.lower, &mut self.upper, &mut self.body]
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        format!(
+            "\\sum_{{{}={}}}^{{{}}}{{{}}}",
+            self.index,
+            self.lower.format(options),
+            self.upper.format(options),
+            self.body.format(options)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::Bindings,
+        parse::{parse, ParserOptions},
+        value::{FloatValue, RationalValue},
+    };
+
+    #[test]
+    fn summation() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap();
+        assert_eq!(parsed("\\sum_{i=1}^{4} i^2").simplified().to_string(), "30");
+        assert_eq!(
+            parsed("\\sum_{i=1}^{3} 2i + 1").simplified().to_string(),
+            "13"
+        );
+        assert_eq!(parsed("\\sum_{i=3}^{2} i").simplified().to_string(), "0");
+        assert_eq!(
+            parsed("\\sum_{i=1}^{100} i").simplified().to_string(),
+            "5050"
+        );
+        assert_eq!(
+            parsed("\\sum_{i=1}^{9999} 1").simplified().to_string(),
+            "9999"
+        );
+        assert_eq!(
+            parsed("\\sum_{i=1}^{3} i x").simplified().to_string(),
+            "(6 * x)"
+        );
+        assert_eq!(
+            parsed("\\sum_{i=1}^{n} i").to_string(),
+            "\\sum_{i=1}^{n}{i}"
+        );
+
+        // Inner summations over the same index keep their own variable
+        assert_eq!(
+            parsed("\\sum_{i=1}^{2} \\sum_{i=1}^{i} i")
+                .simplified()
+                .to_string(),
+            "4"
+        );
+
+        let mut bindings = Bindings::new();
+        bindings.insert(
+            "n".to_string(),
+            Box::new(RationalValue::from(num::BigInt::from(100))),
+        );
+        let value = parsed("\\sum_{k=1}^{n} k").evaluate(&bindings);
+        assert_eq!(FloatValue::approximate(value.as_ref()), Some(5050.0));
+    }
+}
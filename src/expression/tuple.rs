@@ -0,0 +1,132 @@
+use std::ops::Range;
+
+use crate::{
+    format::FormatOptions,
+    value::{IntervalUnionValue, RationalValue, UndefinedValue, Value, VectorValue},
+};
+
+use super::{Bindings, Expression};
+
+/// An ordered list of expressions, like the point `(1, 2, 3)` or the arguments of a call
+#[derive(Debug, Clone)]
+pub struct TupleExpression {
+    elements: Vec<Box<dyn Expression>>,
+    span: Option<Range<usize>>,
+}
+
+impl TupleExpression {
+    pub fn new(elements: Vec<Box<dyn Expression>>) -> Self {
+        Self {
+            elements,
+            span: None,
+        }
+    }
+
+    pub fn get_elements(&self) -> &[Box<dyn Expression>] {
+        &self.elements
+    }
+
+    /// Take the elements out of this tuple
+    pub fn into_elements(self) -> Vec<Box<dyn Expression>> {
+        self.elements
+    }
+
+    /// Simplify each element, keeping them in order
+    pub(super) fn simplified_elements(&self) -> Self {
+        Self::new(
+            self.elements
+                .iter()
+                .map(|element| element.simplified())
+                .collect(),
+        )
+    }
+}
+
+impl Expression for TupleExpression {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", name = "simplify", skip_all, fields(node = "tuple"))
+    )]
+    fn simplified(&self) -> Box<dyn Expression> {
+        Box::new(self.simplified_elements())
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        // Only tuples of exact rational elements have a value
+        let components = self
+            .elements
+            .iter()
+            .map(|element| {
+                element
+                    .evaluate(bindings)
+                    .downcast_ref::<RationalValue>()
+                    .cloned()
+            })
+            .collect::<Option<_>>();
+        match components {
+            Some(components) => Box::new(VectorValue::new(components)),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn domain(&self, variable: &str) -> Option<IntervalUnionValue> {
+        let mut domain = IntervalUnionValue::all();
+        for element in &self.elements {
+            domain = domain.intersection(&element.domain(variable)?);
+        }
+        Some(domain)
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        self.elements
+            .iter()
+            .map(|element| element.as_ref())
+            .collect()
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        self.elements.iter_mut().collect()
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        let elements = self
+            .elements
+            .iter()
+            .map(|element| element.format(options))
+            .collect::<Vec<_>>();
+        format!(
+            "({})",
+            elements.join(&format!("{} ", options.get_locale().argument_separator()))
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{parse, ParserOptions};
+
+    use super::TupleExpression;
+
+    #[test]
+    fn tuples() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap();
+        let simplified = parsed("(1 + 1, x, \\frac{6}{4})").simplified();
+        let tuple = simplified.downcast_ref::<TupleExpression>().unwrap();
+        assert_eq!(tuple.get_elements().len(), 3);
+        assert_eq!(simplified.to_string(), "(2, x, 3/2)");
+        assert_eq!(parsed("(a, b, c)").get_span(), Some(0..9));
+
+        // Two elements in brackets are still an interval
+        assert_eq!(parsed("(0, 1)").to_string(), "(0, 1)");
+        assert!(parsed("(0, 1)").downcast_ref::<TupleExpression>().is_none());
+    }
+}
@@ -1,16 +1,22 @@
-use crate::value::Value;
+use std::ops::Range;
 
-use super::Expression;
+use crate::{
+    format::FormatOptions,
+    value::{IntervalUnionValue, UndefinedValue, Value},
+};
+
+use super::{Bindings, Expression};
 
 /// An expression that contains a value
 #[derive(Debug, Clone)]
 pub struct ValueExpression {
     value: Box<dyn Value>,
+    span: Option<Range<usize>>,
 }
 
 impl ValueExpression {
     pub fn new(value: Box<dyn Value>) -> Self {
-        Self { value }
+        Self { value, span: None }
     }
 
     pub fn get_value(&self) -> &dyn Value {
@@ -24,8 +30,38 @@ impl Expression for ValueExpression {
         Box::new(ValueExpression::new(self.value.clone()))
     }
 
+    /// Evaluate this expression
+    fn evaluate(&self, _bindings: &Bindings) -> Box<dyn Value> {
+        self.value.clone()
+    }
+
+    /// Get the domain of this expression
+    fn domain(&self, _variable: &str) -> Option<IntervalUnionValue> {
+        if self.value.is::<UndefinedValue>() {
+            Some(IntervalUnionValue::empty())
+        } else {
+            Some(IntervalUnionValue::all())
+        }
+    }
+
     /// Get a string representation of this expression
-    fn to_string(&self) -> String {
-        self.value.to_string()
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        Vec::new()
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        Vec::new()
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        self.value.format(options)
     }
 }
@@ -0,0 +1,203 @@
+use std::ops::Range;
+
+use crate::{
+    format::FormatOptions,
+    value::{IntervalUnionValue, UndefinedValue, Value},
+};
+
+use super::{Bindings, Expression};
+
+/// Unicode Greek letters, and the LaTeX commands they're written with
+const GREEK_LETTERS: [(char, &str); 41] = [
+    ('α', "alpha"),
+    ('β', "beta"),
+    ('γ', "gamma"),
+    ('δ', "delta"),
+    ('ε', "epsilon"),
+    ('ϵ', "epsilon"),
+    ('ζ', "zeta"),
+    ('η', "eta"),
+    ('θ', "theta"),
+    ('ϑ', "vartheta"),
+    ('ι', "iota"),
+    ('κ', "kappa"),
+    ('λ', "lambda"),
+    ('μ', "mu"),
+    ('ν', "nu"),
+    ('ξ', "xi"),
+    ('ο', "omicron"),
+    ('π', "pi"),
+    ('ϖ', "varpi"),
+    ('ρ', "rho"),
+    ('ϱ', "varrho"),
+    ('ς', "varsigma"),
+    ('σ', "sigma"),
+    ('τ', "tau"),
+    ('υ', "upsilon"),
+    ('φ', "phi"),
+    ('ϕ', "phi"),
+    ('χ', "chi"),
+    ('ψ', "psi"),
+    ('ω', "omega"),
+    ('Γ', "Gamma"),
+    ('Δ', "Delta"),
+    ('Θ', "Theta"),
+    ('Λ', "Lambda"),
+    ('Ξ', "Xi"),
+    ('Π', "Pi"),
+    ('Σ', "Sigma"),
+    ('Υ', "Upsilon"),
+    ('Φ', "Phi"),
+    ('Ψ', "Psi"),
+    ('Ω', "Omega"),
+];
+
+/// An expression that refers to a variable
+#[derive(Debug, Clone)]
+pub struct VariableExpression {
+    name: String,
+    span: Option<Range<usize>>,
+}
+
+impl VariableExpression {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            span: None,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    /// Get the canonical spelling of a variable name, so `x_{1}` and `x_1` name the same variable,
+    /// and so do `π` and `\pi`
+    pub fn canonical_name(name: &str) -> String {
+        let spelled;
+        let name = match GREEK_LETTERS
+            .iter()
+            .find(|(letter, _)| name.starts_with(*letter))
+        {
+            Some((letter, command)) => {
+                spelled = format!("\\{}{}", command, &name[letter.len_utf8()..]);
+                spelled.as_str()
+            }
+            None => name,
+        };
+        let subscript = name
+            .split_once("_{")
+            .and_then(|(base, subscript)| Some((base, subscript.strip_suffix('}')?)))
+            .filter(|(_, subscript)| subscript.chars().count() == 1);
+        match subscript {
+            Some((base, subscript)) => format!("{}_{}", base, subscript),
+            None => name.to_string(),
+        }
+    }
+}
+
+impl Expression for VariableExpression {
+    fn simplified(&self) -> Box<dyn Expression> {
+        Box::new(self.clone())
+    }
+
+    fn evaluate(&self, bindings: &Bindings) -> Box<dyn Value> {
+        bindings
+            .get(&self.name)
+            .cloned()
+            .unwrap_or_else(|| Box::new(UndefinedValue::new()))
+    }
+
+    fn domain(&self, _variable: &str) -> Option<IntervalUnionValue> {
+        Some(IntervalUnionValue::all())
+    }
+
+    fn get_span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
+    fn get_children(&self) -> Vec<&dyn Expression> {
+        Vec::new()
+    }
+
+    fn get_children_mut(&mut self) -> Vec<&mut Box<dyn Expression>> {
+        Vec::new()
+    }
+
+    fn format(&self, _options: &FormatOptions) -> String {
+        self.name.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::Bindings,
+        parse::{parse, ParserOptions},
+        value::RationalValue,
+    };
+
+    #[test]
+    fn variables() {
+        let expression = parse("2x + 3 - y_{12}", &ParserOptions::default()).unwrap();
+        assert_eq!(
+            expression.simplified().to_string(),
+            "((2 * x) - y_{12} + 3)"
+        );
+
+        // Binding every variable gives a value
+        let mut bindings = Bindings::new();
+        for (name, value) in [("x", "2"), ("y_{12}", "1")] {
+            bindings.insert(
+                name.to_string(),
+                Box::new(value.parse::<RationalValue>().unwrap()),
+            );
+        }
+        assert_eq!(expression.evaluate(&bindings).to_string(), "6");
+    }
+
+    #[test]
+    fn subscripts() {
+        let options = ParserOptions::default();
+        let expression = parse("x_1 + x_{1} - x_2 + x_{max}", &options).unwrap();
+        assert_eq!(
+            expression.simplified().to_string(),
+            "((2 * x_1) - x_2 + x_{max})"
+        );
+
+        // Differently subscripted variables are bound separately
+        let mut bindings = Bindings::new();
+        for (name, value) in [("x_1", "3"), ("x_2", "1"), ("x_{max}", "10")] {
+            bindings.insert(
+                name.to_string(),
+                Box::new(value.parse::<RationalValue>().unwrap()),
+            );
+        }
+        assert_eq!(expression.evaluate(&bindings).to_string(), "15");
+        assert_eq!(
+            parse("\\sum_{k_{0}=1}^{3} k_0", &options)
+                .unwrap()
+                .simplified()
+                .to_string(),
+            "6"
+        );
+    }
+
+    #[test]
+    fn greek_letters() {
+        let options = ParserOptions::default();
+        let expression = parse("2\\theta + \\alpha_{1} \\Omega", &options).unwrap();
+        let written = expression.to_string();
+        assert_eq!(written, "((2 * \\theta) + (\\alpha_1 * \\Omega))");
+        assert_eq!(parse(&written, &options).unwrap().to_string(), written);
+
+        // Greek letters naming constants are still constants
+        assert_eq!(
+            parse("\\pi + \\pi_0", &options).unwrap().to_string(),
+            "(\\pi + \\pi_0)"
+        );
+    }
+}
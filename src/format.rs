@@ -0,0 +1,202 @@
+use crate::locale::{DecimalSeparator, Locale};
+
+/// How rational values are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RationalStyle {
+    /// Write rational values as fractions, like `5/4`
+    Fraction,
+    /// Write rational values as decimals, like `1.25`
+    Decimal,
+}
+
+/// How fractions are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FractionStyle {
+    /// Write fractions inline, like `5/4`
+    Inline,
+    /// Write fractions with LaTeX, like `\frac{5}{4}`
+    Frac,
+}
+
+/// Options controlling how values and expressions are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatOptions {
+    locale: Locale,
+    grouping: bool,
+    max_digits: Option<usize>,
+    rational_style: RationalStyle,
+    fraction_style: FractionStyle,
+    decimal_places: usize,
+}
+
+impl FormatOptions {
+    /// Set the locale whose separators numbers are written with
+    pub fn with_locale(self, locale: Locale) -> Self {
+        Self { locale, ..self }
+    }
+
+    /// Set whether the digits of numbers are separated into groups of three
+    pub fn with_grouping(self, grouping: bool) -> Self {
+        Self { grouping, ..self }
+    }
+
+    /// Set the most digits written before a number switches to scientific notation
+    pub fn with_max_digits(self, max_digits: Option<usize>) -> Self {
+        Self { max_digits, ..self }
+    }
+
+    /// Set how rational values are written
+    pub fn with_rational_style(self, rational_style: RationalStyle) -> Self {
+        Self {
+            rational_style,
+            ..self
+        }
+    }
+
+    /// Set how fractions are written
+    pub fn with_fraction_style(self, fraction_style: FractionStyle) -> Self {
+        Self {
+            fraction_style,
+            ..self
+        }
+    }
+
+    /// Set the number of decimal places non-terminating decimals are cut off after
+    pub fn with_decimal_places(self, decimal_places: usize) -> Self {
+        Self {
+            decimal_places,
+            ..self
+        }
+    }
+
+    /// Get the locale whose separators numbers are written with
+    pub fn get_locale(&self) -> &Locale {
+        &self.locale
+    }
+
+    /// Check if the digits of numbers are separated into groups of three
+    pub fn is_grouping(&self) -> bool {
+        self.grouping
+    }
+
+    /// Get the most digits written before a number switches to scientific notation
+    pub fn get_max_digits(&self) -> Option<usize> {
+        self.max_digits
+    }
+
+    /// Get how rational values are written
+    pub fn get_rational_style(&self) -> RationalStyle {
+        self.rational_style
+    }
+
+    /// Get how fractions are written
+    pub fn get_fraction_style(&self) -> FractionStyle {
+        self.fraction_style
+    }
+
+    /// Get the number of decimal places non-terminating decimals are cut off after
+    pub fn get_decimal_places(&self) -> usize {
+        self.decimal_places
+    }
+
+    /// Write a fraction of an already written numerator and denominator
+    pub fn fraction(&self, numerator: &str, denominator: &str) -> String {
+        match self.fraction_style {
+            FractionStyle::Inline => format!("{}/{}", numerator, denominator),
+            FractionStyle::Frac => format!("\\frac{{{}}}{{{}}}", numerator, denominator),
+        }
+    }
+
+    /// Write a decimal number from its sign and the digits before and after the point
+    pub fn decimal(&self, negative: bool, integer: &str, fraction: &str) -> String {
+        let sign = if negative { "-" } else { "" };
+        let point = match self.locale.get_decimal_separator() {
+            DecimalSeparator::Point => '.',
+            DecimalSeparator::Comma => ',',
+        };
+
+        // Switch to scientific notation when the integer part has too many digits
+        if let Some(max_digits) = self.max_digits.filter(|&max| integer.len() > max.max(1)) {
+            let digits = format!("{}{}", integer, fraction);
+            let significant = digits[..max_digits.max(1)].trim_end_matches('0');
+            let mantissa = match significant.split_at(1) {
+                (first, "") => first.to_string(),
+                (first, rest) => format!("{}{}{}", first, point, rest),
+            };
+            return format!("{}{} \\times 10^{{{}}}", sign, mantissa, integer.len() - 1);
+        }
+
+        // Separate groups of three digits in the integer part
+        let mut written = sign.to_string();
+        let separator = self
+            .locale
+            .get_group_separator()
+            .filter(|_| self.grouping && integer.len() > 3);
+        for (position, digit) in integer.chars().enumerate() {
+            if position > 0 && (integer.len() - position).is_multiple_of(3) {
+                if let Some(separator) = separator {
+                    written.push(separator);
+                }
+            }
+            written.push(digit);
+        }
+        if !fraction.is_empty() {
+            written.push(point);
+            written.push_str(fraction);
+        }
+        written
+    }
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            locale: Locale::default(),
+            grouping: false,
+            max_digits: None,
+            rational_style: RationalStyle::Fraction,
+            fraction_style: FractionStyle::Inline,
+            decimal_places: 10,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        locale::Locale,
+        parse::{parse, ParserOptions},
+    };
+
+    use super::{FormatOptions, FractionStyle, RationalStyle};
+
+    #[test]
+    fn options() {
+        let expression = parse("x / 3 + 12345678 / 8", &ParserOptions::default()).unwrap();
+        let options = FormatOptions::default()
+            .with_fraction_style(FractionStyle::Frac)
+            .with_grouping(true);
+        assert_eq!(
+            expression.format(&options),
+            "(\\frac{x}{3} + \\frac{12,345,678}{8})"
+        );
+        assert_eq!(
+            expression.simplified().format(&options),
+            "(\\frac{x}{3} + \\frac{6,172,839}{4})"
+        );
+
+        let options = options
+            .with_rational_style(RationalStyle::Decimal)
+            .with_locale(Locale::european());
+        assert_eq!(
+            expression.simplified().format(&options),
+            "(\\frac{x}{3} + 1.543.209,75)"
+        );
+        assert_eq!(
+            expression
+                .simplified()
+                .format(&options.with_max_digits(Some(4))),
+            "(\\frac{x}{3} + 1,543 \\times 10^{6})"
+        );
+    }
+}
@@ -1,3 +1,16 @@
+pub mod cached_parse;
+pub mod diagnostic;
 pub mod expression;
+pub mod format;
+pub mod locale;
+mod mathml;
+pub mod numeric;
+pub mod operator;
 pub mod parse;
+mod plain;
+mod rpn;
+pub mod solve;
+mod trace;
+mod translation;
+pub mod units;
 pub mod value;
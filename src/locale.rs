@@ -0,0 +1,203 @@
+use std::borrow::Cow;
+
+/// The character separating the integer and fractional parts of a number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecimalSeparator {
+    Point,
+    Comma,
+}
+
+/// Conventions for writing numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locale {
+    decimal_separator: DecimalSeparator,
+    group_separator: Option<char>,
+}
+
+impl Locale {
+    /// Construct a new locale
+    pub fn new(decimal_separator: DecimalSeparator, group_separator: Option<char>) -> Self {
+        Self {
+            decimal_separator,
+            group_separator,
+        }
+    }
+
+    /// The locale writing numbers like `1,234.5`
+    pub fn english() -> Self {
+        Self::new(DecimalSeparator::Point, Some(','))
+    }
+
+    /// The locale writing numbers like `1.234,5`, used in much of Europe
+    pub fn european() -> Self {
+        Self::new(DecimalSeparator::Comma, Some('.'))
+    }
+
+    /// Get the decimal separator of this locale
+    pub fn get_decimal_separator(&self) -> DecimalSeparator {
+        self.decimal_separator
+    }
+
+    /// Get the separator between groups of three digits of this locale, if it has one
+    pub fn get_group_separator(&self) -> Option<char> {
+        self.group_separator
+    }
+
+    /// Get the character separating a list of arguments, which can't clash with the decimal separator
+    pub fn argument_separator(&self) -> char {
+        match self.decimal_separator {
+            DecimalSeparator::Point => ',',
+            DecimalSeparator::Comma => ';',
+        }
+    }
+
+    /// Rewrite input written in this locale to use a decimal point and comma separated arguments
+    ///
+    /// Every character is replaced by one of the same length, so byte positions are kept. Semicolons
+    /// outside of brackets still separate statements, and `{,}` between digits becomes `{.}`. A
+    /// group separator before a group of three digits becomes `'`, which is read as a digit group.
+    pub fn normalize<'a>(&self, input: &'a str) -> Cow<'a, str> {
+        if self.decimal_separator == DecimalSeparator::Point {
+            return Cow::Borrowed(input);
+        }
+        let mut bytes = input.as_bytes().to_vec();
+        let mut depth = 0_usize;
+        for index in 0..bytes.len() {
+            match bytes[index] {
+                b'(' | b'[' | b'{' => depth += 1,
+                b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+                // A comma between digits is a decimal separator, even when braced like `{,}`
+                b',' if index > 0
+                    && bytes[index - 1].is_ascii_digit()
+                    && bytes.get(index + 1).is_some_and(u8::is_ascii_digit) =>
+                {
+                    bytes[index] = b'.'
+                }
+                b',' if index > 1
+                    && bytes[index - 2].is_ascii_digit()
+                    && bytes[index - 1] == b'{'
+                    && bytes.get(index + 1) == Some(&b'}')
+                    && bytes.get(index + 2).is_some_and(u8::is_ascii_digit) =>
+                {
+                    bytes[index] = b'.'
+                }
+                b';' if depth > 0 => bytes[index] = b',',
+                byte if byte.is_ascii()
+                    && self.group_separator == Some(char::from(byte))
+                    && index > 0
+                    && bytes[index - 1].is_ascii_digit()
+                    && (1..=3).all(|offset| {
+                        bytes.get(index + offset).is_some_and(u8::is_ascii_digit)
+                    })
+                    && !bytes.get(index + 4).is_some_and(u8::is_ascii_digit) =>
+                {
+                    bytes[index] = b'\''
+                }
+                _ => {}
+            }
+        }
+        Cow::Owned(
+            String::from_utf8(bytes)
+                .expect("Unexpected error: replacing ASCII bytes produced invalid UTF-8!"),
+        )
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        format::{FormatOptions, RationalStyle},
+        parse::{parse, ParserOptions},
+    };
+
+    use super::Locale;
+
+    #[test]
+    fn decimal_comma() {
+        let options = ParserOptions::default().with_locale(Locale::european());
+        let expression = parse("3,14 + \\operatorname{B}(1; 2)", &options).unwrap();
+        assert_eq!(expression.simplified().to_string(), "91/25");
+        let program = parse("x = 0,5; \\operatorname{B}(x; 2)", &options).unwrap();
+        assert_eq!(program.to_string(), "x = 1/2; \\operatorname{B}(x, 2)");
+        let options = FormatOptions::default()
+            .with_locale(Locale::european())
+            .with_rational_style(RationalStyle::Decimal)
+            .with_grouping(true);
+        assert_eq!(
+            parse(
+                "-12345.678 + \\operatorname{B}(1, 2)",
+                &ParserOptions::default()
+            )
+            .unwrap()
+            .format(&options),
+            "(-12.345,678 + \\operatorname{B}(1; 2))"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        // Numbers written with digit groups parse back to the same value
+        let locale = Locale::european();
+        let format = FormatOptions::default()
+            .with_locale(locale)
+            .with_rational_style(RationalStyle::Decimal)
+            .with_grouping(true);
+        let options = ParserOptions::default().with_locale(locale);
+        for input in ["1234.5", "-1234567.25", "1000", "12.5"] {
+            let expected = parse(input, &ParserOptions::default())
+                .unwrap()
+                .simplified();
+            let formatted = expected.format(&format);
+            assert_eq!(
+                parse(&formatted, &options)
+                    .unwrap()
+                    .simplified()
+                    .to_string(),
+                expected.to_string()
+            );
+        }
+        assert_eq!(
+            parse("1.234,5", &options).unwrap().simplified().to_string(),
+            "2469/2"
+        );
+
+        // Points that aren't before a group of three digits are still decimal points
+        assert_eq!(
+            parse("1.5 + 1.2345", &options)
+                .unwrap()
+                .simplified()
+                .to_string(),
+            "5469/2000"
+        );
+    }
+
+    #[test]
+    fn digit_groups() {
+        let simplified = |input: &str, locale: Locale| {
+            let options = ParserOptions::default().with_locale(locale);
+            parse(input, &options).unwrap().simplified().to_string()
+        };
+        assert_eq!(simplified("1{,}000.5", Locale::english()), "2001/2");
+        assert_eq!(
+            simplified("1\\,000\\,000 + 2", Locale::english()),
+            "1000002"
+        );
+        assert_eq!(simplified("1\\,000{,}5", Locale::european()), "2001/2");
+        assert_eq!(
+            simplified("2\\,x + 1{,}25", Locale::european()),
+            "((2 * x) + 5/4)"
+        );
+        assert_eq!(
+            parse("12{,}345", &ParserOptions::default())
+                .unwrap()
+                .get_span(),
+            Some(0..8)
+        );
+    }
+}
@@ -1,6 +1,13 @@
-use std::io;
+use std::io::{self, IsTerminal};
 
-use calconsteroids::parse::{parse_latex, parse_pairs};
+use calconsteroids::{
+    diagnostic::Diagnostic,
+    numeric::approximate,
+    parse::{parse, ParserOptions},
+};
+
+/// The number of digits printed by the approximate command
+const APPROXIMATE_DIGITS: usize = 20;
 
 /// The entrypoint to this program
 fn main() {
@@ -12,10 +19,29 @@ fn main() {
         .expect("Failed to read line");
     let input = input.trim();
 
+    // Check for the approximate command
+    let (approximating, input) = match input.strip_prefix("approximate ") {
+        Some(input) => (true, input.trim()),
+        None => (false, input),
+    };
+
     // Parse the expression
-    let pairs = parse_latex(input).expect("Bad expression");
-    let expression = parse_pairs(pairs);
+    let expression = match parse(input, &ParserOptions::default()) {
+        Ok(expression) => expression,
+        Err(error) => {
+            let diagnostic = Diagnostic::from_parse_error(input, &error);
+            eprint!("{}", diagnostic.render(input, io::stderr().is_terminal()));
+            return;
+        }
+    };
 
-    // Print the simplified expression
-    println!("{}", expression.simplified().to_string());
+    // Print the approximated or simplified expression
+    if approximating {
+        match approximate(expression.as_ref(), APPROXIMATE_DIGITS) {
+            Some(approximation) => println!("{}", approximation),
+            None => println!("undefined"),
+        }
+    } else {
+        println!("{}", expression.simplified().to_string());
+    }
 }
@@ -0,0 +1,448 @@
+use pest::{error::InputLocation, iterators::Pair, Parser as _};
+use pest_derive::Parser;
+
+use crate::{
+    expression::Expression,
+    parse::{ParseError, ParserOptions},
+    translation::Translation,
+};
+
+/// A parser for the XML MathML is written in
+#[derive(Parser)]
+#[grammar = "mathml.pest"]
+struct MathmlParser;
+
+/// Elements whose children are written one after another
+const ROWS: [&str; 6] = ["math", "mrow", "mstyle", "mpadded", "mphantom", "merror"];
+
+/// Functions written as LaTeX commands of the same name
+const FUNCTIONS: [&str; 14] = [
+    "sin", "cos", "tan", "sinh", "cosh", "tanh", "arcsin", "arccos", "arctan", "ln", "log", "exp",
+    "min", "max",
+];
+
+/// Greek letters and named constants, as their character and LaTeX command
+const NAMES: [(char, &str); 21] = [
+    ('α', "\\alpha"),
+    ('β', "\\beta"),
+    ('γ', "\\gamma"),
+    ('δ', "\\delta"),
+    ('ε', "\\epsilon"),
+    ('ζ', "\\zeta"),
+    ('η', "\\eta"),
+    ('θ', "\\theta"),
+    ('ι', "\\iota"),
+    ('κ', "\\kappa"),
+    ('λ', "\\lambda"),
+    ('μ', "\\mu"),
+    ('ν', "\\nu"),
+    ('ξ', "\\xi"),
+    ('π', "\\pi"),
+    ('ρ', "\\rho"),
+    ('σ', "\\sigma"),
+    ('τ', "\\tau"),
+    ('φ', "\\phi"),
+    ('ω', "\\omega"),
+    ('∞', "\\infty"),
+];
+
+/// Operators written differently in LaTeX
+const OPERATORS: [(&str, &str); 16] = [
+    ("−", "-"),
+    ("×", "\\times"),
+    ("·", "\\cdot"),
+    ("⋅", "\\cdot"),
+    ("∗", "*"),
+    ("÷", "\\div"),
+    ("≤", "\\le"),
+    ("≥", "\\ge"),
+    ("≠", "\\ne"),
+    ("±", "\\pm"),
+    ("∓", "\\mp"),
+    ("{", "\\{"),
+    ("}", "\\}"),
+    ("mod", "\\bmod"),
+    // Invisible times and function application
+    ("\u{2062}", ""),
+    ("\u{2061}", ""),
+];
+
+/// Named character references, as their name and character
+const ENTITIES: [(&str, &str); 26] = [
+    ("lt", "<"),
+    ("gt", ">"),
+    ("amp", "&"),
+    ("quot", "\""),
+    ("apos", "'"),
+    ("nbsp", " "),
+    ("minus", "−"),
+    ("times", "×"),
+    ("divide", "÷"),
+    ("sdot", "⋅"),
+    ("middot", "·"),
+    ("InvisibleTimes", "\u{2062}"),
+    ("it", "\u{2062}"),
+    ("ApplyFunction", "\u{2061}"),
+    ("af", "\u{2061}"),
+    ("pi", "π"),
+    ("infin", "∞"),
+    ("le", "≤"),
+    ("leq", "≤"),
+    ("ge", "≥"),
+    ("geq", "≥"),
+    ("ne", "≠"),
+    ("pm", "±"),
+    ("PlusMinus", "±"),
+    ("mp", "∓"),
+    ("MinusPlus", "∓"),
+];
+
+/// Replace the character references in some text with the characters they refer to
+fn decode(text: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let reference = &rest[1..end];
+        let character = if let Some(code) = reference.strip_prefix("#x") {
+            u32::from_str_radix(code, 16).ok().and_then(char::from_u32)
+        } else if let Some(code) = reference.strip_prefix('#') {
+            code.parse().ok().and_then(char::from_u32)
+        } else {
+            None
+        };
+        let entity = ENTITIES.iter().find(|(name, _)| *name == reference);
+        match (character, entity) {
+            (Some(character), _) => decoded.push(character),
+            (None, Some((_, entity))) => decoded.push_str(entity),
+            (None, None) => decoded.push_str(&rest[..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Get the name of an element without its namespace prefix
+fn element_name<'i>(pair: &Pair<'i, Rule>) -> &'i str {
+    let name = pair.clone().into_inner().next().unwrap().as_str();
+    name.rsplit(':').next().unwrap()
+}
+
+/// Get the value of an attribute of an element
+fn attribute(pair: &Pair<Rule>, name: &str) -> Option<String> {
+    pair.clone()
+        .into_inner()
+        .filter(|pair| pair.as_rule() == Rule::attribute)
+        .find_map(|attribute| {
+            let mut inner = attribute.into_inner();
+            (inner.next().unwrap().as_str() == name).then(|| decode(inner.next().unwrap().as_str()))
+        })
+}
+
+/// Get the child elements of an element
+fn children<'i>(pair: &Pair<'i, Rule>) -> Vec<Pair<'i, Rule>> {
+    pair.clone()
+        .into_inner()
+        .filter(|pair| pair.as_rule() == Rule::element)
+        .collect()
+}
+
+/// Get the text inside an element, with character references replaced
+fn text(pair: &Pair<Rule>) -> String {
+    let text: String = pair
+        .clone()
+        .into_inner()
+        .filter(|pair| pair.as_rule() == Rule::text)
+        .map(|pair| pair.as_str())
+        .collect();
+    decode(text.trim())
+}
+
+/// Get the child elements of an element which must have a number of them
+fn arguments<'i>(
+    pair: &Pair<'i, Rule>,
+    count: usize,
+    input: &str,
+) -> Result<Vec<Pair<'i, Rule>>, ParseError> {
+    let children = children(pair);
+    if children.len() != count {
+        return Err(ParseError::custom(
+            input,
+            pair.as_span().start(),
+            format!(
+                "MathML element `{}` must have {} children",
+                element_name(pair),
+                count
+            ),
+        ));
+    }
+    Ok(children)
+}
+
+/// Translate elements one after another into LaTeX
+fn translate_row(
+    children: &[Pair<Rule>],
+    translation: &mut Translation,
+    input: &str,
+) -> Result<(), ParseError> {
+    let mut applied = false;
+    for child in children {
+        // Bracket the argument of a function application, since LaTeX functions need brackets
+        if applied {
+            translation.push("\\left(", child.as_span().start());
+            translate(child, translation, input)?;
+            translation.push("\\right)", child.as_span().end());
+        } else {
+            translate(child, translation, input)?;
+        }
+        translation.push(" ", child.as_span().end());
+        applied = element_name(child) == "mo" && text(child) == "\u{2061}";
+    }
+    Ok(())
+}
+
+/// Translate the base of a script, bracketing it unless it's a single token
+fn translate_base(
+    base: &Pair<Rule>,
+    translation: &mut Translation,
+    input: &str,
+) -> Result<(), ParseError> {
+    if matches!(element_name(base), "mi" | "mn") {
+        return translate(base, translation, input);
+    }
+    let position = base.as_span().start();
+    translation.push("\\left(", position);
+    translate(base, translation, input)?;
+    translation.push("\\right)", base.as_span().end());
+    Ok(())
+}
+
+/// Translate a MathML element into LaTeX
+fn translate(
+    pair: &Pair<Rule>,
+    translation: &mut Translation,
+    input: &str,
+) -> Result<(), ParseError> {
+    let position = pair.as_span().start();
+    let name = element_name(pair);
+    match name {
+        _ if ROWS.contains(&name) => translate_row(&children(pair), translation, input)?,
+        "semantics" => match children(pair).first() {
+            Some(child) => translate(child, translation, input)?,
+            None => translation.push(" ", position),
+        },
+        "mn" => translation.push(&text(pair), position),
+        "mi" => {
+            let text = text(pair);
+            let mut characters = text.chars();
+            let latex = match (characters.next(), characters.next()) {
+                (Some(character), None) => NAMES
+                    .iter()
+                    .find(|(name, _)| *name == character)
+                    .map_or(text.clone(), |(_, latex)| latex.to_string()),
+                _ if FUNCTIONS.contains(&text.as_str()) => format!("\\{}", text),
+                _ if NAMES.iter().any(|(_, latex)| latex[1..] == text) => format!("\\{}", text),
+                _ => format!("\\operatorname{{{}}}", text),
+            };
+            translation.push(&latex, position);
+        }
+        "mo" => {
+            let text = text(pair);
+            let latex = OPERATORS
+                .iter()
+                .find(|(operator, _)| *operator == text)
+                .map_or(text.as_str(), |(_, latex)| latex);
+            translation.push(latex, position);
+        }
+        "mtext" | "mspace" => translation.push(" ", position),
+        "mfrac" => {
+            let [numerator, denominator] = &arguments(pair, 2, input)?[..] else {
+                unreachable!()
+            };
+            translation.push("\\frac{", position);
+            translate(numerator, translation, input)?;
+            translation.push("}{", denominator.as_span().start());
+            translate(denominator, translation, input)?;
+            translation.push("}", denominator.as_span().end());
+        }
+        "msqrt" => {
+            translation.push("\\sqrt{", position);
+            translate_row(&children(pair), translation, input)?;
+            translation.push("}", pair.as_span().end());
+        }
+        "mroot" => {
+            let [radicand, index] = &arguments(pair, 2, input)?[..] else {
+                unreachable!()
+            };
+            translation.push("\\sqrt[", position);
+            translate(index, translation, input)?;
+            translation.push("]{", radicand.as_span().start());
+            translate(radicand, translation, input)?;
+            translation.push("}", radicand.as_span().end());
+        }
+        "msup" => {
+            let [base, exponent] = &arguments(pair, 2, input)?[..] else {
+                unreachable!()
+            };
+            translate_base(base, translation, input)?;
+            translation.push("^{", exponent.as_span().start());
+            translate(exponent, translation, input)?;
+            translation.push("}", exponent.as_span().end());
+        }
+        "msub" | "msubsup" => {
+            let count = if name == "msub" { 2 } else { 3 };
+            let arguments = arguments(pair, count, input)?;
+            let (base, subscript) = (&arguments[0], &arguments[1]);
+            translate(base, translation, input)?;
+            translation.push("_{", subscript.as_span().start());
+            translate(subscript, translation, input)?;
+            translation.push("}", subscript.as_span().end());
+            if let Some(exponent) = arguments.get(2) {
+                translation.push("^{", exponent.as_span().start());
+                translate(exponent, translation, input)?;
+                translation.push("}", exponent.as_span().end());
+            }
+        }
+        "mfenced" => {
+            let open = attribute(pair, "open").unwrap_or("(".to_string());
+            let close = attribute(pair, "close").unwrap_or(")".to_string());
+            let separators = attribute(pair, "separators").unwrap_or(",".to_string());
+            let separators: Vec<char> = separators.chars().filter(|c| !c.is_whitespace()).collect();
+            translation.push(&format!("\\left{} ", delimiter(&open)), position);
+            for (index, child) in children(pair).iter().enumerate() {
+                if index > 0 {
+                    let separator = separators
+                        .get(index - 1)
+                        .or(separators.last())
+                        .copied()
+                        .unwrap_or(',');
+                    translation.push(&separator.to_string(), child.as_span().start());
+                }
+                translate(child, translation, input)?;
+            }
+            translation.push(
+                &format!(" \\right{}", delimiter(&close)),
+                pair.as_span().end(),
+            );
+        }
+        _ => {
+            return Err(ParseError::custom(
+                input,
+                position,
+                format!("unsupported MathML element `{}`", name),
+            ))
+        }
+    }
+    Ok(())
+}
+
+/// Get the LaTeX for a fence delimiter
+fn delimiter(fence: &str) -> &str {
+    match fence {
+        "{" => "\\{",
+        "}" => "\\}",
+        fence => fence,
+    }
+}
+
+/// Parse an expression written in Presentation MathML, like
+/// `<math><mfrac><mn>1</mn><mi>x</mi></mfrac></math>`
+///
+/// This gives the same expressions as parsing LaTeX, with spans into the MathML
+pub fn parse_mathml(
+    input: &str,
+    options: &ParserOptions,
+) -> Result<Box<dyn Expression>, ParseError> {
+    if input.len() > options.get_max_length() {
+        return Err(ParseError::TooLong {
+            length: input.len(),
+            max_length: options.get_max_length(),
+        });
+    }
+    let document = MathmlParser::parse(Rule::document, input)
+        .map_err(|xml_error| {
+            let position = match xml_error.location {
+                InputLocation::Pos(position) => position,
+                InputLocation::Span((start, _)) => start,
+            };
+            ParseError::custom(
+                input,
+                position,
+                format!("invalid MathML: {}", xml_error.variant.message()),
+            )
+        })?
+        .next()
+        .unwrap();
+    let root = children(&document).remove(0);
+
+    let mut translation = Translation::new();
+    translate(&root, &mut translation, input)?;
+    translation.parse(input, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{parse_mathml, ParserOptions};
+
+    #[test]
+    fn mathml() {
+        let options = ParserOptions::default();
+        let simplified = |input: &str| {
+            parse_mathml(input, &options)
+                .unwrap()
+                .simplified()
+                .to_string()
+        };
+        assert_eq!(
+            simplified(
+                "<math><mfrac><mn>1</mn><mn>2</mn></mfrac><mo>+</mo>\
+                 <msup><mn>3</mn><mn>2</mn></msup></math>"
+            ),
+            "19/2"
+        );
+        assert_eq!(
+            simplified(
+                "<?xml version=\"1.0\"?>\n<m:math xmlns:m=\"http://www.w3.org/1998/Math/MathML\">\n\
+                 <m:mrow><m:mn>2</m:mn><m:mo>&InvisibleTimes;</m:mo><m:mi>&pi;</m:mi>\
+                 <m:mo>&minus;</m:mo><m:mi>π</m:mi></m:mrow></m:math>"
+            ),
+            "\\pi"
+        );
+        assert_eq!(
+            simplified(
+                "<math><msqrt><mn>16</mn></msqrt><mo>&#x00D7;</mo>\
+                 <mroot><mn>8</mn><mn>3</mn></mroot></math>"
+            ),
+            "8"
+        );
+        assert_eq!(
+            simplified(
+                "<math><msup><mfenced><mrow><mn>1</mn><mo>+</mo><mn>2</mn></mrow></mfenced>\
+                 <mn>2</mn></msup><mo>&le;</mo><msub><mi>x</mi><mn>1</mn></msub></math>"
+            ),
+            "9 \\le x_1"
+        );
+        assert_eq!(
+            simplified("<math><mi>sin</mi><mo>&ApplyFunction;</mo><mn>0</mn></math>"),
+            "0"
+        );
+
+        // Spans point into the MathML
+        let input = "<math><mn>1</mn><mo>+</mo><mi>x</mi></math>";
+        let expression = parse_mathml(input, &options).unwrap();
+        assert_eq!(expression.get_children()[1].get_span(), Some(26..36));
+
+        let input = "<math><mn>1</mn><mtable/></math>";
+        let error = parse_mathml(input, &options).unwrap_err();
+        assert_eq!(error.get_span(), 16..16);
+        assert!(error
+            .render(input)
+            .contains("unsupported MathML element `mtable`"));
+        assert!(parse_mathml("<math><mn>1</mn></mrow>", &options).is_err());
+    }
+}
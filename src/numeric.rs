@@ -0,0 +1,345 @@
+use std::f64::consts::PI;
+
+use num::{BigUint, One as _, Zero as _};
+
+use crate::{
+    expression::{Bindings, Expression, MultiplicationExpression, RootExpression, ValueExpression},
+    value::{ConstantValue, FloatValue, RationalValue, Sign, Value},
+};
+
+/// Coefficients for the Lanczos approximation with g = 7
+const LANCZOS_COEFFICIENTS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+/// Approximate the gamma function
+///
+/// Returns `None` at the poles on the non-positive integers
+pub fn gamma(x: f64) -> Option<f64> {
+    if x <= 0.0 && x.fract() == 0.0 {
+        return None;
+    }
+
+    // Use the reflection formula for the left half of the plane
+    if x < 0.5 {
+        return Some(PI / ((PI * x).sin() * gamma(1.0 - x)?));
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let sum = LANCZOS_COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(LANCZOS_COEFFICIENTS[0], |sum, (index, coefficient)| {
+            sum + coefficient / (x + index as f64 + 1.0)
+        });
+    Some((2.0 * PI).sqrt() * t.powf(x + 0.5) * (-t).exp() * sum)
+}
+
+/// Approximate the beta function
+pub fn beta(x: f64, y: f64) -> Option<f64> {
+    Some(gamma(x)? * gamma(y)? / gamma(x + y)?)
+}
+
+/// Approximate the error function
+pub fn erf(x: f64) -> f64 {
+    if x < 0.0 {
+        return -erf(-x);
+    }
+    if x < 3.0 {
+        // Maclaurin series, which converges quickly for small arguments
+        let mut term = x;
+        let mut sum = x;
+        let mut n = 0.0;
+        while term.abs() > 1e-17 * sum.abs() {
+            n += 1.0;
+            term *= -x * x / n;
+            sum += term / (2.0 * n + 1.0);
+        }
+        2.0 / PI.sqrt() * sum
+    } else {
+        // Continued fraction for the complementary error function
+        let mut fraction = 0.0;
+        for n in (1..=60).rev() {
+            fraction = (n as f64 / 2.0) / (x + fraction);
+        }
+        1.0 - (-x * x).exp() / (PI.sqrt() * (x + fraction))
+    }
+}
+
+/// Compute the largest integer whose nth power doesn't exceed a value
+///
+/// Uses Newton's method on big integers, so the result is exact for any size of input
+pub fn integer_nth_root(value: &BigUint, n: u32) -> BigUint {
+    assert!(n > 0, "Cannot take the zeroth root of a value");
+    if n == 1 || *value <= BigUint::one() {
+        return value.clone();
+    }
+
+    // Start above the root so the iteration decreases monotonically towards it
+    let n_big = BigUint::from(n);
+    let mut root = BigUint::one() << value.bits().div_ceil(u64::from(n));
+    loop {
+        let next = (&root * (&n_big - 1_u32) + value / root.pow(n - 1)) / &n_big;
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
+
+/// Split an integer into the nth power of one integer times another, pulling out every small factor
+///
+/// Returns `(outside, inside)` such that `outside^n * inside` is the value. Factors above the
+/// trial division bound are only pulled out when what remains is a perfect power as a whole.
+pub fn extract_nth_power(value: &BigUint, n: u32) -> (BigUint, BigUint) {
+    let mut outside = BigUint::one();
+    let mut inside = BigUint::one();
+    let mut rest = value.clone();
+    if rest.is_zero() {
+        return (BigUint::zero(), BigUint::one());
+    }
+    for factor in 2_u32..1000 {
+        let factor = BigUint::from(factor);
+        if &factor * &factor > rest {
+            break;
+        }
+        let mut multiplicity = 0;
+        while (&rest % &factor).is_zero() {
+            rest /= &factor;
+            multiplicity += 1;
+        }
+        outside *= factor.pow(multiplicity / n);
+        inside *= factor.pow(multiplicity % n);
+    }
+
+    // The rest has no small factors left
+    let root = integer_nth_root(&rest, n);
+    if root.pow(n) == rest {
+        outside *= root;
+    } else {
+        inside *= rest;
+    }
+    (outside, inside)
+}
+
+/// Approximate a rational power of a rational value as a decimal with the given number of digits
+///
+/// Every digit of the result is correct, as the true value is truncated rather than rounded.
+/// Returns `None` if the power isn't a real number.
+pub fn rational_power_digits(
+    base: &RationalValue,
+    exponent: &RationalValue,
+    digits: usize,
+) -> Option<String> {
+    let base = base.simplified();
+    let exponent = exponent.simplified();
+    if base.is_zero() {
+        return if exponent.is_negative() {
+            None
+        } else {
+            Some(format_digits(&BigUint::zero(), false, digits))
+        };
+    }
+
+    // Negative exponents invert the base
+    let base = if exponent.is_negative() {
+        base.get_reciprocal()
+    } else {
+        base
+    };
+    let power = u32::try_from(exponent.get_numerator()).ok()?;
+    let root = u32::try_from(exponent.get_denominator()).ok()?;
+
+    // Even roots of negative values aren't real
+    let negative = base.is_negative() && power % 2 == 1;
+    if base.is_negative() && root % 2 == 0 {
+        return None;
+    }
+
+    // Scale so that the integer part of the root holds all the requested digits
+    let scale = BigUint::from(10_u32).pow(u32::try_from(digits).ok()? * root);
+    let radicand = base.get_numerator().pow(power) * scale / base.get_denominator().pow(power);
+    Some(format_digits(
+        &integer_nth_root(&radicand, root),
+        negative,
+        digits,
+    ))
+}
+
+/// Format an integer scaled by a power of ten as a decimal
+fn format_digits(scaled: &BigUint, negative: bool, digits: usize) -> String {
+    let mut text = format!("{:0>width$}", scaled.to_string(), width = digits + 1);
+    if digits > 0 {
+        text.insert(text.len() - digits, '.');
+    }
+    if negative && !scaled.is_zero() {
+        text.insert(0, '-');
+    }
+    text
+}
+
+/// Approximate a rational value as a decimal with the given number of digits
+pub fn rational_digits(value: &RationalValue, digits: usize) -> String {
+    rational_power_digits(
+        value,
+        &RationalValue::new(Sign::Positive, 1_u32, 1_u32),
+        digits,
+    )
+    .expect("Unexpected error: a rational value has no decimal expansion!")
+}
+
+/// Get the rational value of an expression, if it is one
+fn rational_value(expression: &dyn Expression) -> Option<RationalValue> {
+    expression
+        .downcast_ref::<ValueExpression>()?
+        .get_value()
+        .downcast_ref::<RationalValue>()
+        .cloned()
+}
+
+/// Split a rational multiple of an integer root of a rational value into its coefficient, radicand and index
+fn radical(expression: &dyn Expression) -> Option<(RationalValue, RationalValue, u32)> {
+    let (coefficient, root) = match expression.downcast_ref::<MultiplicationExpression>() {
+        Some(product) => (
+            rational_value(product.get_lhs())?,
+            product.get_rhs().downcast_ref::<RootExpression>()?,
+        ),
+        None => (
+            RationalValue::one(),
+            expression.downcast_ref::<RootExpression>()?,
+        ),
+    };
+    let index = u32::try_from(rational_value(root.get_index())?.to_integer()?).ok()?;
+    Some((coefficient, rational_value(root.get_radicand())?, index))
+}
+
+/// Approximate a rational multiple of a root of a rational value as a decimal
+fn radical_digits(
+    coefficient: &RationalValue,
+    radicand: &RationalValue,
+    index: u32,
+    digits: usize,
+) -> Option<String> {
+    // Even powers lose the sign of the coefficient, so put it back afterwards
+    let negative = coefficient.is_negative() && index.is_multiple_of(2);
+    let coefficient = if negative {
+        coefficient.get_opposite()
+    } else {
+        coefficient.clone()
+    };
+    let base = coefficient.pow(i32::try_from(index).ok()?)?.mul(radicand);
+    let expansion = rational_power_digits(
+        base.downcast_ref::<RationalValue>()?,
+        &RationalValue::new(Sign::Positive, 1_u32, index),
+        digits,
+    )?;
+    Some(if negative {
+        format!("-{}", expansion)
+    } else {
+        expansion
+    })
+}
+
+/// Approximate an expression as a decimal, keeping exact results correct to the given number of digits
+///
+/// Returns `None` if the expression has no numeric value
+pub fn approximate(expression: &dyn Expression, digits: usize) -> Option<String> {
+    let simplified = expression.simplified();
+    let value = simplified
+        .downcast_ref::<ValueExpression>()
+        .map(|value| value.get_value());
+    if let Some(value) = value.and_then(|value| value.downcast_ref::<RationalValue>()) {
+        return Some(rational_digits(value, digits));
+    }
+
+    // Expand multiples of constants, limited by the accuracy of the expansion times the coefficient
+    if let Some(value) = value.and_then(|value| value.downcast_ref::<ConstantValue>()) {
+        let (expansion, accuracy) = value.get_constant().expansion();
+        let coefficient = value.get_coefficient();
+        let magnitude = (coefficient.get_numerator() / coefficient.get_denominator() + 1_u32)
+            .to_string()
+            .len();
+        let product = coefficient.mul(&expansion);
+        return product.downcast_ref::<RationalValue>().map(|product| {
+            rational_digits(product, digits.min(accuracy.saturating_sub(magnitude)))
+        });
+    }
+
+    // Expand radicals exactly, moving their coefficient under the root
+    if let Some(expansion) =
+        radical(simplified.as_ref()).and_then(|(coefficient, radicand, index)| {
+            radical_digits(&coefficient, &radicand, index, digits)
+        })
+    {
+        return Some(expansion);
+    }
+
+    // Fall back to a floating point evaluation
+    let value = simplified.evaluate(&Bindings::new());
+    FloatValue::approximate(value.as_ref()).map(|value| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+
+    use crate::value::{RationalValue, Sign};
+
+    use super::{beta, erf, extract_nth_power, gamma, integer_nth_root, rational_power_digits};
+
+    #[test]
+    fn radicals() {
+        assert_eq!(
+            integer_nth_root(&BigUint::from(1_000_000_u32), 3),
+            BigUint::from(100_u32)
+        );
+        assert_eq!(
+            integer_nth_root(&BigUint::from(999_999_u32), 3),
+            BigUint::from(99_u32)
+        );
+        assert_eq!(
+            rational_power_digits(
+                &RationalValue::new(Sign::Positive, 2_u32, 1_u32),
+                &RationalValue::new(Sign::Positive, 1_u32, 2_u32),
+                30
+            )
+            .unwrap(),
+            "1.414213562373095048801688724209"
+        );
+        assert_eq!(
+            rational_power_digits(
+                &RationalValue::new(Sign::Negative, 8_u32, 27_u32),
+                &RationalValue::new(Sign::Negative, 2_u32, 3_u32),
+                4
+            )
+            .unwrap(),
+            "2.2500"
+        );
+        assert_eq!(
+            extract_nth_power(&BigUint::from(72_u32), 2),
+            (BigUint::from(6_u32), BigUint::from(2_u32))
+        );
+        assert_eq!(
+            extract_nth_power(&BigUint::from(1_000_003_u32).pow(3), 3),
+            (BigUint::from(1_000_003_u32), BigUint::from(1_u32))
+        );
+    }
+
+    #[test]
+    fn special_functions() {
+        assert!((gamma(5.0).unwrap() - 24.0).abs() < 1e-10);
+        assert!((gamma(0.5).unwrap() - std::f64::consts::PI.sqrt()).abs() < 1e-12);
+        assert!(gamma(-2.0).is_none());
+        assert!((erf(1.0) - 0.842_700_792_949_714_9).abs() < 1e-14);
+        assert!((erf(3.5) - 0.999_999_256_901_627_7).abs() < 1e-14);
+        assert!((beta(2.0, 3.0).unwrap() - 1.0 / 12.0).abs() < 1e-12);
+    }
+}
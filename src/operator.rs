@@ -0,0 +1,263 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, RwLock},
+};
+
+use crate::expression::Expression;
+
+lazy_static::lazy_static! {
+    static ref CUSTOM_OPERATORS: RwLock<HashMap<(String, Fixity), CustomOperator>> =
+        RwLock::new(HashMap::new());
+}
+
+/// Which side an infix operator groups from when chained
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+    /// `a - b - c` is `(a - b) - c`
+    Left,
+    /// `a ^ b ^ c` is `a ^ (b ^ c)`
+    Right,
+}
+
+/// How tightly an operator binds, where higher levels bind tighter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Precedence {
+    level: u8,
+    associativity: Associativity,
+}
+
+impl Precedence {
+    /// Construct a new precedence
+    pub fn new(level: u8, associativity: Associativity) -> Self {
+        Self {
+            level,
+            associativity,
+        }
+    }
+
+    /// Construct a new left associative precedence
+    pub fn left(level: u8) -> Self {
+        Self::new(level, Associativity::Left)
+    }
+
+    /// Construct a new right associative precedence
+    pub fn right(level: u8) -> Self {
+        Self::new(level, Associativity::Right)
+    }
+
+    /// Get the level of this precedence
+    pub fn get_level(&self) -> u8 {
+        self.level
+    }
+
+    /// Get the associativity of this precedence
+    pub fn get_associativity(&self) -> Associativity {
+        self.associativity
+    }
+}
+
+/// A built-in operator, or group of operators sharing a precedence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operator {
+    /// `=`, `\ne`, `<`, `\le`, `>` and `\ge`
+    Relation,
+    /// `\mid`
+    BitOr,
+    /// `\oplus`
+    BitXor,
+    /// `\&`
+    BitAnd,
+    /// `\ll` and `\gg`
+    Shift,
+    /// `+`, `-`, `\pm` and `\mp`
+    Additive,
+    /// `\cdot`, `\times`, `*`, `/`, `\div` and `\bmod`
+    Multiplicative,
+    /// Implicit multiplication, like `2x`
+    Implicit,
+    /// Prefix operators, like `\lnot`
+    Prefix,
+    /// `^`
+    Power,
+    /// `!`, `^\circ` and superscript digits like `²`
+    Factorial,
+}
+
+impl Operator {
+    /// All the built-in operators, from the loosest binding to the tightest by default
+    pub const ALL: [Operator; 11] = [
+        Operator::Relation,
+        Operator::BitOr,
+        Operator::BitXor,
+        Operator::BitAnd,
+        Operator::Shift,
+        Operator::Additive,
+        Operator::Multiplicative,
+        Operator::Implicit,
+        Operator::Prefix,
+        Operator::Power,
+        Operator::Factorial,
+    ];
+
+    /// Get the default precedence of this operator
+    pub fn default_precedence(&self) -> Precedence {
+        let level = Self::ALL
+            .iter()
+            .position(|operator| operator == self)
+            .expect("Unexpected error: an operator is missing from the list of operators!");
+        match self {
+            Operator::Power => Precedence::right(level as u8 + 1),
+            _ => Precedence::left(level as u8 + 1),
+        }
+    }
+}
+
+/// Where an operator is written relative to its operands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Fixity {
+    /// Written before its operand, like `-x`
+    Prefix,
+    /// Written between its operands, like `a + b`
+    Infix,
+    /// Written after its operand, like `n!`
+    Postfix,
+}
+
+/// A function building the expression of an infix operator from its operands
+pub type OperatorBuilder =
+    Arc<dyn Fn(Box<dyn Expression>, Box<dyn Expression>) -> Box<dyn Expression> + Send + Sync>;
+
+/// A function building the expression of a prefix or postfix operator from its operand
+pub type UnaryOperatorBuilder =
+    Arc<dyn Fn(Box<dyn Expression>) -> Box<dyn Expression> + Send + Sync>;
+
+/// How a custom operator builds its expression
+#[derive(Clone)]
+enum Builder {
+    Unary(UnaryOperatorBuilder),
+    Binary(OperatorBuilder),
+}
+
+/// An operator registered by an embedder
+///
+/// Infix operators are written like `a \mathbin{name} b`, and operators of any fixity can be
+/// written with a symbol, like `a ⊕ b`, `%%x` or `x♯`
+#[derive(Clone)]
+pub struct CustomOperator {
+    fixity: Fixity,
+    precedence: Precedence,
+    builder: Builder,
+}
+
+impl CustomOperator {
+    /// Register an infix operator under a name, replacing any infix operator already registered
+    /// with it
+    pub fn register(
+        name: impl Into<String>,
+        precedence: Precedence,
+        builder: impl Fn(Box<dyn Expression>, Box<dyn Expression>) -> Box<dyn Expression>
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        Self::insert(
+            name.into(),
+            Self {
+                fixity: Fixity::Infix,
+                precedence,
+                builder: Builder::Binary(Arc::new(builder)),
+            },
+        );
+    }
+
+    /// Register a prefix operator under a symbol, replacing any prefix operator already registered
+    /// with it
+    pub fn register_prefix(
+        symbol: impl Into<String>,
+        precedence: Precedence,
+        builder: impl Fn(Box<dyn Expression>) -> Box<dyn Expression> + Send + Sync + 'static,
+    ) {
+        Self::insert(
+            symbol.into(),
+            Self {
+                fixity: Fixity::Prefix,
+                precedence,
+                builder: Builder::Unary(Arc::new(builder)),
+            },
+        );
+    }
+
+    /// Register a postfix operator under a symbol, replacing any postfix operator already
+    /// registered with it
+    pub fn register_postfix(
+        symbol: impl Into<String>,
+        precedence: Precedence,
+        builder: impl Fn(Box<dyn Expression>) -> Box<dyn Expression> + Send + Sync + 'static,
+    ) {
+        Self::insert(
+            symbol.into(),
+            Self {
+                fixity: Fixity::Postfix,
+                precedence,
+                builder: Builder::Unary(Arc::new(builder)),
+            },
+        );
+    }
+
+    /// Add an operator to the registry
+    fn insert(name: String, operator: Self) {
+        CUSTOM_OPERATORS
+            .write()
+            .unwrap()
+            .insert((name, operator.fixity), operator);
+    }
+
+    /// Look up a registered operator by name and fixity
+    pub fn lookup(name: &str, fixity: Fixity) -> Option<Self> {
+        CUSTOM_OPERATORS
+            .read()
+            .unwrap()
+            .get(&(name.to_string(), fixity))
+            .cloned()
+    }
+
+    /// Get the fixity of this operator
+    pub fn get_fixity(&self) -> Fixity {
+        self.fixity
+    }
+
+    /// Get the precedence of this operator
+    pub fn get_precedence(&self) -> Precedence {
+        self.precedence
+    }
+
+    /// Build the expression of this infix operator from its operands
+    pub fn apply(&self, lhs: Box<dyn Expression>, rhs: Box<dyn Expression>) -> Box<dyn Expression> {
+        match &self.builder {
+            Builder::Binary(builder) => builder(lhs, rhs),
+            Builder::Unary(_) => panic!(
+                "Unexpected error: a prefix or postfix operator was applied to two operands!"
+            ),
+        }
+    }
+
+    /// Build the expression of this prefix or postfix operator from its operand
+    pub fn apply_unary(&self, operand: Box<dyn Expression>) -> Box<dyn Expression> {
+        match &self.builder {
+            Builder::Unary(builder) => builder(operand),
+            Builder::Binary(_) => {
+                panic!("Unexpected error: an infix operator was applied to one operand!")
+            }
+        }
+    }
+}
+
+impl fmt::Debug for CustomOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomOperator")
+            .field("fixity", &self.fixity)
+            .field("precedence", &self.precedence)
+            .finish_non_exhaustive()
+    }
+}
@@ -1,80 +1,2067 @@
-use pest::{iterators::Pairs, pratt_parser::PrattParser, Parser};
+use std::{cell::RefCell, collections::HashMap, fmt, iter::Peekable, ops::Range};
+
+use pest::{
+    error::{ErrorVariant, InputLocation, LineColLocation},
+    iterators::{Pair, Pairs},
+    Parser,
+};
 use pest_derive::Parser;
 
 use crate::{
+    diagnostic::Diagnostic,
     expression::{
-        AdditionExpression, DivisionExpression, Expression, MultiplicationExpression,
-        SubtractionExpression, ValueExpression,
+        expand_plus_minus, AbsExpression, AdditionExpression, ConditionalExpression,
+        ConjunctionExpression, ConstantExpression, DerivativeExpression, DivisionExpression,
+        Expression, Extremum, ExtremumExpression, FactorialExpression, Function,
+        FunctionCallExpression, FunctionExpression, IntegralExpression, IntervalExpression,
+        LimitDirection, LimitExpression, LimitTarget, MatrixDelimiter, MatrixExpression,
+        ModuloExpression, MultiplicationExpression, NegationExpression, PiecewiseExpression,
+        PlusMinusExpression, PowerExpression, ProductExpression, ProgramExpression, Relation,
+        RelationExpression, RootExpression, SetExpression, SubtractionExpression,
+        SummationExpression, TupleExpression, ValueExpression, VariableExpression,
     },
-    value::RationalValue,
+    locale::Locale,
+    operator::{Associativity, CustomOperator, Fixity, Operator, Precedence},
+    units::PhysicalConstant,
+    value::{ComplexValue, Constant, ConstantValue, InfinityValue, RationalValue, Sign, Value},
 };
 
+/// The largest exponent written in scientific notation, so `1e999999999` doesn't exhaust memory
+const MAX_SCIENTIFIC_EXPONENT: u32 = 10_000;
+
+pub use crate::mathml::parse_mathml;
+pub use crate::plain::parse_plain;
+pub use crate::rpn::parse_rpn;
+
 /// An expression parser
 #[derive(Parser)]
 #[grammar = "latex_expression.pest"]
 pub struct LatexExpressionParser;
 
-lazy_static::lazy_static! {
-    static ref PARSER: PrattParser<Rule> = {
-        use pest::pratt_parser::{Assoc::*, Op};
-        use Rule::*;
-
-        PrattParser::new()
-            .op(Op::infix(plus, Left) | Op::infix(minus, Left))
-            .op(Op::infix(cdot, Left) | Op::infix(asterisk, Left) | Op::infix(slash, Left))
-            .op(Op::prefix(negate))
-            .op(Op::postfix(factorial))
-            .op(Op::infix(carat, Left))
-    };
+/// How tightly implicit multiplication, like `2x`, binds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImplicitMultiplication {
+    /// Implicit multiplication binds tighter than division, so `1/2x` is `1/(2x)`
+    Strong,
+    /// Implicit multiplication binds like explicit multiplication, so `1/2x` is `(1/2)x`
+    Weak,
+}
+
+/// What a function written without brackets, like `\arcsin 2x`, takes as its argument
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FunctionArgument {
+    /// The function takes the first factor, so `\arcsin 2x` is `(\arcsin 2)x`
+    Factor,
+    /// The function takes the whole implicit product, so `\arcsin 2x` is `\arcsin(2x)`
+    Term,
+}
+
+/// The default maximum nesting depth of a parsed expression
+pub const DEFAULT_MAX_DEPTH: usize = 256;
+
+/// The default maximum length in bytes of a parsed input
+pub const DEFAULT_MAX_LENGTH: usize = 4096;
+
+/// The letters that are function names by default
+pub const DEFAULT_FUNCTION_LETTERS: &str = "fgh";
+
+/// Get the bit of an ASCII letter in a set of letters, with lowercase letters first
+fn letter_bit(letter: char) -> Option<u64> {
+    match letter {
+        'a'..='z' => Some(1 << (letter as u32 - 'a' as u32)),
+        'A'..='Z' => Some(1 << (26 + letter as u32 - 'A' as u32)),
+        _ => None,
+    }
+}
+
+/// Get the set of the ASCII letters in a string, ignoring any other characters
+fn letter_set(letters: &str) -> u64 {
+    letters
+        .chars()
+        .filter_map(letter_bit)
+        .fold(0, |set, bit| set | bit)
+}
+
+/// Options controlling how input is parsed, including limits so untrusted input can't exhaust the stack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParserOptions {
+    max_depth: usize,
+    max_length: usize,
+    locale: Locale,
+    implicit_multiplication: ImplicitMultiplication,
+    function_argument: FunctionArgument,
+    imaginary_unit: bool,
+    physical_constants: bool,
+    greek_constants: bool,
+    function_letters: u64,
+    precedences: [Precedence; Operator::ALL.len()],
+}
+
+impl ParserOptions {
+    /// Construct parser options without any limits
+    pub fn unlimited() -> Self {
+        Self {
+            max_depth: usize::MAX,
+            max_length: usize::MAX,
+            locale: Locale::default(),
+            implicit_multiplication: ImplicitMultiplication::Strong,
+            function_argument: FunctionArgument::Term,
+            imaginary_unit: true,
+            physical_constants: false,
+            greek_constants: false,
+            function_letters: letter_set(DEFAULT_FUNCTION_LETTERS),
+            precedences: Operator::ALL.map(|operator| operator.default_precedence()),
+        }
+    }
+
+    /// Set the maximum nesting depth of the parsed expression
+    pub fn with_max_depth(self, max_depth: usize) -> Self {
+        Self { max_depth, ..self }
+    }
+
+    /// Set the maximum length in bytes of the input
+    pub fn with_max_length(self, max_length: usize) -> Self {
+        Self { max_length, ..self }
+    }
+
+    /// Set the locale numbers and argument lists are written in
+    pub fn with_locale(self, locale: Locale) -> Self {
+        Self { locale, ..self }
+    }
+
+    /// Set how tightly implicit multiplication binds
+    pub fn with_implicit_multiplication(
+        self,
+        implicit_multiplication: ImplicitMultiplication,
+    ) -> Self {
+        Self {
+            implicit_multiplication,
+            ..self
+        }
+    }
+
+    /// Set what a function written without brackets takes as its argument
+    pub fn with_function_argument(self, function_argument: FunctionArgument) -> Self {
+        Self {
+            function_argument,
+            ..self
+        }
+    }
+
+    /// Set whether `i` is the imaginary unit rather than a variable
+    pub fn with_imaginary_unit(self, imaginary_unit: bool) -> Self {
+        Self {
+            imaginary_unit,
+            ..self
+        }
+    }
+
+    /// Set whether physical constants like `c` and `G` are recognized by name
+    ///
+    /// This is off by default because those names would otherwise be read as variables
+    pub fn with_physical_constants(self, physical_constants: bool) -> Self {
+        Self {
+            physical_constants,
+            ..self
+        }
+    }
+
+    /// Set whether the golden ratio `\\phi` and the Euler-Mascheroni constant `\\gamma` are
+    /// recognized by name
+    ///
+    /// This is off by default because those letters would otherwise be read as variables
+    pub fn with_greek_constants(self, greek_constants: bool) -> Self {
+        Self {
+            greek_constants,
+            ..self
+        }
+    }
+
+    /// Set the letters that name a function when followed by a single bracketed argument
+    ///
+    /// Other letters multiply the bracket, so by default `f(x)` is a call but `k(x)` is `k * x`.
+    /// This defaults to [`DEFAULT_FUNCTION_LETTERS`], and calls with more than one argument, like
+    /// `Q(n, k)`, are always calls. Characters that aren't ASCII letters are ignored.
+    pub fn with_function_letters(self, letters: &str) -> Self {
+        Self {
+            function_letters: letter_set(letters),
+            ..self
+        }
+    }
+
+    /// Set the precedence of a built-in operator
+    pub fn with_precedence(mut self, operator: Operator, precedence: Precedence) -> Self {
+        self.precedences[operator as usize] = precedence;
+        self
+    }
+
+    /// Get the maximum nesting depth of the parsed expression
+    pub fn get_max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Get the maximum length in bytes of the input
+    pub fn get_max_length(&self) -> usize {
+        self.max_length
+    }
+
+    /// Get the locale numbers and argument lists are written in
+    pub fn get_locale(&self) -> &Locale {
+        &self.locale
+    }
+
+    /// Get how tightly implicit multiplication binds
+    pub fn get_implicit_multiplication(&self) -> ImplicitMultiplication {
+        self.implicit_multiplication
+    }
+
+    /// Get what a function written without brackets takes as its argument
+    pub fn get_function_argument(&self) -> FunctionArgument {
+        self.function_argument
+    }
+
+    /// Check if `i` is the imaginary unit rather than a variable
+    pub fn is_imaginary_unit(&self) -> bool {
+        self.imaginary_unit
+    }
+
+    /// Check if physical constants like `c` and `G` are recognized by name
+    pub fn is_physical_constants(&self) -> bool {
+        self.physical_constants
+    }
+
+    /// Check if the golden ratio `\\phi` and the Euler-Mascheroni constant `\\gamma` are
+    /// recognized by name
+    pub fn is_greek_constants(&self) -> bool {
+        self.greek_constants
+    }
+
+    /// Check if a letter names a function when followed by a single bracketed argument
+    pub fn is_function_letter(&self, letter: char) -> bool {
+        letter_bit(letter).is_some_and(|bit| self.function_letters & bit != 0)
+    }
+
+    /// Get the precedence of a built-in operator
+    pub fn get_precedence(&self, operator: Operator) -> Precedence {
+        self.precedences[operator as usize]
+    }
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_length: DEFAULT_MAX_LENGTH,
+            locale: Locale::default(),
+            implicit_multiplication: ImplicitMultiplication::Strong,
+            function_argument: FunctionArgument::Term,
+            imaginary_unit: true,
+            physical_constants: false,
+            greek_constants: false,
+            function_letters: letter_set(DEFAULT_FUNCTION_LETTERS),
+            precedences: Operator::ALL.map(|operator| operator.default_precedence()),
+        }
+    }
+}
+
+/// An error encountered while parsing an expression
+#[derive(Debug)]
+pub enum ParseError {
+    /// The input isn't a valid expression
+    Syntax(Box<pest::error::Error<Rule>>),
+    /// The input is longer than the maximum length
+    TooLong { length: usize, max_length: usize },
+    /// The expression nests deeper than the maximum depth at the given byte position
+    TooDeep { position: usize, max_depth: usize },
+    /// A custom operator at the given byte position isn't registered
+    UnknownOperator { name: String, position: usize },
+    /// A matrix row at the given byte position has a different number of columns than the first
+    RaggedMatrix {
+        position: usize,
+        columns: usize,
+        expected: usize,
+    },
 }
 
+impl ParseError {
+    /// Construct a new syntax error with a message at a byte position of the input
+    pub(crate) fn custom(input: &str, position: usize, message: String) -> Self {
+        let position = pest::Position::new(input, position)
+            .expect("Unexpected error: an error position isn't on a character boundary!");
+        ParseError::Syntax(Box::new(pest::error::Error::new_from_pos(
+            ErrorVariant::CustomError { message },
+            position,
+        )))
+    }
+
+    /// Get the byte range of the input this error points at
+    pub fn get_span(&self) -> Range<usize> {
+        match self {
+            ParseError::Syntax(error) => match error.location {
+                InputLocation::Pos(position) => position..position,
+                InputLocation::Span((start, end)) => start..end,
+            },
+            ParseError::TooLong { length, max_length } => *max_length..*length,
+            ParseError::TooDeep { position, .. } | ParseError::RaggedMatrix { position, .. } => {
+                *position..*position + 1
+            }
+            ParseError::UnknownOperator { name, position } => {
+                // Names are written in `\mathbin{}`, while symbols are written alone
+                if name.chars().all(|c| c.is_ascii_alphabetic()) {
+                    *position..*position + "\\mathbin{}".len() + name.len()
+                } else {
+                    *position..*position + name.len()
+                }
+            }
+        }
+    }
+
+    /// Get the character a syntax error was found at, or `None` at the end of the input
+    pub fn get_token(&self) -> Option<char> {
+        let ParseError::Syntax(error) = self else {
+            return None;
+        };
+        let column = match error.line_col {
+            LineColLocation::Pos((_, column)) | LineColLocation::Span((_, column), _) => column,
+        };
+        error.line().chars().nth(column - 1)
+    }
+
+    /// Get what a syntax error expected to find instead, like `an operator`
+    pub fn get_expected(&self) -> Vec<&'static str> {
+        let ParseError::Syntax(error) = self else {
+            return Vec::new();
+        };
+        let ErrorVariant::ParsingError { positives, .. } = &error.variant else {
+            return Vec::new();
+        };
+        let mut expected = Vec::new();
+        for description in positives.iter().map(|rule| describe(*rule)) {
+            if !expected.contains(&description) {
+                expected.push(description);
+            }
+        }
+        expected
+    }
+
+    /// Get the message of a syntax error raised while building the expression, like a limit
+    /// being exceeded
+    pub fn get_custom_message(&self) -> Option<&str> {
+        match self {
+            ParseError::Syntax(error) => match &error.variant {
+                ErrorVariant::CustomError { message } => Some(message),
+                ErrorVariant::ParsingError { .. } => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Render this error under the line of the input it points at, with a caret marking it
+    pub fn render(&self, input: &str) -> String {
+        Diagnostic::from_parse_error(input, self).render(input, false)
+    }
+}
+
+/// Describe what a rule expected at the position of a syntax error matches
+fn describe(rule: Rule) -> &'static str {
+    match rule {
+        Rule::EOI => "the end of input",
+        Rule::factorial
+        | Rule::degree
+        | Rule::superscript
+        | Rule::plus
+        | Rule::minus
+        | Rule::plus_minus
+        | Rule::minus_plus
+        | Rule::cdot
+        | Rule::times
+        | Rule::asterisk
+        | Rule::slash
+        | Rule::div
+        | Rule::modulo
+        | Rule::carat
+        | Rule::implicit_times
+        | Rule::custom_operator
+        | Rule::custom_postfix
+        | Rule::shift_left
+        | Rule::shift_right
+        | Rule::bit_and
+        | Rule::bit_or
+        | Rule::bit_xor
+        | Rule::equal
+        | Rule::not_equal
+        | Rule::less_equal
+        | Rule::greater_equal
+        | Rule::less
+        | Rule::greater => "an operator",
+        Rule::interval_end | Rule::set_end => "`,`",
+        Rule::differential => "a differential like `dx`",
+        _ => "an expression",
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Syntax(error) => write!(f, "{}", error),
+            ParseError::TooLong { length, max_length } => write!(
+                f,
+                "input is {} bytes long, but at most {} are allowed",
+                length, max_length
+            ),
+            ParseError::TooDeep {
+                position,
+                max_depth,
+            } => write!(
+                f,
+                "expression nests deeper than {} levels at position {}",
+                max_depth, position
+            ),
+            ParseError::UnknownOperator { name, position } => {
+                write!(f, "unknown operator `{}` at position {}", name, position)
+            }
+            ParseError::RaggedMatrix {
+                position,
+                columns,
+                expected,
+            } => write!(
+                f,
+                "matrix row at position {} has {} columns, but {} were expected",
+                position, columns, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<pest::error::Error<Rule>> for ParseError {
+    fn from(error: pest::error::Error<Rule>) -> Self {
+        ParseError::Syntax(Box::new(error))
+    }
+}
+
+/// An expression being built, along with the height of its tree
+type Built = Result<(Box<dyn Expression>, usize), ParseError>;
+
+/// Bracketed groups already built, keyed by their byte range in the input
+pub(crate) type Groups = HashMap<Range<usize>, (Box<dyn Expression>, usize)>;
+
 /// Parse a LaTeX math expression
 #[inline]
-pub fn parse_latex(input: &str) -> Result<Pairs<Rule>, pest::error::Error<Rule>> {
-    LatexExpressionParser::parse(Rule::expression, input)
+#[allow(clippy::result_large_err)]
+pub fn parse_latex(input: &str) -> Result<Pairs<'_, Rule>, pest::error::Error<Rule>> {
+    LatexExpressionParser::parse(Rule::input, input)
+}
+
+/// Parse a LaTeX math expression into an expression, enforcing the given limits
+pub fn parse(input: &str, options: &ParserOptions) -> Result<Box<dyn Expression>, ParseError> {
+    parse_groups(input, options, None)
+}
+
+/// Parse an expression, reusing and recording bracketed groups if given a cache of them
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", name = "parse", skip_all, fields(length = input.len()))
+)]
+pub(crate) fn parse_groups(
+    input: &str,
+    options: &ParserOptions,
+    groups: Option<&RefCell<Groups>>,
+) -> Result<Box<dyn Expression>, ParseError> {
+    if input.len() > options.max_length {
+        return Err(ParseError::TooLong {
+            length: input.len(),
+            max_length: options.max_length,
+        });
+    }
+
+    // Reject deep nesting before the recursive descent parser sees it
+    let mut depth = 0_usize;
+    for (position, character) in input.char_indices() {
+        match character {
+            '(' | '{' | '[' => {
+                depth += 1;
+                if depth > options.max_depth {
+                    return Err(ParseError::TooDeep {
+                        position,
+                        max_depth: options.max_depth,
+                    });
+                }
+            }
+            ')' | '}' | ']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    let input = options.locale.normalize(input);
+    #[cfg(feature = "tracing")]
+    let start = std::time::Instant::now();
+    let result = build(parse_latex(&input)?, options, groups);
+    #[cfg(feature = "tracing")]
+    match &result {
+        Ok((expression, height)) => tracing::debug!(
+            nodes = expression.node_count(),
+            height,
+            micros = start.elapsed().as_micros() as u64,
+            "parsed expression"
+        ),
+        Err(error) => {
+            tracing::debug!(%error, micros = start.elapsed().as_micros() as u64, "parse failed")
+        }
+    }
+    result.map(|(expression, _)| expand_plus_minus(expression))
 }
 
 /// Parse pairs
 pub fn parse_pairs(pairs: Pairs<Rule>) -> Box<dyn Expression> {
-    PARSER
-        .map_primary(|primary| match primary.as_rule() {
-            Rule::number => Box::new(ValueExpression::new(Box::new(
-                primary.as_str().parse::<RationalValue>().unwrap(),
-            ))),
-            Rule::implicit_multiplication => {
-                let mut inner = primary.into_inner().rev();
-                let mut expression = parse_pairs(Pairs::single(inner.next().unwrap()));
-                for pair in inner {
-                    expression = Box::new(MultiplicationExpression::new(
-                        expression,
-                        parse_pairs(Pairs::single(pair)),
-                    ));
+    build(pairs, &ParserOptions::unlimited(), None)
+        .map(|(expression, _)| expand_plus_minus(expression))
+        .expect("Unexpected error: an unlimited parse exceeded its limits!")
+}
+
+/// Record the span of a newly built node and check its height against the maximum depth
+fn node(
+    mut expression: Box<dyn Expression>,
+    height: usize,
+    span: Range<usize>,
+    position: usize,
+    options: &ParserOptions,
+) -> Built {
+    expression.set_span(span);
+    if height > options.max_depth {
+        Err(ParseError::TooDeep {
+            position,
+            max_depth: options.max_depth,
+        })
+    } else {
+        Ok((expression, height))
+    }
+}
+
+/// Build a bracketed group, taking it from the cache if it was built before
+fn group(
+    range: Range<usize>,
+    groups: Option<&RefCell<Groups>>,
+    build: impl FnOnce() -> Built,
+) -> Built {
+    let Some(groups) = groups else {
+        return build();
+    };
+    if let Some((expression, height)) = groups.borrow().get(&range) {
+        return Ok((dyn_clone::clone_box(expression.as_ref()), *height));
+    }
+    let (expression, height) = build()?;
+    groups
+        .borrow_mut()
+        .insert(range, (dyn_clone::clone_box(expression.as_ref()), height));
+    Ok((expression, height))
+}
+
+/// Build an expression from pairs, tracking the height of the tree as it grows
+fn build<'i>(
+    pairs: impl Iterator<Item = Pair<'i, Rule>>,
+    options: &ParserOptions,
+    groups: Option<&RefCell<Groups>>,
+) -> Built {
+    let builder = Builder {
+        options,
+        groups,
+        pending: RefCell::new(None),
+    };
+    let mut pairs = pairs.filter(|pair| pair.as_rule() != Rule::EOI).peekable();
+    if pairs
+        .peek()
+        .is_some_and(|pair| pair.as_rule() == Rule::statement)
+    {
+        return program(pairs, options, groups);
+    }
+    builder.operation(&mut pairs, 0, 0)
+}
+
+/// Build the arguments inside a pair into a tuple, with the height of the tallest argument
+fn arguments(
+    pair: Pair<'_, Rule>,
+    options: &ParserOptions,
+    groups: Option<&RefCell<Groups>>,
+) -> Result<(TupleExpression, usize), ParseError> {
+    let mut elements = Vec::new();
+    let mut height = 0;
+    for argument in pair
+        .into_inner()
+        .filter(|pair| pair.as_rule() == Rule::argument)
+    {
+        let (element, element_height) = build(argument.into_inner(), options, groups)?;
+        elements.push(element);
+        height = height.max(element_height);
+    }
+    Ok((TupleExpression::new(elements), height))
+}
+
+/// Build the statements of an input, making a program of them if there's more than one
+fn program<'i>(
+    pairs: impl Iterator<Item = Pair<'i, Rule>>,
+    options: &ParserOptions,
+    groups: Option<&RefCell<Groups>>,
+) -> Built {
+    let mut statements = Vec::new();
+    let mut height = 0;
+    for statement in pairs {
+        let (statement, statement_height) = build(statement.into_inner(), options, groups)?;
+        statements.push(statement);
+        height = height.max(statement_height);
+    }
+    if statements.len() == 1 {
+        return Ok((statements.pop().unwrap(), height));
+    }
+    let span = covering(
+        statements[0].as_ref(),
+        statements[statements.len() - 1].as_ref(),
+    );
+    let position = span.as_ref().map_or(0, |span| span.start);
+    node(
+        Box::new(ProgramExpression::new(statements)),
+        height + 1,
+        span.unwrap_or_default(),
+        position,
+        options,
+    )
+}
+
+/// Builds expressions by precedence climbing, with the precedences given by the options
+struct Builder<'a> {
+    options: &'a ParserOptions,
+    groups: Option<&'a RefCell<Groups>>,
+    /// The bracket after a letter that isn't a function, which the letter implicitly multiplies
+    pending: RefCell<Option<(Box<dyn Expression>, usize)>>,
+}
+
+impl Builder<'_> {
+    /// Build an operation from operators binding at least as tightly as the given level
+    fn operation<'i>(
+        &self,
+        pairs: &mut Peekable<impl Iterator<Item = Pair<'i, Rule>>>,
+        level: u8,
+        depth: usize,
+    ) -> Built {
+        if depth > self.options.max_depth {
+            return Err(ParseError::TooDeep {
+                position: pairs.peek().map_or(0, |pair| pair.as_span().start()),
+                max_depth: self.options.max_depth,
+            });
+        }
+
+        let mut lhs = self.operand(pairs, depth)?;
+        loop {
+            // A pending bracket is multiplied as if there were an implicit times before it
+            if self.pending.borrow().is_some() {
+                let precedence = self.options.get_precedence(self.implicit_operator());
+                if precedence.get_level() < level {
+                    break;
                 }
-                expression
+                let rhs = self.operation(pairs, next_level(precedence), depth + 1)?;
+                lhs = self.implicit_product(lhs, rhs)?;
+                continue;
             }
-            Rule::paren_expression => parse_pairs(primary.into_inner()),
-            Rule::expression => parse_pairs(primary.into_inner()),
+
+            let Some(op) = pairs.peek() else {
+                break;
+            };
+            let precedence = self.precedence(op)?;
+            if precedence.get_level() < level {
+                break;
+            }
+            let op = pairs.next().unwrap();
+            if matches!(
+                op.as_rule(),
+                Rule::factorial | Rule::degree | Rule::superscript | Rule::custom_postfix
+            ) {
+                lhs = self.postfix(lhs, op)?;
+                continue;
+            }
+
+            let rhs = self.operation(pairs, next_level(precedence), depth + 1)?;
+            lhs = self.infix(lhs, op, rhs)?;
+        }
+        Ok(lhs)
+    }
+
+    /// Get the operator implicit multiplication binds like
+    fn implicit_operator(&self) -> Operator {
+        match self.options.implicit_multiplication {
+            ImplicitMultiplication::Strong => Operator::Implicit,
+            ImplicitMultiplication::Weak => Operator::Multiplicative,
+        }
+    }
+
+    /// Build the implicit product of two operands
+    fn implicit_product(
+        &self,
+        (lhs, lhs_height): (Box<dyn Expression>, usize),
+        (rhs, rhs_height): (Box<dyn Expression>, usize),
+    ) -> Built {
+        let span = covering(lhs.as_ref(), rhs.as_ref()).unwrap_or_default();
+        let position = span.start;
+        node(
+            Box::new(MultiplicationExpression::new(lhs, rhs)),
+            lhs_height.max(rhs_height) + 1,
+            span,
+            position,
+            self.options,
+        )
+    }
+
+    /// Build an operand, along with any prefix operators applied to it
+    fn operand<'i>(
+        &self,
+        pairs: &mut Peekable<impl Iterator<Item = Pair<'i, Rule>>>,
+        depth: usize,
+    ) -> Built {
+        if let Some(pending) = self.pending.take() {
+            return Ok(pending);
+        }
+        let pair = pairs
+            .next()
+            .expect("Unexpected error: an operator is missing its operand!");
+        match pair.as_rule() {
+            Rule::negate | Rule::bit_not => {
+                let level = self.options.get_precedence(Operator::Prefix).get_level();
+                let operand = self.operation(pairs, level, depth + 1)?;
+                self.prefix(pair, operand)
+            }
+            Rule::summation | Rule::product | Rule::limit | Rule::derivative => {
+                // The body of a summation, product, limit or derivative takes in products, but not sums
+                let level = self
+                    .options
+                    .get_precedence(Operator::Multiplicative)
+                    .get_level();
+                let operand = self.operation(pairs, level, depth + 1)?;
+                self.prefix(pair, operand)
+            }
+            Rule::custom_prefix => {
+                let level = self
+                    .custom(&pair, Fixity::Prefix)?
+                    .get_precedence()
+                    .get_level();
+                let operand = self.operation(pairs, level, depth + 1)?;
+                self.prefix(pair, operand)
+            }
+            _ => self.primary(pair),
+        }
+    }
+
+    /// Get the precedence of an infix or postfix operator
+    fn precedence(&self, op: &Pair<'_, Rule>) -> Result<Precedence, ParseError> {
+        let operator = match op.as_rule() {
+            Rule::equal
+            | Rule::not_equal
+            | Rule::less
+            | Rule::less_equal
+            | Rule::greater
+            | Rule::greater_equal => Operator::Relation,
+            Rule::bit_or => Operator::BitOr,
+            Rule::bit_xor => Operator::BitXor,
+            Rule::bit_and => Operator::BitAnd,
+            Rule::shift_left | Rule::shift_right => Operator::Shift,
+            Rule::plus | Rule::minus | Rule::plus_minus | Rule::minus_plus => Operator::Additive,
+            Rule::cdot | Rule::times | Rule::asterisk | Rule::slash | Rule::div | Rule::modulo => {
+                Operator::Multiplicative
+            }
+            Rule::implicit_times => self.implicit_operator(),
+            Rule::carat => Operator::Power,
+            Rule::factorial | Rule::degree | Rule::superscript => Operator::Factorial,
+            Rule::custom_operator => return Ok(self.custom(op, Fixity::Infix)?.get_precedence()),
+            Rule::custom_postfix => return Ok(self.custom(op, Fixity::Postfix)?.get_precedence()),
             rule => unreachable!("Unexpected rule: {:?}", rule),
+        };
+        Ok(self.options.get_precedence(operator))
+    }
+
+    /// Look up the registered operator named by a custom operator
+    fn custom(&self, op: &Pair<'_, Rule>, fixity: Fixity) -> Result<CustomOperator, ParseError> {
+        let name = op.clone().into_inner().next().unwrap().as_str();
+        CustomOperator::lookup(name, fixity).ok_or_else(|| ParseError::UnknownOperator {
+            name: name.to_string(),
+            position: op.as_span().start(),
         })
-        .map_infix(|lhs, op, rhs| match op.as_rule() {
+    }
+
+    /// Build a primary expression
+    fn primary(&self, primary: Pair<'_, Rule>) -> Built {
+        let (options, groups) = (self.options, self.groups);
+        match primary.as_rule() {
+            Rule::scientific | Rule::e_notation => {
+                let mut inner = primary.clone().into_inner();
+                let mantissa = number_value(&inner.next().unwrap());
+                let exponent = inner.next().unwrap();
+                let power = exponent
+                    .as_str()
+                    .parse::<i32>()
+                    .ok()
+                    .filter(|power| power.unsigned_abs() <= MAX_SCIENTIFIC_EXPONENT)
+                    .and_then(|power| RationalValue::from(num::BigInt::from(10)).pow(power))
+                    .ok_or_else(|| {
+                        ParseError::Syntax(Box::new(pest::error::Error::new_from_span(
+                            pest::error::ErrorVariant::CustomError {
+                                message: format!(
+                                    "exponents in scientific notation can be at most {}",
+                                    MAX_SCIENTIFIC_EXPONENT
+                                ),
+                            },
+                            exponent.as_span(),
+                        )))
+                    })?;
+                node(
+                    Box::new(ValueExpression::new(mantissa.mul(&power))),
+                    1,
+                    range(&primary),
+                    primary.as_span().start(),
+                    options,
+                )
+            }
+            Rule::mixed_number => {
+                // A whole number written right before a fraction is added to it
+                let mut inner = primary.clone().into_inner();
+                let whole = inner
+                    .next()
+                    .unwrap()
+                    .as_str()
+                    .parse::<RationalValue>()
+                    .unwrap();
+                let fraction = inner.next().unwrap();
+                let fraction: Box<dyn Value> = match fraction.as_rule() {
+                    Rule::vulgar_fraction => Box::new(vulgar_fraction(fraction.as_str())),
+                    _ => {
+                        let numerator = fraction.as_str().parse::<RationalValue>().unwrap();
+                        let denominator = inner.next().unwrap().as_str();
+                        numerator.div(&denominator.parse::<RationalValue>().unwrap())
+                    }
+                };
+                node(
+                    Box::new(ValueExpression::new(whole.add(fraction.as_ref()))),
+                    1,
+                    range(&primary),
+                    primary.as_span().start(),
+                    options,
+                )
+            }
+            Rule::vulgar_fraction => node(
+                Box::new(ValueExpression::new(Box::new(vulgar_fraction(
+                    primary.as_str(),
+                )))),
+                1,
+                range(&primary),
+                primary.as_span().start(),
+                options,
+            ),
+            Rule::infinity => node(
+                Box::new(ValueExpression::new(Box::new(InfinityValue::positive()))),
+                1,
+                range(&primary),
+                primary.as_span().start(),
+                options,
+            ),
+            Rule::percentage => {
+                // Percentages are rationals over a hundred
+                let number = primary.clone().into_inner().next().unwrap();
+                let value = number_value(&number);
+                node(
+                    Box::new(ValueExpression::new(
+                        value.div(&RationalValue::from(num::BigInt::from(100))),
+                    )),
+                    1,
+                    range(&primary),
+                    primary.as_span().start(),
+                    options,
+                )
+            }
+            Rule::number | Rule::digit => node(
+                Box::new(ValueExpression::new(Box::new(number_value(&primary)))),
+                1,
+                range(&primary),
+                primary.as_span().start(),
+                options,
+            ),
+            Rule::radix_number => {
+                let (prefix, digits) = primary.as_str().split_at(2);
+                let radix = match prefix {
+                    "0b" => 2,
+                    "0o" => 8,
+                    _ => 16,
+                };
+                node(
+                    Box::new(ValueExpression::new(Box::new(
+                        RationalValue::from_str_radix(digits, radix).unwrap(),
+                    ))),
+                    1,
+                    range(&primary),
+                    primary.as_span().start(),
+                    options,
+                )
+            }
+            Rule::variable if options.imaginary_unit && primary.as_str() == "i" => node(
+                Box::new(ValueExpression::new(Box::new(
+                    ComplexValue::imaginary_unit(),
+                ))),
+                1,
+                range(&primary),
+                primary.as_span().start(),
+                options,
+            ),
+            Rule::variable | Rule::command => node(
+                parse_name(primary.as_str(), options),
+                1,
+                range(&primary),
+                primary.as_span().start(),
+                options,
+            ),
+            // An upright e is always Euler's number, even if `e` were bound as a variable
+            Rule::upright_e => node(
+                Box::new(ConstantExpression::new(Constant::e())),
+                1,
+                range(&primary),
+                primary.as_span().start(),
+                options,
+            ),
+            Rule::function_call => {
+                let span = range(&primary);
+                let mut inner = primary.into_inner();
+                let name = inner.next().unwrap();
+
+                // Logarithms carry their base in their name, defaulting to ten
+                let base = match name.clone().into_inner().next() {
+                    Some(base) => Some(build(base.into_inner(), options, groups)?),
+                    None if name.as_rule() == Rule::log => Some((
+                        Box::new(ValueExpression::new(Box::new(RationalValue::from(
+                            num::BigInt::from(10),
+                        )))) as Box<dyn Expression>,
+                        1,
+                    )),
+                    None => None,
+                };
+                let function = match name.as_rule() {
+                    Rule::sin => Function::Sin,
+                    Rule::cos => Function::Cos,
+                    Rule::tan => Function::Tan,
+                    Rule::sinh => Function::Sinh,
+                    Rule::cosh => Function::Cosh,
+                    Rule::tanh => Function::Tanh,
+                    Rule::ln => Function::Ln,
+                    Rule::log => Function::Log,
+                    Rule::arcsin => Function::Arcsin,
+                    Rule::arccos => Function::Arccos,
+                    Rule::arctan => Function::Arctan,
+                    Rule::gamma => Function::Gamma,
+                    Rule::erf => Function::Erf,
+                    Rule::beta => Function::Beta,
+                    Rule::and_name => Function::BitAnd,
+                    Rule::or_name => Function::BitOr,
+                    Rule::xor_name => Function::BitXor,
+                    Rule::not_name => Function::BitNot,
+                    Rule::shl_name => Function::ShiftLeft,
+                    Rule::shr_name => Function::ShiftRight,
+                    rule => unreachable!("Unexpected rule: {:?}", rule),
+                };
+                let arguments = inner.next().unwrap();
+                let mut factors = Vec::new();
+                let mut derivative = None;
+                let arguments = match arguments.as_rule() {
+                    Rule::primes => {
+                        // Prime notation differentiates with respect to the argument
+                        let argument = inner.next().unwrap();
+                        derivative = Some((arguments.as_str().len(), variable_name(&argument)));
+                        vec![build(Pairs::single(argument), options, groups)?]
+                    }
+                    Rule::argument_list => arguments
+                        .into_inner()
+                        .map(|argument| build(argument.into_inner(), options, groups))
+                        .collect::<Result<Vec<_>, _>>()?,
+                    Rule::bare_argument
+                        if options.function_argument == FunctionArgument::Factor =>
+                    {
+                        // Only take the first factor, multiplying the result by the rest
+                        let mut inner = arguments
+                            .into_inner()
+                            .filter(|pair| pair.as_rule() != Rule::implicit_times);
+                        let argument =
+                            build(Pairs::single(inner.next().unwrap()), options, groups)?;
+                        factors.extend(inner);
+                        vec![argument]
+                    }
+                    Rule::bare_argument => {
+                        vec![build(arguments.into_inner(), options, groups)?]
+                    }
+                    _ => vec![build(Pairs::single(arguments), options, groups)?],
+                };
+                let arguments = base.into_iter().chain(arguments).collect::<Vec<_>>();
+                let height = arguments
+                    .iter()
+                    .map(|(_, height)| *height)
+                    .max()
+                    .unwrap_or(0);
+                let arguments = arguments
+                    .into_iter()
+                    .map(|(argument, _)| argument)
+                    .collect();
+                let end = factors
+                    .first()
+                    .map_or(span.end, |factor| factor.as_span().start());
+                let (mut expression, mut height) = node(
+                    Box::new(FunctionExpression::new(function, arguments)),
+                    height + 1,
+                    span.start..end,
+                    span.start,
+                    options,
+                )?;
+                if let Some((order, variable)) = derivative {
+                    (expression, height) = node(
+                        Box::new(
+                            DerivativeExpression::new(expression, variable)
+                                .with_order(order as u32),
+                        ),
+                        height + 1,
+                        span.clone(),
+                        span.start,
+                        options,
+                    )?;
+                }
+                for factor in factors {
+                    let position = factor.as_span().start();
+                    let (factor, factor_height) = build(Pairs::single(factor), options, groups)?;
+                    let span = covering(expression.as_ref(), factor.as_ref());
+                    (expression, height) = node(
+                        Box::new(MultiplicationExpression::new(expression, factor)),
+                        height.max(factor_height) + 1,
+                        span.unwrap_or(position..position),
+                        position,
+                        options,
+                    )?;
+                }
+                Ok((expression, height))
+            }
+            Rule::variable_call => {
+                let span = range(&primary);
+                let mut inner = primary.into_inner();
+                let name = inner.next().unwrap();
+
+                // A letter that isn't a function multiplies a single bracketed argument instead
+                let list = inner.peek().unwrap();
+                let mut list_arguments = list
+                    .clone()
+                    .into_inner()
+                    .filter(|pair| pair.as_rule() == Rule::argument);
+                if let (Rule::variable, Some(argument), None) =
+                    (name.as_rule(), list_arguments.next(), list_arguments.next())
+                {
+                    if !name
+                        .as_str()
+                        .starts_with(|letter| options.is_function_letter(letter))
+                    {
+                        let span = range(&list);
+                        let bracket = group(span.clone(), groups, || {
+                            let (mut expression, height) =
+                                build(argument.into_inner(), options, groups)?;
+                            expression.set_span(span);
+                            Ok((expression, height))
+                        })?;
+                        *self.pending.borrow_mut() = Some(bracket);
+                        return self.primary(name);
+                    }
+                }
+
+                let name = match name.as_rule() {
+                    Rule::operator_identifier => name.as_str().to_string(),
+                    _ => variable_name(&name),
+                };
+                let (arguments, height) = arguments(inner.next().unwrap(), options, groups)?;
+                let arguments = arguments.into_elements();
+
+                // Two arguments to C and P count combinations and permutations
+                let call: Box<dyn Expression> = match (name.as_str(), arguments.len()) {
+                    ("C", 2) => Box::new(FunctionExpression::new(Function::Binomial, arguments)),
+                    ("P", 2) => Box::new(FunctionExpression::new(Function::Permutation, arguments)),
+                    _ => Box::new(FunctionCallExpression::new(name, arguments)),
+                };
+                node(call, height + 1, span.clone(), span.start, options)
+            }
+            Rule::prime_variable => {
+                let span = range(&primary);
+                let mut inner = primary.into_inner();
+                let name = variable_name(&inner.next().unwrap());
+                let order = inner.next().unwrap().as_str().len();
+                let argument = inner.next().unwrap();
+                let variable = variable_name(&argument);
+                let (argument, height) = self.primary(argument)?;
+                let function = Box::new(FunctionCallExpression::new(name, vec![argument]));
+                node(
+                    Box::new(
+                        DerivativeExpression::new(function, variable).with_order(order as u32),
+                    ),
+                    height + 1,
+                    span.clone(),
+                    span.start,
+                    options,
+                )
+            }
+            Rule::conditional => {
+                let span = range(&primary);
+                let mut height = 0;
+                let mut branches = Vec::new();
+                for branch in primary.into_inner() {
+                    let (branch, branch_height) = build(branch.into_inner(), options, groups)?;
+                    height = height.max(branch_height);
+                    branches.push(branch);
+                }
+                let otherwise = branches.pop().unwrap();
+                let then = branches.pop().unwrap();
+                let condition = branches.pop().unwrap();
+                node(
+                    Box::new(ConditionalExpression::new(condition, then, otherwise)),
+                    height + 1,
+                    span.clone(),
+                    span.start,
+                    options,
+                )
+            }
+            Rule::cases => {
+                let span = range(&primary);
+                let mut branches = Vec::new();
+                let mut height = 0;
+                for row in primary.into_inner() {
+                    let mut inner = row.into_inner();
+                    let (expression, expression_height) =
+                        build(inner.next().unwrap().into_inner(), options, groups)?;
+                    height = height.max(expression_height);
+                    let condition = inner.next().unwrap();
+                    let condition = match condition.as_rule() {
+                        Rule::cases_condition => {
+                            let (condition, condition_height) =
+                                build(condition.into_inner(), options, groups)?;
+                            height = height.max(condition_height);
+                            Some(condition)
+                        }
+                        _ => None,
+                    };
+                    branches.push((expression, condition));
+                }
+                node(
+                    Box::new(PiecewiseExpression::new(branches)),
+                    height + 1,
+                    span.clone(),
+                    span.start,
+                    options,
+                )
+            }
+            Rule::matrix => {
+                let span = range(&primary);
+                let mut inner = primary.into_inner();
+                let delimiter = match inner.next().unwrap().as_str() {
+                    "pmatrix" => MatrixDelimiter::Parentheses,
+                    "bmatrix" => MatrixDelimiter::Brackets,
+                    _ => MatrixDelimiter::Bars,
+                };
+                let mut rows = Vec::new();
+                let mut height = 0;
+                for row in inner {
+                    let position = row.as_span().start();
+                    let entries = row
+                        .into_inner()
+                        .map(|entry| build(entry.into_inner(), options, groups))
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    // Every row must be as long as the first
+                    let expected = rows.first().map_or(entries.len(), Vec::len);
+                    if entries.len() != expected {
+                        return Err(ParseError::RaggedMatrix {
+                            position,
+                            columns: entries.len(),
+                            expected,
+                        });
+                    }
+                    let mut row = Vec::new();
+                    for (entry, entry_height) in entries {
+                        height = height.max(entry_height);
+                        row.push(entry);
+                    }
+                    rows.push(row);
+                }
+                node(
+                    Box::new(MatrixExpression::new(rows, delimiter)),
+                    height + 1,
+                    span.clone(),
+                    span.start,
+                    options,
+                )
+            }
+            Rule::extremum => {
+                let span = range(&primary);
+                let mut inner = primary.into_inner();
+                let extremum = match inner.next().unwrap().as_rule() {
+                    Rule::min => Extremum::Min,
+                    _ => Extremum::Max,
+                };
+                let (arguments, height) = arguments(inner.next().unwrap(), options, groups)?;
+                node(
+                    Box::new(ExtremumExpression::new(extremum, arguments.into_elements())),
+                    height + 1,
+                    span.clone(),
+                    span.start,
+                    options,
+                )
+            }
+            Rule::binom => {
+                let span = range(&primary);
+                let mut inner = primary.into_inner();
+                let (n, n_height) = self.primary(inner.next().unwrap())?;
+                let (k, k_height) = self.primary(inner.next().unwrap())?;
+                node(
+                    Box::new(FunctionExpression::new(Function::Binomial, vec![n, k])),
+                    n_height.max(k_height) + 1,
+                    span.clone(),
+                    span.start,
+                    options,
+                )
+            }
+            Rule::selection => {
+                let span = range(&primary);
+                let mut inner = primary.into_inner();
+                let (n, n_height) = self.primary(inner.next().unwrap())?;
+                let function = match inner.next().unwrap().as_str() {
+                    "C" => Function::Binomial,
+                    _ => Function::Permutation,
+                };
+                let (k, k_height) = self.primary(inner.next().unwrap())?;
+                node(
+                    Box::new(FunctionExpression::new(function, vec![n, k])),
+                    n_height.max(k_height) + 1,
+                    span.clone(),
+                    span.start,
+                    options,
+                )
+            }
+            Rule::frac => {
+                let span = range(&primary);
+                let mut inner = primary.into_inner();
+                let (numerator, numerator_height) = self.primary(inner.next().unwrap())?;
+                let (denominator, denominator_height) = self.primary(inner.next().unwrap())?;
+                node(
+                    Box::new(DivisionExpression::new(numerator, denominator)),
+                    numerator_height.max(denominator_height) + 1,
+                    span.clone(),
+                    span.start,
+                    options,
+                )
+            }
+            Rule::sqrt => {
+                let span = range(&primary);
+                let mut inner = primary.into_inner().peekable();
+                let index = match inner.peek().map(Pair::as_rule) {
+                    Some(Rule::root_index) => {
+                        Some(build(inner.next().unwrap().into_inner(), options, groups)?)
+                    }
+                    _ => None,
+                };
+                let (radicand, height) = self.primary(inner.next().unwrap())?;
+                let (expression, height) = match index {
+                    Some((index, index_height)) => (
+                        RootExpression::new(radicand, index),
+                        height.max(index_height),
+                    ),
+                    None => (RootExpression::square(radicand), height),
+                };
+                node(
+                    Box::new(expression),
+                    height + 1,
+                    span.clone(),
+                    span.start,
+                    options,
+                )
+            }
+            Rule::radical => {
+                let span = range(&primary);
+                let mut inner = primary.into_inner();
+                let index = match inner.next().unwrap().as_str() {
+                    "√" => 2_u32,
+                    "∛" => 3,
+                    _ => 4,
+                };
+                let (radicand, height) = self.primary(inner.next().unwrap())?;
+                node(
+                    Box::new(RootExpression::new(
+                        radicand,
+                        Box::new(ValueExpression::new(Box::new(RationalValue::new(
+                            Sign::Positive,
+                            index,
+                            1_u32,
+                        )))),
+                    )),
+                    height + 1,
+                    span.clone(),
+                    span.start,
+                    options,
+                )
+            }
+            Rule::integral => {
+                let span = range(&primary);
+                let mut inner = primary.into_inner().peekable();
+                let bounds = match inner.peek().map(Pair::as_rule) {
+                    Some(Rule::integral_bounds) => {
+                        let mut bounds = inner.next().unwrap().into_inner();
+                        Some((
+                            self.primary(bounds.next().unwrap())?,
+                            self.primary(bounds.next().unwrap())?,
+                        ))
+                    }
+                    _ => None,
+                };
+                let (mut integrand, height) =
+                    build(inner.next().unwrap().into_inner(), options, groups)?;
+                let variable = variable_name(&inner.next().unwrap().into_inner().next().unwrap());
+                bind_variable(&mut integrand, &variable);
+                let integral = IntegralExpression::new(integrand, variable);
+                let (integral, height) = match bounds {
+                    Some(((lower, lower_height), (upper, upper_height))) => (
+                        integral.with_bounds(lower, upper),
+                        height.max(lower_height).max(upper_height),
+                    ),
+                    None => (integral, height),
+                };
+                node(
+                    Box::new(integral),
+                    height + 1,
+                    span.clone(),
+                    span.start,
+                    options,
+                )
+            }
+            Rule::abs_expression => {
+                let span = range(&primary);
+                let (argument, height) = build(primary.into_inner(), options, groups)?;
+                node(
+                    Box::new(AbsExpression::new(argument)),
+                    height + 1,
+                    span.clone(),
+                    span.start,
+                    options,
+                )
+            }
+            Rule::floor_expression | Rule::ceil_expression => {
+                let span = range(&primary);
+                let function = match primary.as_rule() {
+                    Rule::floor_expression => Function::Floor,
+                    _ => Function::Ceil,
+                };
+                let (argument, height) = build(primary.into_inner(), options, groups)?;
+                node(
+                    Box::new(FunctionExpression::new(function, vec![argument])),
+                    height + 1,
+                    span.clone(),
+                    span.start,
+                    options,
+                )
+            }
+            Rule::paren_expression
+                if primary
+                    .clone()
+                    .into_inner()
+                    .any(|pair| pair.as_rule() == Rule::interval_end) =>
+            {
+                // Brackets around two expressions separated by a comma are an interval
+                let span = range(&primary);
+                let brackets = primary
+                    .as_str()
+                    .trim_start_matches(|c| c != '(' && c != '[');
+                let lower_closed = brackets.starts_with('[');
+                let inner = primary.into_inner();
+                let (lower, lower_height) = build(
+                    inner
+                        .clone()
+                        .filter(|pair| pair.as_rule() != Rule::interval_end),
+                    options,
+                    groups,
+                )?;
+                let mut end = inner.last().unwrap().into_inner();
+                let (upper, upper_height) =
+                    build(end.next().unwrap().into_inner(), options, groups)?;
+                let upper_closed = end.next().unwrap().as_str() == "]";
+                node(
+                    Box::new(
+                        IntervalExpression::new(lower, upper)
+                            .with_closed(lower_closed, upper_closed),
+                    ),
+                    lower_height.max(upper_height) + 1,
+                    span.clone(),
+                    span.start,
+                    options,
+                )
+            }
+            Rule::paren_expression
+                if primary
+                    .clone()
+                    .into_inner()
+                    .any(|pair| pair.as_rule() == Rule::tuple_end) =>
+            {
+                // Parentheses around three or more expressions separated by commas are a tuple
+                let span = range(&primary);
+                let inner = primary.into_inner();
+                let (first, first_height) = build(
+                    inner
+                        .clone()
+                        .filter(|pair| pair.as_rule() != Rule::tuple_end),
+                    options,
+                    groups,
+                )?;
+                let (tuple, height) = arguments(inner.last().unwrap(), options, groups)?;
+                let mut elements = vec![first];
+                elements.extend(tuple.into_elements());
+                node(
+                    Box::new(TupleExpression::new(elements)),
+                    height.max(first_height) + 1,
+                    span.clone(),
+                    span.start,
+                    options,
+                )
+            }
+            Rule::paren_expression
+                if primary
+                    .clone()
+                    .into_inner()
+                    .any(|pair| pair.as_rule() == Rule::set_end) =>
+            {
+                // Braces around expressions separated by commas are a set
+                let span = range(&primary);
+                let inner = primary.into_inner();
+                let (first, first_height) = build(
+                    inner.clone().filter(|pair| pair.as_rule() != Rule::set_end),
+                    options,
+                    groups,
+                )?;
+                let mut elements = vec![first];
+                let mut height = first_height;
+                for argument in inner.last().unwrap().into_inner() {
+                    let (element, element_height) = build(argument.into_inner(), options, groups)?;
+                    elements.push(element);
+                    height = height.max(element_height);
+                }
+                node(
+                    Box::new(SetExpression::new(elements)),
+                    height + 1,
+                    span.clone(),
+                    span.start,
+                    options,
+                )
+            }
+            Rule::paren_expression | Rule::braced_expression => {
+                let span = range(&primary);
+                group(span.clone(), groups, || {
+                    // Include the brackets in the span of the group
+                    let (mut expression, height) = build(primary.into_inner(), options, groups)?;
+                    expression.set_span(span);
+                    Ok((expression, height))
+                })
+            }
+            Rule::expression => build(primary.into_inner(), options, groups),
+            rule => unreachable!("Unexpected rule: {:?}", rule),
+        }
+    }
+
+    /// Build an infix operation from its operands
+    fn infix(
+        &self,
+        (lhs, lhs_height): (Box<dyn Expression>, usize),
+        op: Pair<'_, Rule>,
+        (rhs, rhs_height): (Box<dyn Expression>, usize),
+    ) -> Built {
+        let span = covering(lhs.as_ref(), rhs.as_ref()).unwrap_or_else(|| range(&op));
+        let expression: Box<dyn Expression> = match op.as_rule() {
             Rule::plus => Box::new(AdditionExpression::new(lhs, rhs)),
             Rule::minus => Box::new(SubtractionExpression::new(lhs, rhs)),
+            Rule::plus_minus => Box::new(PlusMinusExpression::new(lhs, rhs)),
+            Rule::minus_plus => Box::new(PlusMinusExpression::minus_plus(lhs, rhs)),
             Rule::asterisk => Box::new(MultiplicationExpression::new(lhs, rhs)),
-            Rule::cdot => Box::new(MultiplicationExpression::new(lhs, rhs)),
-            Rule::slash => Box::new(DivisionExpression::new(lhs, rhs)),
+            Rule::cdot | Rule::times | Rule::implicit_times => {
+                Box::new(MultiplicationExpression::new(lhs, rhs))
+            }
+            Rule::slash | Rule::div => Box::new(DivisionExpression::new(lhs, rhs)),
+            Rule::modulo => Box::new(ModuloExpression::new(lhs, rhs)),
+            Rule::carat => Box::new(PowerExpression::new(lhs, rhs)),
+            Rule::equal => chain_relation(lhs, Relation::Equal, rhs),
+            Rule::not_equal => chain_relation(lhs, Relation::NotEqual, rhs),
+            Rule::less => chain_relation(lhs, Relation::Less, rhs),
+            Rule::less_equal => chain_relation(lhs, Relation::LessEqual, rhs),
+            Rule::greater => chain_relation(lhs, Relation::Greater, rhs),
+            Rule::greater_equal => chain_relation(lhs, Relation::GreaterEqual, rhs),
+            Rule::bit_and => Box::new(FunctionExpression::new(Function::BitAnd, vec![lhs, rhs])),
+            Rule::bit_or => Box::new(FunctionExpression::new(Function::BitOr, vec![lhs, rhs])),
+            Rule::bit_xor => Box::new(FunctionExpression::new(Function::BitXor, vec![lhs, rhs])),
+            Rule::shift_left => {
+                Box::new(FunctionExpression::new(Function::ShiftLeft, vec![lhs, rhs]))
+            }
+            Rule::shift_right => Box::new(FunctionExpression::new(
+                Function::ShiftRight,
+                vec![lhs, rhs],
+            )),
+            Rule::custom_operator => self.custom(&op, Fixity::Infix)?.apply(lhs, rhs),
             rule => unreachable!("Unexpected rule: {:?}", rule),
-        })
-        .map_prefix(|op, rhs| {
-            let rhs = Box::new(rhs);
-            match op.as_rule() {
-                rule => unreachable!("Unexpected rule: {:?}", rule),
+        };
+        node(
+            expression,
+            lhs_height.max(rhs_height) + 1,
+            span,
+            op.as_span().start(),
+            self.options,
+        )
+    }
+
+    /// Build a prefix operation from its operand
+    fn prefix(&self, op: Pair<'_, Rule>, (rhs, height): (Box<dyn Expression>, usize)) -> Built {
+        let span = op.as_span().start()..rhs.get_span().map_or(op.as_span().end(), |span| span.end);
+        let position = op.as_span().start();
+        let mut height = height;
+        let mut rhs = rhs;
+        let expression: Box<dyn Expression> = match op.as_rule() {
+            Rule::negate => Box::new(NegationExpression::new(rhs)),
+            Rule::custom_prefix => self.custom(&op, Fixity::Prefix)?.apply_unary(rhs),
+            rule @ (Rule::summation | Rule::product) => {
+                let mut inner = op.into_inner();
+                let index = variable_name(&inner.next().unwrap());
+                bind_variable(&mut rhs, &index);
+                let (lower, lower_height) = build(
+                    inner.next().unwrap().into_inner(),
+                    self.options,
+                    self.groups,
+                )?;
+                let (upper, upper_height) = self.primary(inner.next().unwrap())?;
+                height = height.max(lower_height).max(upper_height);
+                match rule {
+                    Rule::summation => Box::new(SummationExpression::new(index, lower, upper, rhs)),
+                    _ => Box::new(ProductExpression::new(index, lower, upper, rhs)),
+                }
             }
-        })
-        .map_postfix(|lhs, op| {
-            let lhs = Box::new(lhs);
-            match op.as_rule() {
-                rule => unreachable!("Unexpected rule: {:?}", rule),
+            Rule::limit => {
+                let mut inner = op.into_inner();
+                let variable = variable_name(&inner.next().unwrap());
+                bind_variable(&mut rhs, &variable);
+                let target = inner.next().unwrap();
+                let target = match target.as_rule() {
+                    Rule::positive_infinity => LimitTarget::PositiveInfinity,
+                    Rule::negative_infinity => LimitTarget::NegativeInfinity,
+                    _ => {
+                        let (point, point_height) =
+                            build(target.into_inner(), self.options, self.groups)?;
+                        height = height.max(point_height);
+                        LimitTarget::Finite(point)
+                    }
+                };
+                let direction = match inner.next().map(|side| side.as_rule()) {
+                    Some(Rule::approach_above) => LimitDirection::Above,
+                    Some(Rule::approach_below) => LimitDirection::Below,
+                    _ => LimitDirection::Both,
+                };
+                Box::new(LimitExpression::new(variable, target, rhs).with_direction(direction))
             }
-        })
-        .parse(pairs)
+            Rule::derivative => {
+                let mut order = None;
+                let mut variable = String::new();
+                for pair in op.into_inner() {
+                    match pair.as_rule() {
+                        Rule::derivative_order => order = order.or(Some(derivative_order(pair)?)),
+                        _ => variable = variable_name(&pair),
+                    }
+                }
+                bind_variable(&mut rhs, &variable);
+                Box::new(DerivativeExpression::new(rhs, variable).with_order(order.unwrap_or(1)))
+            }
+            Rule::bit_not => Box::new(FunctionExpression::new(Function::BitNot, vec![rhs])),
+            rule => unreachable!("Unexpected rule: {:?}", rule),
+        };
+        node(expression, height + 1, span, position, self.options)
+    }
+
+    /// Build a postfix operation from its operand
+    fn postfix(&self, (lhs, height): (Box<dyn Expression>, usize), op: Pair<'_, Rule>) -> Built {
+        let span = lhs
+            .get_span()
+            .map_or(op.as_span().start(), |span| span.start)..op.as_span().end();
+        let expression: Box<dyn Expression> = match op.as_rule() {
+            Rule::factorial => Box::new(FactorialExpression::new(lhs)),
+            Rule::custom_postfix => self.custom(&op, Fixity::Postfix)?.apply_unary(lhs),
+            Rule::degree => {
+                // Degrees are converted to radians, so trig functions get exact angles
+                let radians = ConstantValue::new(
+                    RationalValue::new(Sign::Positive, 1_u32, 180_u32),
+                    Constant::pi(),
+                );
+                Box::new(MultiplicationExpression::new(
+                    lhs,
+                    Box::new(ValueExpression::new(Box::new(radians))),
+                ))
+            }
+            Rule::superscript => {
+                // Superscript digits are an integer exponent
+                let exponent = op
+                    .as_str()
+                    .chars()
+                    .map(|digit| match digit {
+                        '⁻' => '-',
+                        '¹' => '1',
+                        '²' => '2',
+                        '³' => '3',
+                        digit => char::from_u32(u32::from(digit) - 0x2070 + u32::from('0'))
+                            .expect("Unexpected error: a superscript digit isn't a digit!"),
+                    })
+                    .collect::<String>()
+                    .parse::<RationalValue>()
+                    .unwrap();
+                Box::new(PowerExpression::new(
+                    lhs,
+                    Box::new(ValueExpression::new(Box::new(exponent))),
+                ))
+            }
+            rule => unreachable!("Unexpected rule: {:?}", rule),
+        };
+        node(
+            expression,
+            height + 1,
+            span,
+            op.as_span().start(),
+            self.options,
+        )
+    }
+}
+
+/// Get the order of a derivative from its exponent
+fn derivative_order(pair: Pair<Rule>) -> Result<u32, ParseError> {
+    let digits = pair.into_inner().next().unwrap();
+    digits.as_str().parse().map_err(|_| {
+        ParseError::Syntax(Box::new(pest::error::Error::new_from_span(
+            pest::error::ErrorVariant::CustomError {
+                message: "The order of a derivative must be a natural number".to_string(),
+            },
+            digits.as_span(),
+        )))
+    })
+}
+
+/// Get the value of a number pair, leaving out any separators between groups of digits
+fn number_value(pair: &Pair<Rule>) -> RationalValue {
+    pair.as_str()
+        .replace("{,}", "")
+        .replace("\\,", "")
+        .replace('\'', "")
+        .replace("{.}", ".")
+        .parse()
+        .unwrap()
+}
+
+/// Get the value of a Unicode vulgar fraction, like `½`
+fn vulgar_fraction(fraction: &str) -> RationalValue {
+    let (numerator, denominator) = match fraction {
+        "½" => (1_u32, 2_u32),
+        "⅓" => (1, 3),
+        "⅔" => (2, 3),
+        "¼" => (1, 4),
+        "¾" => (3, 4),
+        "⅕" => (1, 5),
+        "⅖" => (2, 5),
+        "⅗" => (3, 5),
+        "⅘" => (4, 5),
+        "⅙" => (1, 6),
+        "⅚" => (5, 6),
+        "⅐" => (1, 7),
+        "⅛" => (1, 8),
+        "⅜" => (3, 8),
+        "⅝" => (5, 8),
+        "⅞" => (7, 8),
+        "⅑" => (1, 9),
+        _ => (1, 10),
+    };
+    RationalValue::new(Sign::Positive, numerator, denominator)
+}
+
+/// Get the canonical name of a variable pair
+fn variable_name(pair: &Pair<Rule>) -> String {
+    VariableExpression::canonical_name(pair.as_str())
+}
+
+/// Get the byte range of a pair in the input
+fn range(pair: &Pair<Rule>) -> Range<usize> {
+    pair.as_span().start()..pair.as_span().end()
+}
+
+/// Get the level the right operand of an operator stops at, which is past its own level if it is
+/// left associative
+fn next_level(precedence: Precedence) -> u8 {
+    match precedence.get_associativity() {
+        Associativity::Left => precedence.get_level().saturating_add(1),
+        Associativity::Right => precedence.get_level(),
+    }
+}
+
+/// Get the byte range from the start of one expression to the end of another, if both were parsed
+fn covering(lhs: &dyn Expression, rhs: &dyn Expression) -> Option<Range<usize>> {
+    Some(lhs.get_span()?.start..rhs.get_span()?.end)
+}
+
+/// Build a relation between two expressions, spanning both of them
+fn spanned_relation(
+    lhs: Box<dyn Expression>,
+    relation: Relation,
+    rhs: Box<dyn Expression>,
+) -> Box<dyn Expression> {
+    let span = covering(lhs.as_ref(), rhs.as_ref());
+    let mut expression = RelationExpression::new(lhs, relation, rhs);
+    if let Some(span) = span {
+        expression.set_span(span);
+    }
+    Box::new(expression)
+}
+
+/// Parse a name into a registered or physical constant, or a variable otherwise
+pub(crate) fn parse_name(name: &str, options: &ParserOptions) -> Box<dyn Expression> {
+    let name = &VariableExpression::canonical_name(name);
+    if let Some(constant) =
+        Constant::lookup(name).filter(|constant| !constant.is_greek() || options.greek_constants)
+    {
+        Box::new(ConstantExpression::new(constant))
+    } else if let Some(constant) =
+        PhysicalConstant::lookup(name).filter(|_| options.physical_constants)
+    {
+        Box::new(ValueExpression::new(Box::new(constant.value())))
+    } else {
+        Box::new(VariableExpression::new(name))
+    }
+}
+
+/// Turn imaginary units back into the variable `i` in an expression binding `i`, like the body
+/// of `\sum_{i=1}^{n} i`
+fn bind_variable(expression: &mut Box<dyn Expression>, variable: &str) {
+    if variable != "i" {
+        return;
+    }
+    let imaginary_unit = expression
+        .downcast_ref::<ValueExpression>()
+        .and_then(|value| value.get_value().downcast_ref::<ComplexValue>())
+        .is_some_and(ComplexValue::is_imaginary_unit);
+    if imaginary_unit {
+        let mut index: Box<dyn Expression> = Box::new(VariableExpression::new(variable));
+        if let Some(span) = expression.get_span() {
+            index.set_span(span);
+        }
+        *expression = index;
+        return;
+    }
+    for child in expression.get_children_mut() {
+        bind_variable(child, variable);
+    }
+}
+
+/// Build a relation, turning chains like `1 < x \le 5` into a conjunction of relations
+fn chain_relation(
+    lhs: Box<dyn Expression>,
+    relation: Relation,
+    rhs: Box<dyn Expression>,
+) -> Box<dyn Expression> {
+    if let Some(previous) = lhs.downcast_ref::<RelationExpression>() {
+        let middle = dyn_clone::clone_box(previous.get_rhs());
+        Box::new(ConjunctionExpression::new(vec![
+            lhs,
+            spanned_relation(middle, relation, rhs),
+        ]))
+    } else if let Some(previous) = lhs.downcast_ref::<ConjunctionExpression>() {
+        let mut operands = previous.get_operands().to_vec();
+        let middle = operands
+            .last()
+            .and_then(|last| last.downcast_ref::<RelationExpression>())
+            .map(|last| dyn_clone::clone_box(last.get_rhs()));
+        match middle {
+            Some(middle) => {
+                operands.push(spanned_relation(middle, relation, rhs));
+                Box::new(ConjunctionExpression::new(operands))
+            }
+            None => spanned_relation(lhs, relation, rhs),
+        }
+    } else {
+        spanned_relation(lhs, relation, rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::{AdditionExpression, DivisionExpression, ValueExpression},
+        format::{FormatOptions, FractionStyle},
+        operator::{CustomOperator, Operator, Precedence},
+        value::RationalValue,
+    };
+
+    use super::{parse, FunctionArgument, ImplicitMultiplication, ParseError, ParserOptions};
+
+    #[test]
+    fn limits() {
+        let options = ParserOptions::default().with_max_depth(16);
+        let nested = format!("{}1{}", "(".repeat(16), ")".repeat(16));
+        assert!(parse(&nested, &options).is_ok());
+
+        // Deep nesting is rejected before it can overflow the stack
+        let nested = format!("{}1{}", "(".repeat(100_000), ")".repeat(100_000));
+        assert!(matches!(
+            parse(&nested, &options.with_max_length(usize::MAX)),
+            Err(ParseError::TooDeep { position: 16, .. })
+        ));
+        assert!(matches!(
+            parse(&nested, &options),
+            Err(ParseError::TooLong { .. })
+        ));
+
+        // Long chains of operators build deep trees too
+        let chain = vec!["1"; 32].join(" + ");
+        assert!(matches!(
+            parse(&chain, &options),
+            Err(ParseError::TooDeep { .. })
+        ));
+        assert!(matches!(
+            parse("1 + )", &options),
+            Err(ParseError::Syntax(_))
+        ));
+    }
+
+    #[test]
+    fn spans() {
+        let expression = parse("2x + \\arcsin{x / 2}", &ParserOptions::default()).unwrap();
+        assert_eq!(expression.get_span(), Some(0..19));
+        assert_eq!(expression.node_count(), 8);
+        let addition = expression.downcast_ref::<AdditionExpression>().unwrap();
+        assert_eq!(addition.get_lhs().get_span(), Some(0..2));
+        assert_eq!(addition.get_rhs().get_span(), Some(5..19));
+    }
+
+    #[test]
+    fn implicit_multiplication() {
+        let strong = ParserOptions::default();
+        let weak = strong
+            .with_implicit_multiplication(ImplicitMultiplication::Weak)
+            .with_function_argument(FunctionArgument::Factor);
+        let parsed =
+            |input: &str, options: &ParserOptions| parse(input, options).unwrap().to_string();
+        assert_eq!(parsed("1/2x", &strong), "(1 / (2 * x))");
+        assert_eq!(parsed("1/2x", &weak), "((1 / 2) * x)");
+        assert_eq!(parsed("\\arcsin 2x", &strong), "\\arcsin{(2 * x)}");
+        assert_eq!(parsed("\\arcsin 2x", &weak), "(\\arcsin{2} * x)");
+        assert_eq!(parsed("\\sin x", &strong), "\\sin{x}");
+        assert_eq!(parsed("\\sin x", &weak), "\\sin{x}");
+        assert_eq!(parsed("\\sin 2x", &strong), "\\sin{(2 * x)}");
+        assert_eq!(parsed("\\sin 2x", &weak), "(\\sin{2} * x)");
+
+        // A bare argument stops at the next function
+        assert_eq!(parsed("\\sin x \\cos x", &strong), "(\\sin{x} * \\cos{x})");
+        assert_eq!(parsed("\\sin x \\cos x", &weak), "(\\sin{x} * \\cos{x})");
+        assert_eq!(parsed("(x + 1) (x - 1)", &strong), "((x + 1) * (x - 1))");
+    }
+
+    #[test]
+    fn function_names() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap().to_string();
+        assert_eq!(parsed("\\sin x"), "\\sin{x}");
+        assert_eq!(parsed("\\cos x"), "\\cos{x}");
+        assert_eq!(parsed("\\tan t"), "\\tan{t}");
+        assert_eq!(parsed("\\sin\\theta"), "\\sin{\\theta}");
+        assert_eq!(parsed("\\sinh x"), "\\sinh{x}");
+        assert_eq!(parsed("\\tanh u"), "\\tanh{u}");
+        assert_eq!(parsed("\\ln x"), "\\ln{x}");
+        assert_eq!(parsed("\\log x"), "\\log{x}");
+        assert_eq!(parsed("\\log _ { 2 } x"), "\\log_{2}{x}");
+        assert_eq!(parsed("\\arcsin x"), "\\arcsin{x}");
+        assert_eq!(parsed("\\arcsinh"), "\\arcsinh");
+        assert_eq!(parsed("\\arctanx"), "\\arctanx");
+    }
+
+    #[test]
+    fn delimiters() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap();
+        assert_eq!(
+            parsed("\\left( \\frac{1}{2} + [3 - 1] \\right) \\cdot \\{2\\}")
+                .simplified()
+                .to_string(),
+            "5"
+        );
+        assert_eq!(
+            parsed("\\Bigl[ x \\bigl(x + 1\\bigr) \\Bigr]").to_string(),
+            "(x * (x + 1))"
+        );
+        assert_eq!(parsed("2 \\left[ x \\right]").get_span(), Some(0..18));
+        assert!(parse("(1 + 2]", &options).is_err());
+    }
+
+    #[test]
+    fn percentages() {
+        let options = ParserOptions::default();
+        let simplified = |input: &str| parse(input, &options).unwrap().simplified().to_string();
+        assert_eq!(simplified("120 \\cdot 15\\%"), "18");
+        assert_eq!(simplified("50%"), "1/2");
+        assert_eq!(simplified("12.5\\% x"), "(x / 8)");
+    }
+
+    #[test]
+    fn degrees() {
+        let options = ParserOptions::default();
+        let simplified = |input: &str| parse(input, &options).unwrap().simplified().to_string();
+        assert_eq!(simplified("\\sin(30^\\circ)"), "1/2");
+        assert_eq!(simplified("\\cos{60^{\\circ}} + \\tan(45^\\circ)"), "3/2");
+        assert_eq!(simplified("2^3"), "8");
+    }
+
+    #[test]
+    fn scientific_notation() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap();
+        assert_eq!(parsed("1.5e10").to_string(), "15000000000");
+        assert_eq!(parsed("2.5E-3").to_string(), "1/400");
+        assert_eq!(parsed("3 \\times 10^{8}").to_string(), "300000000");
+        assert_eq!(parsed("1.6 \\cdot 10^{-19}").get_span(), Some(0..18));
+        assert_eq!(parsed("x / 2 \\times 10^3").to_string(), "(x / 2000)");
+        assert_eq!(parsed("2e").to_string(), "(2 * e)");
+        assert!(matches!(
+            parse("1e99999", &options),
+            Err(ParseError::Syntax(_))
+        ));
+    }
+
+    #[test]
+    fn radix_numbers() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap().to_string();
+        assert_eq!(parsed("0b101"), "5");
+        assert_eq!(parsed("0o17"), "15");
+        assert_eq!(parsed("0x1F + 0xff"), "(31 + 255)");
+        assert_eq!(parsed("2 \\cdot 0x10"), "(2 * 16)");
+
+        // Hex digits after `0x` are part of the literal, while other letters are implied products
+        assert_eq!(parsed("0xa"), "10");
+        assert_eq!(parsed("0xy"), "((0 * x) * y)");
+        assert_eq!(parsed("0xag"), "(((0 * x) * a) * g)");
+        assert_eq!(parsed("0x"), "(0 * x)");
+        assert!(parse("0b102", &options).is_err());
+    }
+
+    #[test]
+    fn mixed_numbers() {
+        let options = ParserOptions::default();
+        let simplified = |input: &str| parse(input, &options).unwrap().simplified().to_string();
+        assert_eq!(simplified("1\\frac{1}{2}"), "3/2");
+        assert_eq!(simplified("-2\\tfrac{3}{4} + 1"), "-7/4");
+        assert_eq!(simplified("2 \\frac{1}{2}"), "1");
+        assert_eq!(simplified("2\\frac{x}{4}"), "(x / 2)");
+    }
+
+    #[test]
+    fn spacing_commands() {
+        let options = ParserOptions::default();
+        let simplified = |input: &str| parse(input, &options).unwrap().simplified().to_string();
+        assert_eq!(simplified("2\\,x \\;+\\quad 1"), "((2 * x) + 1)");
+        assert_eq!(simplified("3\\!\\cdot\\:4~-\\ 2"), "10");
+        assert_eq!(simplified("\\qquad\\frac{1}{2}\n\t+ \\frac{1}{2}\\,"), "1");
+        assert_eq!(simplified("\\int_0^1 x \\, dx"), "\\int_{0}^{1}{x} \\, dx");
+    }
+
+    #[test]
+    fn unicode_symbols() {
+        let options = ParserOptions::default();
+        let simplified = |input: &str| parse(input, &options).unwrap().simplified().to_string();
+        assert_eq!(simplified("6 × 2 ÷ 3 − 1 · 2"), "2");
+        assert_eq!(simplified("3² + 2³ − 2⁻¹"), "33/2");
+        assert_eq!(simplified("√16 + ∛27 + √(8 + 1)"), "10");
+        assert_eq!(simplified("½ + ¾ + 2½"), "15/4");
+        assert_eq!(simplified("2π − π"), "\\pi");
+        assert_eq!(simplified("α ≤ 2x²"), "\\alpha \\le (2 * (x ^ 2))");
+        assert_eq!(simplified("−∞ ≠ ∞"), "true");
+        assert_eq!(parse("x²", &options).unwrap().get_span(), Some(0..3));
+    }
+
+    #[test]
+    fn fractions() {
+        let options = ParserOptions::default();
+        let parsed = |input: &str| parse(input, &options).unwrap();
+        assert_eq!(
+            parsed("\\frac{1}{2} + \\frac{3}{4}")
+                .simplified()
+                .to_string(),
+            "5/4"
+        );
+        assert_eq!(
+            parsed("\\frac{\\frac{x}{2}}{3 + y}").to_string(),
+            "((x / 2) / (3 + y))"
+        );
+        assert_eq!(parsed("\\dfrac{1}{2}").get_span(), Some(0..12));
+
+        // Fractions written in the LaTeX style parse back to the same expression
+        let expression = parsed("x / (1 + y)");
+        let written =
+            expression.format(&FormatOptions::default().with_fraction_style(FractionStyle::Frac));
+        assert_eq!(parsed(&written).to_string(), expression.to_string());
+    }
+
+    #[test]
+    fn precedence() {
+        CustomOperator::register("avg", Precedence::right(6), |lhs, rhs| {
+            let two = Box::new(ValueExpression::new(Box::new(
+                "2".parse::<RationalValue>().unwrap(),
+            )));
+            Box::new(DivisionExpression::new(
+                Box::new(AdditionExpression::new(lhs, rhs)),
+                two,
+            ))
+        });
+        let options = ParserOptions::default();
+        let simplified =
+            |input: &str, options: &ParserOptions| parse(input, options).unwrap().simplified();
+
+        // Right associative operators group from the right
+        assert_eq!(
+            simplified("1 \\mathbin{avg} 2 \\mathbin{avg} 3", &options).to_string(),
+            "7/4"
+        );
+        assert_eq!(
+            simplified("1 \\mathbin{avg} 2 \\cdot 3", &options).to_string(),
+            "7/2"
+        );
+        assert!(matches!(
+            parse("1 \\mathbin{mean} 2", &options),
+            Err(ParseError::UnknownOperator { position: 2, .. })
+        ));
+
+        // Operators can be written with symbols, before, between or after their operands
+        CustomOperator::register("⊕", Precedence::left(6), |lhs, rhs| {
+            Box::new(AdditionExpression::new(lhs, rhs))
+        });
+        CustomOperator::register_prefix("%%", Precedence::left(9), |operand| {
+            Box::new(DivisionExpression::new(operand.clone(), operand))
+        });
+        CustomOperator::register_postfix("♯", Precedence::left(11), |operand| {
+            Box::new(AdditionExpression::new(operand.clone(), operand))
+        });
+        assert_eq!(simplified("2 ⊕ 3♯ \\cdot 2", &options).to_string(), "14");
+        assert_eq!(simplified("%%3 + 2♯", &options).to_string(), "5");
+        assert_eq!(
+            parse("x♯ + %%y", &options).unwrap().to_string(),
+            "((x + x) + (y / y))"
+        );
+        let error = parse("1 ⊖ 2", &options).unwrap_err();
+        assert_eq!(error.get_span(), 2..5);
+
+        // Built-in operators can be rearranged
+        assert_eq!(simplified("1 + 2 \\cdot 3", &options).to_string(), "7");
+        assert_eq!(
+            simplified("12 \\div 4 \\times 3", &options).to_string(),
+            "9"
+        );
+        let options = options.with_precedence(Operator::Additive, Precedence::left(8));
+        assert_eq!(simplified("1 + 2 \\cdot 3", &options).to_string(), "9");
+        assert_eq!(
+            ParserOptions::default().get_precedence(Operator::Power),
+            Precedence::right(10)
+        );
+    }
 }
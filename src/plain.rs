@@ -0,0 +1,155 @@
+use crate::{
+    expression::Expression,
+    parse::{ParseError, ParserOptions},
+    translation::Translation,
+};
+
+/// Functions written the same way in plain text and LaTeX, apart from the backslash
+const FUNCTIONS: [(&str, &str); 17] = [
+    ("sin", "\\sin"),
+    ("cos", "\\cos"),
+    ("tan", "\\tan"),
+    ("sinh", "\\sinh"),
+    ("cosh", "\\cosh"),
+    ("tanh", "\\tanh"),
+    ("ln", "\\ln"),
+    ("log", "\\log"),
+    ("arcsin", "\\arcsin"),
+    ("arccos", "\\arccos"),
+    ("arctan", "\\arctan"),
+    ("asin", "\\arcsin"),
+    ("acos", "\\arccos"),
+    ("atan", "\\arctan"),
+    ("gamma", "\\Gamma"),
+    ("erf", "\\operatorname{erf}"),
+    ("mod", " \\bmod "),
+];
+
+/// Functions whose bracketed argument turns into LaTeX delimiters, as the text written for the
+/// name, the opening bracket and the closing bracket
+const DELIMITED_FUNCTIONS: [(&str, [&str; 3]); 5] = [
+    ("sqrt", ["\\sqrt", "{", "}"]),
+    ("abs", ["\\left", "|", "\\right|"]),
+    ("floor", ["\\left", "\\lfloor ", "\\right\\rfloor "]),
+    ("ceil", ["\\left", "\\lceil ", "\\right\\rceil "]),
+    ("exp", ["\\left(e^", "{", "}\\right)"]),
+];
+
+/// Named constants and Greek letters, which are written as LaTeX commands
+const NAMES: [&str; 19] = [
+    "pi", "alpha", "beta", "gamma", "delta", "epsilon", "zeta", "eta", "theta", "iota", "kappa",
+    "lambda", "mu", "nu", "xi", "rho", "sigma", "tau", "phi",
+];
+
+/// Operators written with more than one character in plain text
+const OPERATORS: [(&str, &str); 4] = [
+    ("**", "^"),
+    ("<=", "\\le "),
+    (">=", "\\ge "),
+    ("!=", "\\ne "),
+];
+
+/// Translate plain calculator syntax into LaTeX
+fn translate(input: &str) -> Translation {
+    let mut translation = Translation::new();
+    let mut closing = Vec::new();
+    let mut rest = input;
+    while let Some(character) = rest.chars().next() {
+        let position = input.len() - rest.len();
+        if character.is_ascii_alphabetic() {
+            // Translate whole words, leaving other letters as single letter variables
+            let length = rest
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(rest.len());
+            let (word, after) = rest.split_at(length);
+            let delimited = DELIMITED_FUNCTIONS
+                .iter()
+                .find(|(name, _)| *name == word)
+                .filter(|_| after.starts_with('('));
+            if let Some((_, [name, open, close])) = delimited {
+                translation.push(name, position);
+                translation.push(open, position + length);
+                closing.push(*close);
+                rest = &after[1..];
+                continue;
+            }
+            if let Some((_, latex)) = FUNCTIONS.iter().find(|(name, _)| *name == word) {
+                translation.push(latex, position);
+            } else if NAMES.contains(&word) {
+                translation.push(&format!("\\{}", word), position);
+            } else {
+                translation.push(word, position);
+            }
+            rest = after;
+            continue;
+        }
+        if let Some((operator, latex)) = OPERATORS
+            .iter()
+            .find(|(operator, _)| rest.starts_with(operator))
+        {
+            translation.push(latex, position);
+            rest = &rest[operator.len()..];
+            continue;
+        }
+        match character {
+            '(' => {
+                translation.push("(", position);
+                closing.push(")");
+            }
+            ')' => translation.push(closing.pop().unwrap_or(")"), position),
+            _ => translation.push(&rest[..character.len_utf8()], position),
+        }
+        rest = &rest[character.len_utf8()..];
+    }
+    translation
+}
+
+/// Parse an expression written in plain calculator syntax, like `2*(3+4)^2 / 5` or `sqrt(x)`
+///
+/// This gives the same expressions as parsing LaTeX, with spans into the plain text
+pub fn parse_plain(
+    input: &str,
+    options: &ParserOptions,
+) -> Result<Box<dyn Expression>, ParseError> {
+    translate(input).parse(input, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{parse_plain, ParserOptions};
+
+    #[test]
+    fn plain_text() {
+        let options = ParserOptions::default();
+        let simplified = |input: &str| {
+            parse_plain(input, &options)
+                .unwrap()
+                .simplified()
+                .to_string()
+        };
+        assert_eq!(simplified("2*(3+4)^2 / 5"), "98/5");
+        assert_eq!(simplified("sqrt(16) + abs(0 - 3) + sin(0) + 2**3"), "15");
+        assert_eq!(simplified("floor(7/2) + ceil(1/3) + 17 mod 5"), "6");
+        assert_eq!(simplified("2pi - pi"), "\\pi");
+        assert_eq!(simplified("x <= 3"), "x \\le 3");
+        assert_eq!(simplified("0b101 + 0o17 + 0x1F"), "51");
+        assert_eq!(parse_plain("0xa", &options).unwrap().to_string(), "10");
+        assert_eq!(
+            parse_plain("0xy", &options).unwrap().to_string(),
+            "((0 * x) * y)"
+        );
+        assert_eq!(
+            parse_plain("exp(x)^2", &options).unwrap().to_string(),
+            "((e ^ x) ^ 2)"
+        );
+
+        // Spans point into the plain text
+        let expression = parse_plain("1 + sqrt(x)", &options).unwrap();
+        assert_eq!(expression.get_span(), Some(0..11));
+        assert_eq!(expression.get_children()[1].get_span(), Some(4..11));
+
+        let error = parse_plain("sqrt(4) + * 2", &options).unwrap_err();
+        assert_eq!(error.get_span(), 10..10);
+        assert_eq!(error.get_token(), Some('*'));
+    }
+}
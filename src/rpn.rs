@@ -0,0 +1,240 @@
+use std::ops::Range;
+
+use crate::{
+    expression::{
+        AbsExpression, AdditionExpression, ConstantExpression, DivisionExpression, Expression,
+        FactorialExpression, Function, FunctionExpression, ModuloExpression,
+        MultiplicationExpression, NegationExpression, PowerExpression, RootExpression,
+        SubtractionExpression, ValueExpression,
+    },
+    parse::{parse_name, ParseError, ParserOptions},
+    value::{ComplexValue, Constant, InfinityValue, RationalValue},
+};
+
+/// Functions of one argument, as their name in postfix notation
+const FUNCTIONS: [(&str, Function); 15] = [
+    ("sin", Function::Sin),
+    ("cos", Function::Cos),
+    ("tan", Function::Tan),
+    ("sinh", Function::Sinh),
+    ("cosh", Function::Cosh),
+    ("tanh", Function::Tanh),
+    ("asin", Function::Arcsin),
+    ("acos", Function::Arccos),
+    ("atan", Function::Arctan),
+    ("ln", Function::Ln),
+    ("log", Function::Log),
+    ("gamma", Function::Gamma),
+    ("erf", Function::Erf),
+    ("floor", Function::Floor),
+    ("ceil", Function::Ceil),
+];
+
+/// Operators taking two operands off the stack
+const BINARY_OPERATORS: [&str; 7] = ["+", "-", "*", "/", "^", "%", "mod"];
+
+/// Operators taking one operand off the stack, apart from functions
+const UNARY_OPERATORS: [&str; 5] = ["neg", "!", "sqrt", "abs", "exp"];
+
+/// Get the number of operands an operator takes off the stack, or `None` if it isn't an operator
+fn arity(token: &str) -> Option<usize> {
+    if BINARY_OPERATORS.contains(&token) {
+        Some(2)
+    } else if UNARY_OPERATORS.contains(&token) || FUNCTIONS.iter().any(|(name, _)| *name == token) {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+/// Apply an operator to the operands taken off the stack, in the order they were pushed
+fn apply(token: &str, mut operands: Vec<Box<dyn Expression>>) -> Box<dyn Expression> {
+    let rhs = operands.pop().unwrap();
+    if let Some(lhs) = operands.pop() {
+        return match token {
+            "+" => Box::new(AdditionExpression::new(lhs, rhs)),
+            "-" => Box::new(SubtractionExpression::new(lhs, rhs)),
+            "*" => Box::new(MultiplicationExpression::new(lhs, rhs)),
+            "/" => Box::new(DivisionExpression::new(lhs, rhs)),
+            "^" => Box::new(PowerExpression::new(lhs, rhs)),
+            _ => Box::new(ModuloExpression::new(lhs, rhs)),
+        };
+    }
+    match token {
+        "neg" => Box::new(NegationExpression::new(rhs)),
+        "!" => Box::new(FactorialExpression::new(rhs)),
+        "sqrt" => Box::new(RootExpression::square(rhs)),
+        "abs" => Box::new(AbsExpression::new(rhs)),
+        "exp" => Box::new(PowerExpression::new(
+            Box::new(ConstantExpression::new(Constant::e())),
+            rhs,
+        )),
+        // Logarithms without a base are base ten, like in LaTeX
+        "log" => Box::new(FunctionExpression::new(
+            Function::Log,
+            vec![
+                Box::new(ValueExpression::new(Box::new(RationalValue::from(
+                    num::BigInt::from(10),
+                )))),
+                rhs,
+            ],
+        )),
+        _ => {
+            let (_, function) = FUNCTIONS.iter().find(|(name, _)| *name == token).unwrap();
+            Box::new(FunctionExpression::new(*function, vec![rhs]))
+        }
+    }
+}
+
+/// Parse an operand token into a number, constant or variable
+fn operand(token: &str, options: &ParserOptions) -> Option<Box<dyn Expression>> {
+    let digits = token.strip_prefix('-').unwrap_or(token);
+    if digits.starts_with(|c: char| c.is_ascii_digit()) {
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, "0"));
+        let numeric =
+            |digits: &str| !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit());
+        if !numeric(integer) || !numeric(fraction) {
+            return None;
+        }
+        return Some(Box::new(ValueExpression::new(Box::new(
+            token.parse::<RationalValue>().ok()?,
+        ))));
+    }
+    if !token.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    Some(match token {
+        "pi" => parse_name("\\pi", options),
+        "e" => Box::new(ConstantExpression::new(Constant::e())),
+        "inf" => Box::new(ValueExpression::new(Box::new(InfinityValue::positive()))),
+        "i" if options.is_imaginary_unit() => Box::new(ValueExpression::new(Box::new(
+            ComplexValue::imaginary_unit(),
+        ))),
+        name => parse_name(name, options),
+    })
+}
+
+/// Split an input into whitespace separated tokens, with their byte ranges
+fn tokens(input: &str) -> Vec<(&str, Range<usize>)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (position, character) in input.char_indices().chain([(input.len(), ' ')]) {
+        match (start, character.is_whitespace()) {
+            (None, false) => start = Some(position),
+            (Some(first), true) => {
+                tokens.push((&input[first..position], first..position));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    tokens
+}
+
+/// Parse an expression written in postfix notation, like `3 4 + 2 *`
+///
+/// Tokens are separated by whitespace, and each operator or function takes its operands off the
+/// top of the stack. This gives the same expressions as parsing LaTeX, with spans into the input
+pub fn parse_rpn(input: &str, options: &ParserOptions) -> Result<Box<dyn Expression>, ParseError> {
+    if input.len() > options.get_max_length() {
+        return Err(ParseError::TooLong {
+            length: input.len(),
+            max_length: options.get_max_length(),
+        });
+    }
+
+    // Keep each expression on the stack with its height, like the LaTeX parser
+    let mut stack: Vec<(Box<dyn Expression>, usize)> = Vec::new();
+    for (token, span) in tokens(input) {
+        let (expression, height) = match arity(token) {
+            Some(count) => {
+                if stack.len() < count {
+                    return Err(ParseError::custom(
+                        input,
+                        span.start,
+                        format!(
+                            "`{}` needs {} operands, but the stack has {}",
+                            token,
+                            count,
+                            stack.len()
+                        ),
+                    ));
+                }
+                let (operands, heights): (Vec<_>, Vec<_>) =
+                    stack.split_off(stack.len() - count).into_iter().unzip();
+                let start = operands[0].get_span().map_or(span.start, |span| span.start);
+                let mut expression = apply(token, operands);
+                expression.set_span(start..span.end);
+                (expression, heights.into_iter().max().unwrap() + 1)
+            }
+            None => {
+                let mut expression = operand(token, options).ok_or_else(|| {
+                    ParseError::custom(input, span.start, format!("unknown token `{}`", token))
+                })?;
+                expression.set_span(span.clone());
+                (expression, 1)
+            }
+        };
+        if height > options.get_max_depth() {
+            return Err(ParseError::TooDeep {
+                position: span.start,
+                max_depth: options.get_max_depth(),
+            });
+        }
+        stack.push((expression, height));
+    }
+
+    // Exactly one expression must be left
+    match stack.len() {
+        1 => Ok(stack.pop().unwrap().0),
+        0 => Err(ParseError::custom(
+            input,
+            input.len(),
+            "expected an expression".to_string(),
+        )),
+        count => Err(ParseError::custom(
+            input,
+            input.len(),
+            format!(
+                "{} expressions are left on the stack, so an operator is missing",
+                count
+            ),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{parse_rpn, ParserOptions};
+
+    #[test]
+    fn postfix() {
+        let options = ParserOptions::default();
+        let simplified = |input: &str| parse_rpn(input, &options).unwrap().simplified().to_string();
+        assert_eq!(simplified("3 4 + 2 *"), "14");
+        assert_eq!(simplified("1 2 3 * + 4 /"), "7/4");
+        assert_eq!(simplified("2 3 ^ 5 mod neg"), "-3");
+        assert_eq!(simplified("16 sqrt 4 ! + 0.5 -"), "55/2");
+        assert_eq!(simplified("2 0 sin + 100 log *"), "4");
+        assert_eq!(
+            parse_rpn("x 1 + pi *", &options).unwrap().to_string(),
+            "((x + 1) * \\pi)"
+        );
+
+        // Spans cover the operands and the operator
+        let expression = parse_rpn("3 4 +  2 *", &options).unwrap();
+        assert_eq!(expression.get_span(), Some(0..10));
+        assert_eq!(expression.get_children()[0].get_span(), Some(0..5));
+        assert_eq!(expression.get_children()[1].get_span(), Some(7..8));
+
+        let error = parse_rpn("3 +", &options).unwrap_err();
+        assert_eq!(error.get_span(), 2..2);
+        assert_eq!(
+            error.get_custom_message(),
+            Some("`+` needs 2 operands, but the stack has 1")
+        );
+        assert_eq!(parse_rpn("3 4", &options).unwrap_err().get_span(), 3..3);
+        assert_eq!(parse_rpn("3 $", &options).unwrap_err().get_span(), 2..2);
+        assert!(parse_rpn("", &options).is_err());
+    }
+}
@@ -0,0 +1,404 @@
+use std::cmp::Ordering;
+
+use num::{BigInt, BigUint, Integer as _, One as _};
+
+use crate::{
+    expression::{
+        AdditionExpression, ConjunctionExpression, DivisionExpression, Expression,
+        MultiplicationExpression, NegationExpression, PowerExpression, Relation,
+        RelationExpression, SubtractionExpression, ValueExpression, VariableExpression,
+    },
+    trace,
+    value::{
+        Bound, IntervalUnionValue, IntervalValue, PolynomialValue, RationalValue, Sign, Value,
+    },
+};
+
+/// The largest power of a polynomial that is expanded into its coefficients
+const MAX_POLYNOMIAL_POWER: u32 = 64;
+
+/// The largest coefficient whose divisors are tried as the parts of a rational root
+const MAX_ROOT_COEFFICIENT: u64 = 1 << 20;
+
+/// Convert the result of rational arithmetic back into a rational value
+fn rational(value: Box<dyn Value>) -> Option<RationalValue> {
+    value.downcast_ref::<RationalValue>().cloned()
+}
+
+/// Get the coefficients `(a, b)` such that an expression equals `a * variable + b`
+///
+/// Returns `None` if the expression is not linear in the variable
+pub fn linear_form(
+    expression: &dyn Expression,
+    variable: &str,
+) -> Option<(RationalValue, RationalValue)> {
+    if let Some(expression) = expression.downcast_ref::<ValueExpression>() {
+        let value = expression.get_value().downcast_ref::<RationalValue>()?;
+        Some((RationalValue::zero(), value.clone()))
+    } else if let Some(expression) = expression.downcast_ref::<VariableExpression>() {
+        if expression.get_name() == variable {
+            Some((RationalValue::one(), RationalValue::zero()))
+        } else {
+            None
+        }
+    } else if let Some(expression) = expression.downcast_ref::<AdditionExpression>() {
+        let (lhs_a, lhs_b) = linear_form(expression.get_lhs(), variable)?;
+        let (rhs_a, rhs_b) = linear_form(expression.get_rhs(), variable)?;
+        Some((rational(lhs_a.add(&rhs_a))?, rational(lhs_b.add(&rhs_b))?))
+    } else if let Some(expression) = expression.downcast_ref::<SubtractionExpression>() {
+        let (lhs_a, lhs_b) = linear_form(expression.get_lhs(), variable)?;
+        let (rhs_a, rhs_b) = linear_form(expression.get_rhs(), variable)?;
+        Some((rational(lhs_a.sub(&rhs_a))?, rational(lhs_b.sub(&rhs_b))?))
+    } else if let Some(expression) = expression.downcast_ref::<NegationExpression>() {
+        let (a, b) = linear_form(expression.get_argument(), variable)?;
+        Some((a.get_opposite(), b.get_opposite()))
+    } else if let Some(expression) = expression.downcast_ref::<MultiplicationExpression>() {
+        let (lhs_a, lhs_b) = linear_form(expression.get_lhs(), variable)?;
+        let (rhs_a, rhs_b) = linear_form(expression.get_rhs(), variable)?;
+
+        // One side must be constant for the product to stay linear
+        if lhs_a.is_zero() {
+            Some((rational(rhs_a.mul(&lhs_b))?, rational(rhs_b.mul(&lhs_b))?))
+        } else if rhs_a.is_zero() {
+            Some((rational(lhs_a.mul(&rhs_b))?, rational(lhs_b.mul(&rhs_b))?))
+        } else {
+            None
+        }
+    } else if let Some(expression) = expression.downcast_ref::<DivisionExpression>() {
+        let (lhs_a, lhs_b) = linear_form(expression.get_lhs(), variable)?;
+        let (rhs_a, rhs_b) = linear_form(expression.get_rhs(), variable)?;
+
+        // Only division by a nonzero constant is linear
+        if rhs_a.is_zero() && !rhs_b.is_zero() {
+            Some((rational(lhs_a.div(&rhs_b))?, rational(lhs_b.div(&rhs_b))?))
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
+
+/// Get an expression as a polynomial in a variable with rational coefficients
+///
+/// Returns `None` if the expression is not a polynomial in the variable
+pub fn polynomial_form(expression: &dyn Expression, variable: &str) -> Option<PolynomialValue> {
+    let polynomial = |value: Box<dyn Value>| value.downcast_ref::<PolynomialValue>().cloned();
+    if let Some(expression) = expression.downcast_ref::<ValueExpression>() {
+        let value = expression.get_value().downcast_ref::<RationalValue>()?;
+        Some(PolynomialValue::constant(variable, value.clone()))
+    } else if let Some(expression) = expression.downcast_ref::<VariableExpression>() {
+        (expression.get_name() == variable).then(|| PolynomialValue::variable(variable))
+    } else if let Some(expression) = expression.downcast_ref::<AdditionExpression>() {
+        let lhs = polynomial_form(expression.get_lhs(), variable)?;
+        polynomial(lhs.add(&polynomial_form(expression.get_rhs(), variable)?))
+    } else if let Some(expression) = expression.downcast_ref::<SubtractionExpression>() {
+        let lhs = polynomial_form(expression.get_lhs(), variable)?;
+        polynomial(lhs.sub(&polynomial_form(expression.get_rhs(), variable)?))
+    } else if let Some(expression) = expression.downcast_ref::<NegationExpression>() {
+        let argument = polynomial_form(expression.get_argument(), variable)?;
+        polynomial(argument.mul(&RationalValue::one().get_opposite()))
+    } else if let Some(expression) = expression.downcast_ref::<MultiplicationExpression>() {
+        let lhs = polynomial_form(expression.get_lhs(), variable)?;
+        polynomial(lhs.mul(&polynomial_form(expression.get_rhs(), variable)?))
+    } else if let Some(expression) = expression.downcast_ref::<DivisionExpression>() {
+        // Only division by a nonzero constant keeps a polynomial
+        let lhs = polynomial_form(expression.get_lhs(), variable)?;
+        let rhs = polynomial_form(expression.get_rhs(), variable)?;
+        match rhs.degree() {
+            Some(0) => polynomial(lhs.div(&rhs.get_coefficients()[0])),
+            _ => None,
+        }
+    } else if let Some(expression) = expression.downcast_ref::<PowerExpression>() {
+        let base = polynomial_form(expression.get_base(), variable)?;
+        let exponent = expression
+            .get_exponent()
+            .downcast_ref::<ValueExpression>()?
+            .get_value()
+            .downcast_ref::<RationalValue>()?
+            .to_integer()?;
+        let exponent = u32::try_from(exponent).ok()?;
+        (exponent <= MAX_POLYNOMIAL_POWER).then(|| base.pow(exponent))
+    } else {
+        None
+    }
+}
+
+/// Solve a relation or conjunction of relations for a variable
+///
+/// Returns `None` if the expression cannot be solved
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip(expression),
+        fields(nodes = expression.node_count())
+    )
+)]
+pub fn solve(expression: &dyn Expression, variable: &str) -> Option<IntervalUnionValue> {
+    if let Some(expression) = expression.downcast_ref::<RelationExpression>() {
+        solve_relation(expression, variable)
+    } else if let Some(expression) = expression.downcast_ref::<ConjunctionExpression>() {
+        let mut solution = IntervalUnionValue::all();
+        for (iteration, operand) in expression.get_operands().iter().enumerate() {
+            solution = solution.intersection(&solve(operand.as_ref(), variable)?);
+            trace::iteration("solve", iteration, || solution.to_string());
+        }
+        Some(solution)
+    } else {
+        None
+    }
+}
+
+/// Solve a single relation for a variable
+fn solve_relation(expression: &RelationExpression, variable: &str) -> Option<IntervalUnionValue> {
+    // Move everything to the left side, giving `a * variable + b` compared to zero
+    let (Some((lhs_a, lhs_b)), Some((rhs_a, rhs_b))) = (
+        linear_form(expression.get_lhs(), variable),
+        linear_form(expression.get_rhs(), variable),
+    ) else {
+        return solve_constant(expression).or_else(|| solve_polynomial(expression, variable));
+    };
+    let a = rational(lhs_a.sub(&rhs_a))?;
+    let b = rational(lhs_b.sub(&rhs_b))?;
+    let relation = expression.get_relation();
+
+    // Without the variable the relation either always or never holds
+    if a.is_zero() {
+        return Some(if relation.holds(b.cmp(&RationalValue::zero())?) {
+            IntervalUnionValue::all()
+        } else {
+            IntervalUnionValue::empty()
+        });
+    }
+
+    // Isolate the variable, flipping the relation when dividing by a negative
+    let boundary = rational(b.get_opposite().div(&a))?;
+    let relation = if a.is_negative() {
+        relation.flipped()
+    } else {
+        relation
+    };
+    let solution = match relation {
+        Relation::Equal => IntervalValue::point(boundary),
+        Relation::NotEqual => return Some(IntervalUnionValue::all().excluding(&boundary)),
+        Relation::Less => IntervalValue::new(Bound::Unbounded, Bound::Open(boundary)),
+        Relation::LessEqual => IntervalValue::new(Bound::Unbounded, Bound::Closed(boundary)),
+        Relation::Greater => IntervalValue::new(Bound::Open(boundary), Bound::Unbounded),
+        Relation::GreaterEqual => IntervalValue::new(Bound::Closed(boundary), Bound::Unbounded),
+    };
+    Some(solution.into())
+}
+
+/// Solve a relation between two sides that simplify to comparable values, like `\pi \ne 0`
+fn solve_constant(expression: &RelationExpression) -> Option<IntervalUnionValue> {
+    let lhs = expression.get_lhs().simplified();
+    let rhs = expression.get_rhs().simplified();
+    let ordering = lhs
+        .downcast_ref::<ValueExpression>()?
+        .get_value()
+        .cmp(rhs.downcast_ref::<ValueExpression>()?.get_value())?;
+    Some(if expression.get_relation().holds(ordering) {
+        IntervalUnionValue::all()
+    } else {
+        IntervalUnionValue::empty()
+    })
+}
+
+/// Solve an equation or inequation between polynomials by finding the roots of their difference
+fn solve_polynomial(expression: &RelationExpression, variable: &str) -> Option<IntervalUnionValue> {
+    let lhs = polynomial_form(expression.get_lhs(), variable)?;
+    let difference = lhs.sub(&polynomial_form(expression.get_rhs(), variable)?);
+    let difference = difference.downcast_ref::<PolynomialValue>()?;
+    let relation = expression.get_relation();
+    if difference.is_zero() {
+        return Some(if relation.holds(Ordering::Equal) {
+            IntervalUnionValue::all()
+        } else {
+            IntervalUnionValue::empty()
+        });
+    }
+    let roots = rational_roots(difference)?;
+    match relation {
+        Relation::Equal => Some(IntervalUnionValue::new(
+            roots.into_iter().map(IntervalValue::point).collect(),
+        )),
+        Relation::NotEqual => Some(
+            roots
+                .iter()
+                .fold(IntervalUnionValue::all(), |solution, root| {
+                    solution.excluding(root)
+                }),
+        ),
+        _ => None,
+    }
+}
+
+/// Get the positive divisors of a natural number, if it is small enough to factor
+fn divisors(number: &BigUint) -> Option<Vec<u64>> {
+    let number = u64::try_from(number)
+        .ok()
+        .filter(|number| *number <= MAX_ROOT_COEFFICIENT)?;
+    Some(
+        (1..)
+            .take_while(|divisor| divisor * divisor <= number)
+            .filter(|divisor| number % divisor == 0)
+            .flat_map(|divisor| [divisor, number / divisor])
+            .collect(),
+    )
+}
+
+/// Find the real roots of a nonzero polynomial
+///
+/// Returns `None` if the polynomial may have a real root that isn't rational
+fn rational_roots(polynomial: &PolynomialValue) -> Option<Vec<RationalValue>> {
+    // Scale the coefficients to integers, so that every rational root is a divisor of the lowest
+    // nonzero coefficient over a divisor of the leading coefficient
+    let scale = polynomial
+        .get_coefficients()
+        .iter()
+        .fold(BigUint::one(), |scale, coefficient| {
+            scale.lcm(coefficient.get_denominator())
+        });
+    let scale = RationalValue::from(BigInt::from(scale));
+    let integers = polynomial
+        .get_coefficients()
+        .iter()
+        .map(|coefficient| Some(rational(coefficient.mul(&scale))?.simplified()))
+        .collect::<Option<Vec<_>>>()?;
+    let lowest = integers.iter().find(|coefficient| !coefficient.is_zero())?;
+    let leading = integers.last()?;
+
+    let mut candidates = vec![RationalValue::zero()];
+    for numerator in divisors(lowest.get_numerator())? {
+        for denominator in divisors(leading.get_numerator())? {
+            for sign in [Sign::Positive, Sign::Negative] {
+                candidates.push(RationalValue::new(sign, numerator, denominator).simplified());
+            }
+        }
+    }
+
+    // Divide out each root, so that what is left has no rational roots
+    let mut remaining = polynomial.clone();
+    let mut roots = Vec::new();
+    for candidate in candidates {
+        if !remaining.evaluate_at(&candidate).is_zero() {
+            continue;
+        }
+        let factor = PolynomialValue::new(
+            polynomial.get_variable(),
+            vec![candidate.get_opposite(), RationalValue::one()],
+        );
+        while remaining.evaluate_at(&candidate).is_zero() {
+            remaining = remaining.div_rem(&factor)?.0;
+        }
+        roots.push(candidate);
+    }
+
+    // What is left has no real roots if it is constant, or a quadratic with a negative discriminant
+    match remaining.get_coefficients() {
+        [_] => Some(roots),
+        [c, b, a] => {
+            let discriminant = rational(
+                rational(b.mul(b))?.sub(
+                    rational(a.mul(c))?
+                        .mul(&RationalValue::from(BigInt::from(4)))
+                        .as_ref(),
+                ),
+            )?;
+            discriminant.is_negative().then_some(roots)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::Bindings,
+        parse::{parse_latex, parse_pairs},
+        value::{BooleanValue, RationalValue, Value},
+    };
+
+    use super::{polynomial_form, solve};
+
+    #[test]
+    fn chained_inequality() {
+        let expression = parse_pairs(parse_latex("1 < x \\le 5").unwrap());
+        assert_eq!(
+            solve(expression.as_ref(), "x").unwrap().to_string(),
+            "(1, 5]"
+        );
+
+        let mut bindings = Bindings::new();
+        bindings.insert(
+            "x".to_string(),
+            Box::new("5".parse::<RationalValue>().unwrap()),
+        );
+        assert!(expression
+            .evaluate(&bindings)
+            .downcast_ref::<BooleanValue>()
+            .unwrap()
+            .get_value());
+    }
+
+    #[test]
+    fn equation() {
+        let expression = parse_pairs(parse_latex("2x + 1 = 5").unwrap());
+        assert_eq!(expression.simplified().to_string(), "((2 * x) + 1) = 5");
+        assert_eq!(
+            solve(expression.as_ref(), "x").unwrap().to_string(),
+            "[2, 2]"
+        );
+
+        // Equations without the variable hold everywhere or nowhere
+        let expression = parse_pairs(parse_latex("x + 1 = x + 2").unwrap());
+        assert!(solve(expression.as_ref(), "x").unwrap().is_empty());
+
+        // Polynomial equations are solved by their rational roots
+        let solved = |input: &str| {
+            solve(parse_pairs(parse_latex(input).unwrap()).as_ref(), "x")
+                .map(|solution| solution.to_string())
+        };
+        assert_eq!(
+            solved("x^2 - x = 2").as_deref(),
+            Some("[-1, -1] \\cup [2, 2]")
+        );
+        assert_eq!(
+            solved("4x^3 \\ne x").as_deref(),
+            Some("(-\\infty, -1/2) \\cup (-1/2, 0) \\cup (0, 1/2) \\cup (1/2, \\infty)")
+        );
+        assert_eq!(solved("(x - 1)^2 = 0").as_deref(), Some("[1, 1]"));
+        assert_eq!(solved("x^2 = 2"), None);
+        assert_eq!(solved("x^2 < 1"), None);
+    }
+
+    #[test]
+    fn flipped_inequality() {
+        let expression = parse_pairs(parse_latex("3 - 2x \\ge 7").unwrap());
+        assert_eq!(
+            solve(expression.as_ref(), "x").unwrap().to_string(),
+            "(-\\infty, -2]"
+        );
+    }
+
+    #[test]
+    fn polynomials() {
+        let polynomial = |input: &str| {
+            polynomial_form(parse_pairs(parse_latex(input).unwrap()).as_ref(), "x")
+                .map(|polynomial| polynomial.to_string())
+        };
+        assert_eq!(
+            polynomial("(x + 1)^2 - \\frac{x}{2}").as_deref(),
+            Some("(x^{2} + 3/2x + 1)")
+        );
+        assert_eq!(
+            polynomial("-(x - 2)(x + 2)").as_deref(),
+            Some("(-x^{2} + 4)")
+        );
+        assert_eq!(polynomial("3").as_deref(), Some("3"));
+        assert_eq!(polynomial("\\frac{1}{x}"), None);
+        assert_eq!(polynomial("x^{\\frac{1}{2}}"), None);
+        assert_eq!(polynomial("x + y"), None);
+    }
+}
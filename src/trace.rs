@@ -0,0 +1,17 @@
+/// Record that a simplification rule rewrote a node, when built with the `tracing` feature
+#[inline]
+pub(crate) fn rule(node: &'static str, rule: &'static str) {
+    #[cfg(feature = "tracing")]
+    tracing::trace!(node, rule, "applied simplification rule");
+    #[cfg(not(feature = "tracing"))]
+    let _ = (node, rule);
+}
+
+/// Record the state after an iteration of an algorithm, when built with the `tracing` feature
+#[inline]
+pub(crate) fn iteration(algorithm: &'static str, iteration: usize, state: impl FnOnce() -> String) {
+    #[cfg(feature = "tracing")]
+    tracing::trace!(algorithm, iteration, state = state(), "finished iteration");
+    #[cfg(not(feature = "tracing"))]
+    let _ = (algorithm, iteration, state);
+}
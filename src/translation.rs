@@ -0,0 +1,110 @@
+use pest::{error::InputLocation, Position};
+
+use crate::{
+    expression::Expression,
+    parse::{parse, ParseError, ParserOptions},
+};
+
+/// LaTeX translated from another notation, remembering where each byte of it came from
+pub(crate) struct Translation {
+    latex: String,
+    positions: Vec<usize>,
+}
+
+impl Translation {
+    /// Construct a new empty translation
+    pub(crate) fn new() -> Self {
+        Self {
+            latex: String::new(),
+            positions: Vec::new(),
+        }
+    }
+
+    /// Write some LaTeX translated from the input starting at a position
+    pub(crate) fn push(&mut self, text: &str, position: usize) {
+        self.latex.push_str(text);
+        self.positions
+            .extend(std::iter::repeat_n(position, text.len()));
+    }
+
+    /// Get the position in the input a position in the LaTeX came from
+    fn position(&self, position: usize) -> usize {
+        self.positions
+            .get(position)
+            .or(self.positions.last())
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Parse the LaTeX of this translation of some input, with spans and errors pointing into the
+    /// input instead of the LaTeX
+    pub(crate) fn parse(
+        mut self,
+        input: &str,
+        options: &ParserOptions,
+    ) -> Result<Box<dyn Expression>, ParseError> {
+        if input.len() > options.get_max_length() {
+            return Err(ParseError::TooLong {
+                length: input.len(),
+                max_length: options.get_max_length(),
+            });
+        }
+        self.positions.push(input.len());
+
+        // The translation may be longer than the input, but only the input is limited
+        let mut expression = parse(&self.latex, &options.with_max_length(usize::MAX))
+            .map_err(|error| self.relocate_error(error, input))?;
+        self.relocate(&mut expression);
+        Ok(expression)
+    }
+
+    /// Move the spans of an expression parsed from this translation back to the input
+    fn relocate(&self, expression: &mut Box<dyn Expression>) {
+        if let Some(span) = expression.get_span() {
+            expression.set_span(self.position(span.start)..self.position(span.end));
+        }
+        for child in expression.get_children_mut() {
+            self.relocate(child);
+        }
+    }
+
+    /// Move the position of an error from parsing this translation back to the input
+    fn relocate_error(&self, error: ParseError, input: &str) -> ParseError {
+        match error {
+            ParseError::Syntax(error) => {
+                let start = match error.location {
+                    InputLocation::Pos(position) => position,
+                    InputLocation::Span((start, _)) => start,
+                };
+                let position = Position::new(input, self.position(start)).expect(
+                    "Unexpected error: a translated position isn't on a character boundary!",
+                );
+                ParseError::Syntax(Box::new(pest::error::Error::new_from_pos(
+                    error.variant.clone(),
+                    position,
+                )))
+            }
+            ParseError::TooDeep {
+                position,
+                max_depth,
+            } => ParseError::TooDeep {
+                position: self.position(position),
+                max_depth,
+            },
+            ParseError::UnknownOperator { name, position } => ParseError::UnknownOperator {
+                name,
+                position: self.position(position),
+            },
+            ParseError::RaggedMatrix {
+                position,
+                columns,
+                expected,
+            } => ParseError::RaggedMatrix {
+                position: self.position(position),
+                columns,
+                expected,
+            },
+            error => error,
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use num::BigUint;
+
+use crate::value::{QuantityValue, RationalValue, Sign, Unit, Value};
+
+/// A physical constant with a unit
+#[derive(Debug, Clone, Copy)]
+pub struct PhysicalConstant {
+    /// The name the constant is written as
+    pub name: &'static str,
+    /// A description of the constant
+    pub description: &'static str,
+    /// The decimal digits of the constant's value
+    pub mantissa: &'static str,
+    /// The power of ten the mantissa is scaled by
+    pub exponent: i32,
+    /// The exponents of the SI base units, in the order used by `Unit::new`
+    pub unit: [i8; 7],
+}
+
+/// The physical constants, with their CODATA 2018 values
+pub const PHYSICAL_CONSTANTS: [PhysicalConstant; 7] = [
+    PhysicalConstant {
+        name: "c",
+        description: "speed of light in vacuum",
+        mantissa: "299792458",
+        exponent: 0,
+        unit: [0, 1, -1, 0, 0, 0, 0],
+    },
+    PhysicalConstant {
+        name: "h",
+        description: "Planck constant",
+        mantissa: "6.62607015",
+        exponent: -34,
+        unit: [1, 2, -1, 0, 0, 0, 0],
+    },
+    PhysicalConstant {
+        name: "\\hbar",
+        description: "reduced Planck constant",
+        mantissa: "1.054571817",
+        exponent: -34,
+        unit: [1, 2, -1, 0, 0, 0, 0],
+    },
+    PhysicalConstant {
+        name: "G",
+        description: "Newtonian constant of gravitation",
+        mantissa: "6.67430",
+        exponent: -11,
+        unit: [-1, 3, -2, 0, 0, 0, 0],
+    },
+    PhysicalConstant {
+        name: "N_A",
+        description: "Avogadro constant",
+        mantissa: "6.02214076",
+        exponent: 23,
+        unit: [0, 0, 0, 0, 0, -1, 0],
+    },
+    PhysicalConstant {
+        name: "k_B",
+        description: "Boltzmann constant",
+        mantissa: "1.380649",
+        exponent: -23,
+        unit: [1, 2, -2, 0, -1, 0, 0],
+    },
+    PhysicalConstant {
+        name: "m_e",
+        description: "electron mass",
+        mantissa: "9.1093837015",
+        exponent: -31,
+        unit: [1, 0, 0, 0, 0, 0, 0],
+    },
+];
+
+impl PhysicalConstant {
+    /// Look up a physical constant by name
+    pub fn lookup(name: &str) -> Option<PhysicalConstant> {
+        PHYSICAL_CONSTANTS
+            .iter()
+            .find(|constant| constant.name == name)
+            .copied()
+    }
+
+    /// Get the value of this constant as an exact quantity
+    pub fn value(&self) -> QuantityValue {
+        let mantissa = self
+            .mantissa
+            .parse::<RationalValue>()
+            .expect("Physical constants have valid mantissas");
+        let scale = RationalValue::new(
+            Sign::Positive,
+            BigUint::from(10_u32).pow(self.exponent.unsigned_abs()),
+            1_u32,
+        );
+        let magnitude = if self.exponent < 0 {
+            mantissa.div(&scale)
+        } else {
+            mantissa.mul(&scale)
+        };
+        QuantityValue::new(magnitude, Unit::new(self.unit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::{Bindings, ValueExpression},
+        parse::{parse, ParserOptions},
+        value::{FloatValue, QuantityValue, UndefinedValue, Unit, Value},
+    };
+
+    /// Simplify an input down to a value, recognizing physical constants
+    fn simplified(input: &str) -> Box<dyn Value> {
+        let options = ParserOptions::default().with_physical_constants(true);
+        let expression = parse(input, &options).unwrap().simplified();
+        dyn_clone::clone_box(
+            expression
+                .downcast_ref::<ValueExpression>()
+                .unwrap()
+                .get_value(),
+        )
+    }
+
+    #[test]
+    fn physical_constants() {
+        let energy = simplified("m_e \\cdot c \\cdot c");
+        let energy = energy.downcast_ref::<QuantityValue>().unwrap();
+        assert_eq!(energy.get_unit(), Unit::new([1, 2, -2, 0, 0, 0, 0]));
+        let magnitude = FloatValue::approximate(energy.get_magnitude()).unwrap();
+        assert!((magnitude / 8.187_105_776_9e-14 - 1.0).abs() < 1e-10);
+
+        // Constants are only recognized when asked for
+        let expression = parse("c", &ParserOptions::default()).unwrap();
+        assert_eq!(expression.to_string(), "c");
+
+        // Units whose exponents overflow are undefined
+        let unit = Unit::new([0, 127, 0, 0, 0, 0, 0]);
+        assert_eq!(unit.mul(&unit), None);
+        assert_eq!(unit.div(&unit), Some(Unit::dimensionless()));
+        let power = format!("c{}", " \\cdot c".repeat(127));
+        let options = ParserOptions::default().with_physical_constants(true);
+        let value = parse(&power, &options).unwrap().evaluate(&Bindings::new());
+        assert!(value.is::<UndefinedValue>());
+    }
+}
@@ -0,0 +1,79 @@
+use crate::format::FormatOptions;
+
+use super::{UndefinedValue, Value};
+
+/// A boolean value
+#[derive(Debug, Clone)]
+pub struct BooleanValue {
+    value: bool,
+}
+
+impl BooleanValue {
+    pub fn new(value: bool) -> Self {
+        Self { value }
+    }
+
+    pub fn get_value(&self) -> bool {
+        self.value
+    }
+}
+
+impl Value for BooleanValue {
+    fn add(&self, _other: &dyn Value) -> Box<dyn Value> {
+        Box::new(UndefinedValue::new())
+    }
+
+    fn sub(&self, _other: &dyn Value) -> Box<dyn Value> {
+        Box::new(UndefinedValue::new())
+    }
+
+    fn mul(&self, _other: &dyn Value) -> Box<dyn Value> {
+        Box::new(UndefinedValue::new())
+    }
+
+    fn div(&self, _other: &dyn Value) -> Box<dyn Value> {
+        Box::new(UndefinedValue::new())
+    }
+
+    fn cmp(&self, other: &dyn Value) -> Option<std::cmp::Ordering> {
+        // Booleans are only comparable with each other, with false before true
+        other
+            .downcast_ref::<BooleanValue>()
+            .map(|other| self.value.cmp(&other.value))
+    }
+
+    fn format(&self, _options: &FormatOptions) -> String {
+        self.value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::{
+        expression::Bindings,
+        parse::{parse, ParserOptions},
+        value::{RationalValue, Value},
+    };
+
+    use super::BooleanValue;
+
+    #[test]
+    fn booleans() {
+        let yes = BooleanValue::new(true);
+        let no = BooleanValue::new(false);
+        assert_eq!(yes.to_string(), "true");
+        assert_eq!(no.to_string(), "false");
+        assert_eq!(yes.cmp(&BooleanValue::new(true)), Some(Ordering::Equal));
+        assert_eq!(no.cmp(&yes), Some(Ordering::Less));
+        assert_eq!(yes.cmp(&RationalValue::one()), None);
+        assert_eq!(yes.add(&no).to_string(), "undefined");
+
+        // Relations evaluate to booleans
+        let options = ParserOptions::default();
+        let value = parse("1 < 2", &options).unwrap().evaluate(&Bindings::new());
+        assert_eq!(value.cmp(&yes), Some(Ordering::Equal));
+        assert_eq!(value.to_string(), "true");
+    }
+}
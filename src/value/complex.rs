@@ -0,0 +1,219 @@
+use std::cmp::Ordering;
+
+use crate::format::FormatOptions;
+
+use super::{rational::rational, RationalValue, Sign, UndefinedValue, Value};
+
+/// An exact complex value, with rational real and imaginary parts
+#[derive(Debug, Clone)]
+pub struct ComplexValue {
+    real: RationalValue,
+    imaginary: RationalValue,
+}
+
+impl ComplexValue {
+    pub fn new(real: RationalValue, imaginary: RationalValue) -> Self {
+        Self { real, imaginary }
+    }
+
+    /// Construct the imaginary unit
+    pub fn imaginary_unit() -> Self {
+        Self::new(RationalValue::zero(), RationalValue::one())
+    }
+
+    pub fn get_real(&self) -> &RationalValue {
+        &self.real
+    }
+
+    pub fn get_imaginary(&self) -> &RationalValue {
+        &self.imaginary
+    }
+
+    /// Get the complex conjugate of this value
+    pub fn get_conjugate(&self) -> Self {
+        Self::new(self.real.clone(), self.imaginary.get_opposite())
+    }
+
+    /// Check if this value is the imaginary unit
+    pub fn is_imaginary_unit(&self) -> bool {
+        self.real.is_zero() && self.imaginary.cmp(&RationalValue::one()) == Some(Ordering::Equal)
+    }
+
+    /// Raise this value to an integer power, or `None` for a negative power of zero
+    pub fn pow(&self, exponent: i32) -> Option<Box<dyn Value>> {
+        let base = if exponent < 0 {
+            let reciprocal = RationalValue::one().div(self);
+            Self::from_value(reciprocal.as_ref())?
+        } else {
+            self.clone()
+        };
+
+        // Square and multiply, going through the bits of the exponent
+        let mut result: Box<dyn Value> = Box::new(RationalValue::one());
+        let mut square: Box<dyn Value> = Box::new(base);
+        let mut exponent = exponent.unsigned_abs();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.mul(square.as_ref());
+            }
+            square = square.mul(square.as_ref());
+            exponent >>= 1;
+        }
+        Some(result)
+    }
+
+    /// Get a value as a complex value, if it is a rational or complex value
+    fn from_value(value: &dyn Value) -> Option<Self> {
+        if let Some(value) = value.downcast_ref::<ComplexValue>() {
+            Some(value.clone())
+        } else {
+            value
+                .downcast_ref::<RationalValue>()
+                .map(|value| Self::from(value.clone()))
+        }
+    }
+
+    /// Box this value, collapsing it to a rational value if it is real
+    fn normalized(self) -> Box<dyn Value> {
+        if self.imaginary.is_zero() {
+            Box::new(self.real)
+        } else {
+            Box::new(self)
+        }
+    }
+}
+
+impl From<RationalValue> for ComplexValue {
+    fn from(value: RationalValue) -> Self {
+        Self::new(value, RationalValue::zero())
+    }
+}
+
+impl Value for ComplexValue {
+    fn add(&self, other: &dyn Value) -> Box<dyn Value> {
+        let Some(other) = Self::from_value(other) else {
+            return Box::new(UndefinedValue::new());
+        };
+        Self::new(
+            rational(self.real.add(&other.real)),
+            rational(self.imaginary.add(&other.imaginary)),
+        )
+        .normalized()
+    }
+
+    fn sub(&self, other: &dyn Value) -> Box<dyn Value> {
+        let Some(other) = Self::from_value(other) else {
+            return Box::new(UndefinedValue::new());
+        };
+        Self::new(
+            rational(self.real.sub(&other.real)),
+            rational(self.imaginary.sub(&other.imaginary)),
+        )
+        .normalized()
+    }
+
+    fn mul(&self, other: &dyn Value) -> Box<dyn Value> {
+        let Some(other) = Self::from_value(other) else {
+            return Box::new(UndefinedValue::new());
+        };
+
+        // (a + bi)(c + di) = (ac - bd) + (ad + bc)i
+        let real = self
+            .real
+            .mul(&other.real)
+            .sub(self.imaginary.mul(&other.imaginary).as_ref());
+        let imaginary = self
+            .real
+            .mul(&other.imaginary)
+            .add(self.imaginary.mul(&other.real).as_ref());
+        Self::new(rational(real), rational(imaginary)).normalized()
+    }
+
+    fn div(&self, other: &dyn Value) -> Box<dyn Value> {
+        let Some(other) = Self::from_value(other) else {
+            return Box::new(UndefinedValue::new());
+        };
+
+        // Multiply through by the conjugate, so only a real value is left to divide by
+        let magnitude = rational(
+            other
+                .real
+                .mul(&other.real)
+                .add(other.imaginary.mul(&other.imaginary).as_ref()),
+        );
+        if magnitude.is_zero() {
+            return Box::new(UndefinedValue::new());
+        }
+        let Some(numerator) = Self::from_value(self.mul(&other.get_conjugate()).as_ref()) else {
+            return Box::new(UndefinedValue::new());
+        };
+        Self::new(
+            rational(numerator.real.div(&magnitude)),
+            rational(numerator.imaginary.div(&magnitude)),
+        )
+        .normalized()
+    }
+
+    fn cmp(&self, other: &dyn Value) -> Option<Ordering> {
+        // Complex values have no order, but can still be equal
+        let other = Self::from_value(other)?;
+        (self.real.cmp(&other.real) == Some(Ordering::Equal)
+            && self.imaginary.cmp(&other.imaginary) == Some(Ordering::Equal))
+        .then_some(Ordering::Equal)
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        let one = RationalValue::one();
+        let imaginary = |value: &RationalValue| {
+            if value.cmp(&one) == Some(Ordering::Equal) {
+                "i".to_string()
+            } else {
+                format!("{}i", value.format(options))
+            }
+        };
+        if self.real.is_zero() {
+            return match self.imaginary.get_sign() {
+                Sign::Negative => format!("-{}", imaginary(&self.imaginary.get_absolute())),
+                Sign::Positive => imaginary(&self.imaginary),
+            };
+        }
+        let operator = match self.imaginary.get_sign() {
+            Sign::Negative => '-',
+            Sign::Positive => '+',
+        };
+        format!(
+            "({} {} {})",
+            self.real.format(options),
+            operator,
+            imaginary(&self.imaginary.get_absolute())
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{parse, ParserOptions};
+
+    #[test]
+    fn complex_arithmetic() {
+        let options = ParserOptions::default();
+        let simplified = |input: &str| parse(input, &options).unwrap().simplified().to_string();
+        assert_eq!(simplified("3 + 4i"), "(3 + 4i)");
+        assert_eq!(simplified("i \\cdot i"), "-1");
+        assert_eq!(simplified("(1 + 2i)(3 - i)"), "(5 + 5i)");
+        assert_eq!(simplified("\\frac{1}{1 + i}"), "(1/2 - 1/2i)");
+        assert_eq!(simplified("(2 + i) - (2 + i)"), "0");
+        assert_eq!(simplified("0 - i"), "-i");
+        assert_eq!(simplified("(1 + i)^2"), "2i");
+        assert_eq!(simplified("i^{-1}"), "-i");
+        assert_eq!(simplified("(1 - i)^{4}"), "-4");
+
+        // Summation indices named i are still variables
+        assert_eq!(simplified("\\sum_{i=1}^{3} i"), "6");
+        assert_eq!(simplified("\\sum_{i=1}^{3} (i + 1)"), "9");
+
+        // The imaginary unit can be turned off to use i as a variable
+        let options = options.with_imaginary_unit(false);
+        assert_eq!(parse("2i", &options).unwrap().to_string(), "(2 * i)");
+    }
+}
@@ -0,0 +1,377 @@
+use std::{cmp::Ordering, collections::HashMap, sync::RwLock};
+
+use num::bigint::ParseBigIntError;
+
+use crate::format::FormatOptions;
+
+use super::{
+    DecimalValue, FloatValue, InfinityValue, QuantityValue, RationalValue, Sign, UndefinedValue,
+    Value,
+};
+
+/// The decimal expansion of pi
+const PI: &str = "3.1415926535897932384626433832795028841971693993751058209749445923078164062862089986280348253421170679";
+
+/// The decimal expansion of Euler's number
+const E: &str = "2.7182818284590452353602874713526624977572470936999595749669676277240766303535475945713821785251664274";
+
+/// The decimal expansion of the golden ratio
+const PHI: &str = "1.6180339887498948482045868343656381177203091798057628621354486227052604628189024497072072041893911374";
+
+/// The decimal expansion of the Euler-Mascheroni constant
+const GAMMA: &str = "0.5772156649015328606065120900824024310421593359399235988057672348848677267776646709369470632917467495";
+
+/// The definition of a registered constant
+#[derive(Debug, Clone)]
+struct ConstantDefinition {
+    expansion: RationalValue,
+    digits: usize,
+    greek: bool,
+}
+
+lazy_static::lazy_static! {
+    /// The registered constants by name
+    ///
+    /// Physical constants like `c` and `G` carry units, so they are in
+    /// [`PHYSICAL_CONSTANTS`](crate::units::PHYSICAL_CONSTANTS) instead, and only parsed when
+    /// enabled in the parser options. The Greek letters `\\phi` and `\\gamma` are usually
+    /// variables, so they are registered but also only parsed as constants when enabled.
+    static ref CONSTANTS: RwLock<HashMap<String, ConstantDefinition>> = {
+        let mut constants = HashMap::new();
+        for (name, expansion) in [("\\pi", PI), ("e", E)] {
+            constants.insert(name.to_string(), ConstantDefinition::parse(expansion).unwrap());
+        }
+        for (name, expansion) in [("\\phi", PHI), ("\\gamma", GAMMA)] {
+            let definition = ConstantDefinition {
+                greek: true,
+                ..ConstantDefinition::parse(expansion).unwrap()
+            };
+            constants.insert(name.to_string(), definition);
+        }
+        RwLock::new(constants)
+    };
+}
+
+impl ConstantDefinition {
+    /// Parse a definition from a decimal expansion
+    fn parse(expansion: &str) -> Result<Self, ParseBigIntError> {
+        Ok(Self {
+            expansion: expansion.parse()?,
+            digits: expansion
+                .split_once('.')
+                .map_or(0, |(_, digits)| digits.len()),
+            greek: false,
+        })
+    }
+}
+
+/// A named mathematical constant
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constant {
+    name: String,
+}
+
+impl Constant {
+    /// Register a named constant by its decimal expansion, making it available to the parser
+    ///
+    /// The name is how the constant is written, like `\\pi` or `e`. Registering an existing name
+    /// replaces its expansion.
+    pub fn register(
+        name: impl Into<String>,
+        expansion: &str,
+    ) -> Result<Constant, ParseBigIntError> {
+        let name = name.into();
+        let definition = ConstantDefinition::parse(expansion)?;
+        CONSTANTS
+            .write()
+            .expect("Constant registry was poisoned")
+            .insert(name.clone(), definition);
+        Ok(Constant { name })
+    }
+
+    /// Look up a registered constant by name
+    pub fn lookup(name: &str) -> Option<Constant> {
+        CONSTANTS
+            .read()
+            .expect("Constant registry was poisoned")
+            .contains_key(name)
+            .then(|| Constant {
+                name: name.to_string(),
+            })
+    }
+
+    /// Get the constant pi
+    pub fn pi() -> Constant {
+        Constant::lookup("\\pi").expect("Pi is always registered")
+    }
+
+    /// Get Euler's number
+    pub fn e() -> Constant {
+        Constant::lookup("e").expect("Euler's number is always registered")
+    }
+
+    /// Check if this is a built-in constant written as a Greek letter, like `\\phi`, which is only
+    /// parsed when enabled in the parser options
+    pub fn is_greek(&self) -> bool {
+        CONSTANTS.read().expect("Constant registry was poisoned")[&self.name].greek
+    }
+
+    /// Get the LaTeX name of this constant
+    pub fn latex(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the decimal expansion of this constant and the number of digits it is accurate to
+    pub fn expansion(&self) -> (RationalValue, usize) {
+        let constants = CONSTANTS.read().expect("Constant registry was poisoned");
+        let definition = &constants[&self.name];
+        (definition.expansion.clone(), definition.digits)
+    }
+
+    /// Get a floating point approximation of this constant
+    pub fn approximate(&self) -> f64 {
+        FloatValue::approximate(&self.expansion().0).unwrap_or(f64::NAN)
+    }
+}
+
+/// An exact rational multiple of a named constant
+#[derive(Debug, Clone)]
+pub struct ConstantValue {
+    coefficient: RationalValue,
+    constant: Constant,
+}
+
+impl ConstantValue {
+    pub fn new(coefficient: RationalValue, constant: Constant) -> Self {
+        Self {
+            coefficient: coefficient.simplified(),
+            constant,
+        }
+    }
+
+    pub fn get_coefficient(&self) -> &RationalValue {
+        &self.coefficient
+    }
+
+    pub fn get_constant(&self) -> &Constant {
+        &self.constant
+    }
+
+    /// Get a floating point approximation of this value
+    pub fn approximated(&self) -> FloatValue {
+        FloatValue::new(
+            FloatValue::approximate(&self.coefficient).unwrap_or(f64::NAN)
+                * self.constant.approximate(),
+        )
+    }
+
+    /// Get a decimal approximation of this value, with no more digits than the constant is known to
+    pub fn decimal(&self, precision: usize) -> DecimalValue {
+        let (expansion, digits) = self.constant.expansion();
+        let value = self.coefficient.mul(&expansion);
+        let value = value
+            .downcast_ref::<RationalValue>()
+            .expect("Unexpected error: a product of rationals isn't rational!");
+        DecimalValue::from_rational(value, precision.min(digits))
+    }
+
+    /// Construct a multiple of a constant, collapsing to a rational value when the coefficient is zero
+    fn multiple(coefficient: Box<dyn Value>, constant: Constant) -> Box<dyn Value> {
+        match coefficient.downcast_ref::<RationalValue>() {
+            Some(coefficient) if coefficient.is_zero() => Box::new(RationalValue::zero()),
+            Some(coefficient) => Box::new(ConstantValue::new(coefficient.clone(), constant)),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+}
+
+impl Value for ConstantValue {
+    fn add(&self, other: &dyn Value) -> Box<dyn Value> {
+        match other.downcast_ref::<ConstantValue>() {
+            Some(other) if other.constant == self.constant => Self::multiple(
+                self.coefficient.add(&other.coefficient),
+                self.constant.clone(),
+            ),
+            _ if other.is::<FloatValue>() => self.approximated().add(other),
+            _ if other.is::<InfinityValue>() => other.add(self),
+            _ => match other.downcast_ref::<DecimalValue>() {
+                Some(decimal) => self.decimal(decimal.get_precision()).add(other),
+                None => Box::new(UndefinedValue::new()),
+            },
+        }
+    }
+
+    fn sub(&self, other: &dyn Value) -> Box<dyn Value> {
+        match other.downcast_ref::<ConstantValue>() {
+            Some(other) if other.constant == self.constant => Self::multiple(
+                self.coefficient.sub(&other.coefficient),
+                self.constant.clone(),
+            ),
+            _ if other.is::<FloatValue>() => self.approximated().sub(other),
+            _ if other.is::<InfinityValue>() => other.mul(&RationalValue::one().get_opposite()),
+            _ => match other.downcast_ref::<DecimalValue>() {
+                Some(decimal) => self.decimal(decimal.get_precision()).sub(other),
+                None => Box::new(UndefinedValue::new()),
+            },
+        }
+    }
+
+    fn mul(&self, other: &dyn Value) -> Box<dyn Value> {
+        if let Some(other) = other.downcast_ref::<RationalValue>() {
+            Self::multiple(self.coefficient.mul(other), self.constant.clone())
+        } else if other.is::<FloatValue>() {
+            self.approximated().mul(other)
+        } else if let Some(decimal) = other.downcast_ref::<DecimalValue>() {
+            self.decimal(decimal.get_precision()).mul(other)
+        } else if other.is::<InfinityValue>() {
+            other.mul(self)
+        } else if other.is::<QuantityValue>() {
+            QuantityValue::dimensionless(Box::new(self.clone())).mul(other)
+        } else {
+            Box::new(UndefinedValue::new())
+        }
+    }
+
+    fn div(&self, other: &dyn Value) -> Box<dyn Value> {
+        if let Some(other) = other.downcast_ref::<RationalValue>() {
+            Self::multiple(self.coefficient.div(other), self.constant.clone())
+        } else if let Some(other) = other.downcast_ref::<ConstantValue>() {
+            if other.constant == self.constant {
+                self.coefficient.div(&other.coefficient)
+            } else {
+                Box::new(UndefinedValue::new())
+            }
+        } else if other.is::<FloatValue>() {
+            self.approximated().div(other)
+        } else if let Some(decimal) = other.downcast_ref::<DecimalValue>() {
+            self.decimal(decimal.get_precision()).div(other)
+        } else if other.is::<InfinityValue>() {
+            Box::new(RationalValue::zero())
+        } else if other.is::<QuantityValue>() {
+            QuantityValue::dimensionless(Box::new(self.clone())).div(other)
+        } else {
+            Box::new(UndefinedValue::new())
+        }
+    }
+
+    fn cmp(&self, other: &dyn Value) -> Option<Ordering> {
+        match other.downcast_ref::<ConstantValue>() {
+            Some(other) if other.constant == self.constant => {
+                self.coefficient.cmp(&other.coefficient)
+            }
+            _ => match other.downcast_ref::<DecimalValue>() {
+                Some(decimal) => self.decimal(decimal.get_precision()).cmp(other),
+                None => self.approximated().cmp(other),
+            },
+        }
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        let sign = if self.coefficient.is_negative() {
+            "-"
+        } else {
+            ""
+        };
+        let numerator = self.coefficient.get_numerator();
+        let denominator = self.coefficient.get_denominator();
+        let multiple = if *numerator == 1_u32.into() {
+            self.constant.latex().to_string()
+        } else {
+            format!(
+                "{}{}",
+                options.decimal(false, &numerator.to_string(), ""),
+                self.constant.latex()
+            )
+        };
+        if *denominator == 1_u32.into() {
+            format!("{}{}", sign, multiple)
+        } else {
+            let denominator = options.decimal(false, &denominator.to_string(), "");
+            format!("{}{}", sign, options.fraction(&multiple, &denominator))
+        }
+    }
+}
+
+impl From<Constant> for ConstantValue {
+    fn from(value: Constant) -> Self {
+        Self::new(RationalValue::new(Sign::Positive, 1_u32, 1_u32), value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::{
+        expression::Bindings,
+        numeric::approximate,
+        parse::{parse, parse_latex, parse_pairs, ParserOptions},
+        value::{DecimalValue, Value},
+    };
+
+    use super::{Constant, ConstantValue};
+
+    fn simplify(input: &str) -> String {
+        parse_pairs(parse_latex(input).unwrap())
+            .simplified()
+            .to_string()
+    }
+
+    #[test]
+    fn exact_constant_arithmetic() {
+        assert_eq!(simplify("2\\pi + 3\\pi"), "5\\pi");
+        assert_eq!(simplify("e + \\pi"), "(e + \\pi)");
+        assert_eq!(simplify("\\mathrm{e} - e"), "0");
+
+        // The golden ratio and Euler-Mascheroni constant have to be enabled
+        let options = ParserOptions::default().with_greek_constants(true);
+        let simplified = |input: &str| parse(input, &options).unwrap().simplified().to_string();
+        assert_eq!(simplified("\\phi / 2 - \\phi"), "-\\phi/2");
+        assert_eq!(simplified("\\gamma + \\gamma"), "2\\gamma");
+        assert_eq!(simplify("\\gamma + \\gamma"), "(2 * \\gamma)");
+
+        // Infinities swallow constants like any other finite value
+        let evaluated = |input: &str| {
+            parse_pairs(parse_latex(input).unwrap())
+                .evaluate(&Bindings::new())
+                .to_string()
+        };
+        assert_eq!(evaluated("\\pi - \\infty"), "-\\infty");
+        assert_eq!(evaluated("-\\pi \\cdot \\infty"), "-\\infty");
+        assert_eq!(evaluated("\\frac{2e}{\\infty}"), "0");
+    }
+
+    #[test]
+    fn high_precision_approximation() {
+        let expression = parse_pairs(parse_latex("2\\pi").unwrap());
+        assert_eq!(
+            approximate(expression.as_ref(), 40).unwrap(),
+            "6.2831853071795864769252867665590057683943"
+        );
+    }
+
+    #[test]
+    fn decimal_constants() {
+        let pi = ConstantValue::from(Constant::pi());
+        assert_eq!(
+            pi.decimal(30).to_string(),
+            "3.14159265358979323846264338328"
+        );
+
+        // Mixing with decimal values keeps the precision of the decimal
+        let half = DecimalValue::from_rational(&"0.5".parse().unwrap(), 10);
+        assert_eq!(pi.mul(&half).to_string(), "1.570796327");
+        assert_eq!(half.sub(&pi).to_string(), "-2.641592654");
+        assert_eq!(half.cmp(&pi), Some(Ordering::Less));
+    }
+
+    #[test]
+    fn registered_constant() {
+        Constant::register("\\rho", "1.3247179572447460259609088544780973407344").unwrap();
+        assert_eq!(simplify("2\\rho - \\rho"), "\\rho");
+        assert!(
+            (Constant::lookup("\\rho").unwrap().approximate() - 1.324_717_957_244_746).abs()
+                < 1e-15
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use std::cmp::Ordering;
+
+use num::{BigInt, BigUint, Integer as _, Signed as _, Zero as _};
+
+use crate::{format::FormatOptions, numeric::integer_nth_root};
+
+use super::{ConstantValue, FloatValue, RationalValue, Sign, UndefinedValue, Value};
+
+/// The number of significant digits decimal values keep unless told otherwise
+pub const DEFAULT_PRECISION: usize = 50;
+
+/// An approximate decimal value with any number of significant digits, for results like roots that
+/// can't stay rational
+#[derive(Debug, Clone)]
+pub struct DecimalValue {
+    mantissa: BigInt,
+    exponent: i64,
+    precision: usize,
+}
+
+/// Get the number of decimal digits in an integer
+fn digit_count(value: &BigUint) -> i64 {
+    value.to_string().len() as i64
+}
+
+/// Get a power of ten
+fn ten_to(exponent: u64) -> BigInt {
+    num::pow(BigInt::from(10_u32), exponent as usize)
+}
+
+impl DecimalValue {
+    /// Construct a new decimal value `mantissa * 10^exponent`, rounded to a number of significant
+    /// digits
+    pub fn new(mantissa: BigInt, exponent: i64, precision: usize) -> Self {
+        let precision = precision.max(1);
+        let mut mantissa = mantissa;
+        let mut exponent = exponent;
+
+        // Round half away from zero to the precision
+        let excess = digit_count(mantissa.magnitude()) - precision as i64;
+        if excess > 0 {
+            let scale = ten_to(excess as u64);
+            let (quotient, remainder) = mantissa.div_rem(&scale);
+            mantissa = if remainder.magnitude() * 2_u32 >= *scale.magnitude() {
+                quotient + mantissa.signum()
+            } else {
+                quotient
+            };
+            exponent += excess;
+        }
+
+        // Drop trailing zeros, so equal values are written the same way
+        if mantissa.is_zero() {
+            exponent = 0;
+        } else {
+            let ten = BigInt::from(10_u32);
+            while (&mantissa % &ten).is_zero() {
+                mantissa /= &ten;
+                exponent += 1;
+            }
+        }
+        Self {
+            mantissa,
+            exponent,
+            precision,
+        }
+    }
+
+    /// Construct the decimal value closest to a rational value with a number of significant digits
+    pub fn from_rational(value: &RationalValue, precision: usize) -> Self {
+        let mut numerator = BigInt::from(value.get_numerator().clone());
+        if value.is_negative() {
+            numerator = -numerator;
+        }
+        let denominator = BigInt::from(value.get_denominator().clone());
+
+        // Keep an extra digit past the precision so the result rounds correctly
+        let shift = precision as i64 + 1 + digit_count(value.get_denominator())
+            - digit_count(value.get_numerator());
+        let scaled = if shift >= 0 {
+            numerator * ten_to(shift as u64) / denominator
+        } else {
+            numerator / (denominator * ten_to(shift.unsigned_abs()))
+        };
+        Self::new(scaled, -shift, precision)
+    }
+
+    /// Set the number of significant digits of this value, rounding it if there are fewer
+    pub fn with_precision(self, precision: usize) -> Self {
+        Self::new(self.mantissa, self.exponent, precision)
+    }
+
+    pub fn get_precision(&self) -> usize {
+        self.precision
+    }
+
+    /// Get the exact rational value of the digits of this value
+    pub fn to_rational(&self) -> RationalValue {
+        let sign = if self.mantissa.is_negative() {
+            Sign::Negative
+        } else {
+            Sign::Positive
+        };
+        let magnitude = self.mantissa.magnitude().clone();
+        if self.exponent >= 0 {
+            RationalValue::new(
+                sign,
+                magnitude * ten_to(self.exponent as u64).magnitude(),
+                1_u32,
+            )
+        } else {
+            let denominator = ten_to(self.exponent.unsigned_abs()).magnitude().clone();
+            RationalValue::new(sign, magnitude, denominator).simplified()
+        }
+    }
+
+    /// Get a floating point approximation of this value
+    pub fn approximated(&self) -> FloatValue {
+        FloatValue::new(FloatValue::approximate(&self.to_rational()).unwrap_or(f64::NAN))
+    }
+
+    /// Get the square root of this value with the same precision, or `None` if it is negative
+    pub fn sqrt(&self) -> Option<Self> {
+        if self.mantissa.is_negative() {
+            return None;
+        }
+
+        // Scale by an even power of ten so the integer root has enough digits
+        let magnitude = digit_count(self.mantissa.magnitude()) + self.exponent;
+        let shift = self.precision as i64 + 1 - magnitude.div_euclid(2);
+        let scale =
+            RationalValue::from(BigInt::from(10_u32)).pow(i32::try_from(2 * shift).ok()?)?;
+        let scaled = self.to_rational().mul(&scale);
+        let scaled = scaled.downcast_ref::<RationalValue>()?.floor();
+        let root = integer_nth_root(scaled.magnitude(), 2);
+        Some(Self::new(BigInt::from(root), -shift, self.precision))
+    }
+
+    /// Get a value as an exact rational value and the precision a result with it keeps
+    fn operand(&self, other: &dyn Value) -> Option<(RationalValue, usize)> {
+        if let Some(other) = other.downcast_ref::<DecimalValue>() {
+            Some((other.to_rational(), self.precision.min(other.precision)))
+        } else if let Some(other) = other.downcast_ref::<ConstantValue>() {
+            self.operand(&other.decimal(self.precision))
+        } else {
+            other
+                .downcast_ref::<RationalValue>()
+                .map(|other| (other.clone(), self.precision))
+        }
+    }
+
+    /// Apply an operation exactly to the digits of two values, rounding the result
+    ///
+    /// Values that aren't decimal or rational are combined with an approximation instead
+    fn combine(
+        &self,
+        other: &dyn Value,
+        operation: impl Fn(&dyn Value, &dyn Value) -> Box<dyn Value>,
+    ) -> Box<dyn Value> {
+        let Some((rhs, precision)) = self.operand(other) else {
+            return operation(&self.approximated(), other);
+        };
+        match operation(&self.to_rational(), &rhs).downcast_ref::<RationalValue>() {
+            Some(result) => Box::new(Self::from_rational(result, precision)),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+}
+
+impl Value for DecimalValue {
+    fn add(&self, other: &dyn Value) -> Box<dyn Value> {
+        self.combine(other, |lhs, rhs| lhs.add(rhs))
+    }
+
+    fn sub(&self, other: &dyn Value) -> Box<dyn Value> {
+        self.combine(other, |lhs, rhs| lhs.sub(rhs))
+    }
+
+    fn mul(&self, other: &dyn Value) -> Box<dyn Value> {
+        self.combine(other, |lhs, rhs| lhs.mul(rhs))
+    }
+
+    fn div(&self, other: &dyn Value) -> Box<dyn Value> {
+        self.combine(other, |lhs, rhs| lhs.div(rhs))
+    }
+
+    fn cmp(&self, other: &dyn Value) -> Option<Ordering> {
+        match self.operand(other) {
+            Some((other, _)) => self.to_rational().cmp(&other),
+            None => self.approximated().cmp(other),
+        }
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        let digits = self.mantissa.magnitude().to_string();
+        let (integer, fraction) = if self.exponent >= 0 {
+            let zeros = "0".repeat(self.exponent as usize);
+            (format!("{}{}", digits, zeros), String::new())
+        } else {
+            let places = self.exponent.unsigned_abs() as usize;
+            if digits.len() > places {
+                let (integer, fraction) = digits.split_at(digits.len() - places);
+                (integer.to_string(), fraction.to_string())
+            } else {
+                let zeros = "0".repeat(places - digits.len());
+                ("0".to_string(), format!("{}{}", zeros, digits))
+            }
+        };
+        options.decimal(self.mantissa.is_negative(), &integer, &fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::value::{FloatValue, RationalValue, Sign, Value};
+
+    use super::DecimalValue;
+
+    #[test]
+    fn decimals() {
+        let third = RationalValue::new(Sign::Positive, 1_u32, 3_u32);
+        let two_thirds = RationalValue::new(Sign::Negative, 2_u32, 3_u32);
+        assert_eq!(
+            DecimalValue::from_rational(&third, 20).to_string(),
+            "0.33333333333333333333"
+        );
+        assert_eq!(
+            DecimalValue::from_rational(&two_thirds, 5).to_string(),
+            "-0.66667"
+        );
+        assert_eq!(
+            DecimalValue::from_rational(&"1250".parse().unwrap(), 2).to_string(),
+            "1300"
+        );
+
+        // Mixing with rational values keeps the precision of the decimal
+        let decimal = DecimalValue::from_rational(&third, 10);
+        assert_eq!(
+            decimal.add(&RationalValue::one()).to_string(),
+            "1.333333333"
+        );
+        assert_eq!(
+            RationalValue::one().sub(&decimal).to_string(),
+            "0.6666666667"
+        );
+        assert_eq!(
+            decimal
+                .mul(&RationalValue::from(num::BigInt::from(3)))
+                .to_string(),
+            "0.9999999999"
+        );
+        assert_eq!(decimal.cmp(&third), Some(std::cmp::Ordering::Less));
+        assert_eq!(decimal.div(&RationalValue::zero()).to_string(), "undefined");
+        assert!(decimal.mul(&FloatValue::new(3.0)).is::<FloatValue>());
+
+        // Roots keep every digit of the precision
+        let two = DecimalValue::from_rational(&RationalValue::from(num::BigInt::from(2)), 30);
+        assert_eq!(
+            two.sqrt().unwrap().to_string(),
+            "1.41421356237309504880168872421"
+        );
+        let hundredth = DecimalValue::from_rational(&"0.01".parse().unwrap(), 5);
+        assert_eq!(hundredth.sqrt().unwrap().to_string(), "0.1");
+        assert!(DecimalValue::from_rational(&two_thirds, 5).sqrt().is_none());
+    }
+}
@@ -0,0 +1,142 @@
+use std::cmp::Ordering;
+
+use num::ToPrimitive as _;
+
+use crate::format::FormatOptions;
+
+use super::{
+    ConstantValue, DecimalValue, InfinityValue, IntervalValue, QuantityValue, RationalValue, Sign,
+    SurdValue, UndefinedValue, Value,
+};
+
+/// An approximate floating point value
+#[derive(Debug, Clone)]
+pub struct FloatValue {
+    value: f64,
+}
+
+impl FloatValue {
+    pub fn new(value: f64) -> Self {
+        Self { value }
+    }
+
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+
+    /// Approximate any numeric value as a float
+    pub fn approximate(value: &dyn Value) -> Option<f64> {
+        if let Some(value) = value.downcast_ref::<FloatValue>() {
+            Some(value.value)
+        } else if let Some(value) = value.downcast_ref::<RationalValue>() {
+            let magnitude = value.get_numerator().to_f64()? / value.get_denominator().to_f64()?;
+            Some(match value.get_sign() {
+                Sign::Positive => magnitude,
+                Sign::Negative => -magnitude,
+            })
+        } else if let Some(value) = value.downcast_ref::<ConstantValue>() {
+            Some(
+                FloatValue::approximate(value.get_coefficient())?
+                    * value.get_constant().approximate(),
+            )
+        } else if let Some(value) = value.downcast_ref::<DecimalValue>() {
+            FloatValue::approximate(&value.to_rational())
+        } else {
+            value
+                .downcast_ref::<SurdValue>()
+                .map(|value| value.approximated().value)
+        }
+    }
+
+    /// Wrap the result of a float operation, treating non-finite results as undefined
+    fn result(value: f64) -> Box<dyn Value> {
+        if value.is_finite() {
+            Box::new(FloatValue::new(value))
+        } else {
+            Box::new(UndefinedValue::new())
+        }
+    }
+}
+
+impl Value for FloatValue {
+    fn add(&self, other: &dyn Value) -> Box<dyn Value> {
+        if other.is::<IntervalValue>() {
+            // The error of a float is unknown, so it can't be enclosed in an interval
+            return Box::new(UndefinedValue::new());
+        }
+        if other.is::<QuantityValue>() {
+            return QuantityValue::dimensionless(Box::new(self.clone())).add(other);
+        }
+        if other.is::<InfinityValue>() {
+            return other.add(self);
+        }
+        match FloatValue::approximate(other) {
+            Some(other) => FloatValue::result(self.value + other),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn sub(&self, other: &dyn Value) -> Box<dyn Value> {
+        if other.is::<IntervalValue>() {
+            // The error of a float is unknown, so it can't be enclosed in an interval
+            return Box::new(UndefinedValue::new());
+        }
+        if other.is::<QuantityValue>() {
+            return QuantityValue::dimensionless(Box::new(self.clone())).sub(other);
+        }
+        if let Some(other) = other.downcast_ref::<InfinityValue>() {
+            return other.get_opposite().add(self);
+        }
+        match FloatValue::approximate(other) {
+            Some(other) => FloatValue::result(self.value - other),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn mul(&self, other: &dyn Value) -> Box<dyn Value> {
+        if other.is::<IntervalValue>() {
+            // The error of a float is unknown, so it can't be enclosed in an interval
+            return Box::new(UndefinedValue::new());
+        }
+        if other.is::<QuantityValue>() {
+            return QuantityValue::dimensionless(Box::new(self.clone())).mul(other);
+        }
+        if other.is::<InfinityValue>() {
+            return other.mul(self);
+        }
+        match FloatValue::approximate(other) {
+            Some(other) => FloatValue::result(self.value * other),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn div(&self, other: &dyn Value) -> Box<dyn Value> {
+        if other.is::<IntervalValue>() {
+            // The error of a float is unknown, so it can't be enclosed in an interval
+            return Box::new(UndefinedValue::new());
+        }
+        if other.is::<QuantityValue>() {
+            return QuantityValue::dimensionless(Box::new(self.clone())).div(other);
+        }
+        if other.is::<InfinityValue>() {
+            return Box::new(FloatValue::new(0.0));
+        }
+        match FloatValue::approximate(other) {
+            Some(other) => FloatValue::result(self.value / other),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn cmp(&self, other: &dyn Value) -> Option<Ordering> {
+        if other.is::<InfinityValue>() {
+            return other.cmp(self).map(Ordering::reverse);
+        }
+        self.value.partial_cmp(&FloatValue::approximate(other)?)
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        let written = self.value.abs().to_string();
+        let (integer, fraction) = written.split_once('.').unwrap_or((&written, ""));
+        options.decimal(self.value.is_sign_negative(), integer, fraction)
+    }
+}
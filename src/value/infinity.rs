@@ -0,0 +1,133 @@
+use std::cmp::Ordering;
+
+use crate::format::FormatOptions;
+
+use super::{FloatValue, Sign, UndefinedValue, Value};
+
+/// Positive or negative infinity, following the arithmetic of the extended reals
+#[derive(Debug, Clone)]
+pub struct InfinityValue {
+    sign: Sign,
+}
+
+impl InfinityValue {
+    pub fn new(sign: Sign) -> Self {
+        Self { sign }
+    }
+
+    /// Construct positive infinity
+    pub fn positive() -> Self {
+        Self::new(Sign::Positive)
+    }
+
+    /// Construct negative infinity
+    pub fn negative() -> Self {
+        Self::new(Sign::Negative)
+    }
+
+    pub fn get_sign(&self) -> &Sign {
+        &self.sign
+    }
+
+    /// Get the infinity with the opposite sign
+    pub fn get_opposite(&self) -> Self {
+        Self::new(self.sign ^ Sign::Negative)
+    }
+
+    /// Get the sign of a finite value, or `None` if it's zero or not a number
+    fn finite_sign(value: &dyn Value) -> Option<Sign> {
+        let value = FloatValue::approximate(value)?;
+        (value != 0.0).then(|| Sign::from(value < 0.0))
+    }
+}
+
+impl Value for InfinityValue {
+    fn add(&self, other: &dyn Value) -> Box<dyn Value> {
+        // Infinities of opposite signs don't cancel to anything
+        if let Some(other) = other.downcast_ref::<InfinityValue>() {
+            return if other.sign == self.sign {
+                Box::new(self.clone())
+            } else {
+                Box::new(UndefinedValue::new())
+            };
+        }
+        match FloatValue::approximate(other) {
+            Some(_) => Box::new(self.clone()),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn sub(&self, other: &dyn Value) -> Box<dyn Value> {
+        match other.downcast_ref::<InfinityValue>() {
+            Some(other) => self.add(&other.get_opposite()),
+            None => self.add(other),
+        }
+    }
+
+    fn mul(&self, other: &dyn Value) -> Box<dyn Value> {
+        // Zero times infinity is indeterminate
+        let sign = match other.downcast_ref::<InfinityValue>() {
+            Some(other) => Some(other.sign),
+            None => Self::finite_sign(other),
+        };
+        match sign {
+            Some(sign) => Box::new(Self::new(self.sign ^ sign)),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn div(&self, other: &dyn Value) -> Box<dyn Value> {
+        if other.is::<InfinityValue>() {
+            return Box::new(UndefinedValue::new());
+        }
+        match Self::finite_sign(other) {
+            Some(sign) => Box::new(Self::new(self.sign ^ sign)),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn cmp(&self, other: &dyn Value) -> Option<Ordering> {
+        let ordering = match self.sign {
+            Sign::Positive => Ordering::Greater,
+            Sign::Negative => Ordering::Less,
+        };
+        match other.downcast_ref::<InfinityValue>() {
+            Some(other) if other.sign == self.sign => Some(Ordering::Equal),
+            Some(_) => Some(ordering),
+            None => FloatValue::approximate(other).map(|_| ordering),
+        }
+    }
+
+    fn format(&self, _options: &FormatOptions) -> String {
+        match self.sign {
+            Sign::Positive => "\\infty".to_string(),
+            Sign::Negative => "-\\infty".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        expression::Bindings,
+        parse::{parse, ParserOptions},
+    };
+
+    #[test]
+    fn extended_reals() {
+        let options = ParserOptions::default();
+        let evaluated = |input: &str| {
+            parse(input, &options)
+                .unwrap()
+                .evaluate(&Bindings::new())
+                .to_string()
+        };
+        assert_eq!(evaluated("\\frac{1}{\\infty}"), "0");
+        assert_eq!(evaluated("\\infty - \\infty"), "undefined");
+        assert_eq!(evaluated("0 \\cdot \\infty"), "undefined");
+        assert_eq!(evaluated("2 - 3\\infty"), "-\\infty");
+        assert_eq!(evaluated("\\frac{\\infty}{-2} + 1.5"), "-\\infty");
+        assert_eq!(evaluated("\\infty > 10^{100}"), "true");
+        assert_eq!(evaluated("-\\infty < -\\infty"), "false");
+    }
+}
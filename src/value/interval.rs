@@ -0,0 +1,539 @@
+use std::cmp::Ordering;
+
+use num::{BigRational, BigUint, Signed as _};
+
+use crate::format::FormatOptions;
+
+use super::{
+    ConstantValue, DecimalValue, FloatValue, RationalValue, Sign, SurdValue, UndefinedValue, Value,
+};
+
+/// The relative error allowed for a float square root, a few units in the last place
+const SQRT_ERROR: f64 = 1e-15;
+
+/// One end of an interval
+#[derive(Debug, Clone)]
+pub enum Bound {
+    Unbounded,
+    Open(RationalValue),
+    Closed(RationalValue),
+}
+
+impl Bound {
+    /// Get the value of this bound, if it has one
+    pub fn get_value(&self) -> Option<&RationalValue> {
+        match self {
+            Bound::Unbounded => None,
+            Bound::Open(value) | Bound::Closed(value) => Some(value),
+        }
+    }
+
+    /// Check if this bound includes its value
+    pub fn is_closed(&self) -> bool {
+        matches!(self, Bound::Closed(_))
+    }
+}
+
+/// An interval of rational values
+#[derive(Debug, Clone)]
+pub struct IntervalValue {
+    lower: Bound,
+    upper: Bound,
+}
+
+impl IntervalValue {
+    pub fn new(lower: Bound, upper: Bound) -> Self {
+        Self { lower, upper }
+    }
+
+    /// Construct the interval containing every value
+    pub fn all() -> Self {
+        Self::new(Bound::Unbounded, Bound::Unbounded)
+    }
+
+    /// Construct the interval containing no values
+    pub fn empty() -> Self {
+        Self::new(
+            Bound::Open(RationalValue::zero()),
+            Bound::Open(RationalValue::zero()),
+        )
+    }
+
+    pub fn get_lower(&self) -> &Bound {
+        &self.lower
+    }
+
+    pub fn get_upper(&self) -> &Bound {
+        &self.upper
+    }
+
+    /// Check if this interval contains no values
+    pub fn is_empty(&self) -> bool {
+        match (self.lower.get_value(), self.upper.get_value()) {
+            (Some(lower), Some(upper)) => match lower.cmp(upper) {
+                Some(Ordering::Greater) => true,
+                Some(Ordering::Equal) => !(self.lower.is_closed() && self.upper.is_closed()),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Check if this interval contains a value
+    pub fn contains(&self, value: &RationalValue) -> bool {
+        let above_lower = match &self.lower {
+            Bound::Unbounded => true,
+            Bound::Open(lower) => value.cmp(lower) == Some(Ordering::Greater),
+            Bound::Closed(lower) => value.cmp(lower) != Some(Ordering::Less),
+        };
+        let below_upper = match &self.upper {
+            Bound::Unbounded => true,
+            Bound::Open(upper) => value.cmp(upper) == Some(Ordering::Less),
+            Bound::Closed(upper) => value.cmp(upper) != Some(Ordering::Greater),
+        };
+        above_lower && below_upper
+    }
+
+    /// Construct the interval containing only a single value
+    pub fn point(value: RationalValue) -> Self {
+        Self::new(Bound::Closed(value.clone()), Bound::Closed(value))
+    }
+
+    /// Construct a closed interval around a float, rounded outwards by a relative error
+    ///
+    /// Returns `None` if the value isn't finite
+    fn enclosing(value: f64, relative_error: f64) -> Option<Self> {
+        let margin = value.abs() * relative_error;
+        let bound = |value: f64| {
+            let value = BigRational::from_float(value)?;
+            let sign = if value.is_negative() {
+                Sign::Negative
+            } else {
+                Sign::Positive
+            };
+            Some(Bound::Closed(RationalValue::new(
+                sign,
+                value.numer().magnitude().clone(),
+                value.denom().magnitude().clone(),
+            )))
+        };
+        Some(Self::new(
+            bound((value - margin).next_down())?,
+            bound((value + margin).next_up())?,
+        ))
+    }
+
+    /// Construct an interval containing a multiple of a constant, from its known digits
+    fn enclosing_constant(value: &ConstantValue) -> Self {
+        let (expansion, digits) = value.get_constant().expansion();
+        let error = RationalValue::new(
+            Sign::Positive,
+            1_u32,
+            BigUint::from(10_u32).pow(digits as u32),
+        );
+        let constant = IntervalValue::new(
+            Bound::Closed(combine(&expansion, &error, RationalValue::sub)),
+            Bound::Closed(combine(&expansion, &error, RationalValue::add)),
+        );
+        IntervalValue::point(value.get_coefficient().clone()).mul_interval(&constant)
+    }
+
+    /// Construct an interval containing a quadratic surd, checking the bounds of its square root
+    ///
+    /// Returns `None` if the radicand is too large to approximate
+    fn enclosing_surd(value: &SurdValue) -> Option<Self> {
+        let radicand = RationalValue::new(Sign::Positive, value.get_radicand().clone(), 1_u32);
+        let root =
+            IntervalValue::enclosing(FloatValue::approximate(&radicand)?.sqrt(), SQRT_ERROR)?;
+
+        // The float square root is only trusted once its bounds are squared exactly
+        let square = |bound: &Bound| {
+            let value = bound.get_value().unwrap();
+            combine(value, value, RationalValue::mul).cmp(&radicand)
+        };
+        if square(&root.lower) == Some(Ordering::Greater)
+            || square(&root.upper) == Some(Ordering::Less)
+        {
+            return None;
+        }
+        Some(
+            IntervalValue::point(value.get_rational().clone()).add_interval(
+                &IntervalValue::point(value.get_coefficient().clone()).mul_interval(&root),
+            ),
+        )
+    }
+
+    /// Check if this interval is bounded on both ends
+    pub fn is_bounded(&self) -> bool {
+        self.lower.get_value().is_some() && self.upper.get_value().is_some()
+    }
+
+    /// Get the smallest interval containing both this interval and another
+    pub fn hull(&self, other: &IntervalValue) -> IntervalValue {
+        if self.is_empty() {
+            return other.clone();
+        }
+        if other.is_empty() {
+            return self.clone();
+        }
+        IntervalValue::new(
+            looser_bound(&self.lower, &other.lower, Ordering::Less),
+            looser_bound(&self.upper, &other.upper, Ordering::Greater),
+        )
+    }
+
+    /// Split this interval into a number of equally sized pieces
+    ///
+    /// Unbounded intervals are returned whole
+    pub fn subdivided(&self, pieces: usize) -> Vec<IntervalValue> {
+        let (Some(lower), Some(upper)) = (self.lower.get_value(), self.upper.get_value()) else {
+            return vec![self.clone()];
+        };
+        if pieces <= 1 || self.is_empty() {
+            return vec![self.clone()];
+        }
+        let width = combine(upper, lower, RationalValue::sub);
+        let step = combine(
+            &width,
+            &RationalValue::new(Sign::Positive, pieces, 1_u32),
+            RationalValue::div,
+        );
+
+        // Cut at evenly spaced points, keeping the original bounds on the outer pieces
+        let mut cuts = vec![lower.clone()];
+        for _ in 1..pieces {
+            let next = combine(cuts.last().unwrap(), &step, RationalValue::add);
+            cuts.push(next);
+        }
+        (0..pieces)
+            .map(|index| {
+                let piece_lower = if index == 0 {
+                    self.lower.clone()
+                } else {
+                    Bound::Closed(cuts[index].clone())
+                };
+                let piece_upper = if index == pieces - 1 {
+                    self.upper.clone()
+                } else {
+                    Bound::Closed(cuts[index + 1].clone())
+                };
+                IntervalValue::new(piece_lower, piece_upper)
+            })
+            .collect()
+    }
+
+    /// Get the interval of reciprocals of this interval, if it does not touch zero
+    fn reciprocal(&self) -> Option<IntervalValue> {
+        // Only intervals lying entirely on one side of zero have bounded reciprocals
+        let zero = RationalValue::zero();
+        let positive = self
+            .lower
+            .get_value()
+            .is_some_and(|lower| lower.cmp(&zero) == Some(Ordering::Greater));
+        let negative = self
+            .upper
+            .get_value()
+            .is_some_and(|upper| upper.cmp(&zero) == Some(Ordering::Less));
+        if !positive && !negative {
+            return None;
+        }
+        let invert = |bound: &Bound| match bound {
+            Bound::Unbounded => Bound::Open(RationalValue::zero()),
+            Bound::Open(value) => Bound::Open(value.get_reciprocal()),
+            Bound::Closed(value) => Bound::Closed(value.get_reciprocal()),
+        };
+        Some(IntervalValue::new(invert(&self.upper), invert(&self.lower)))
+    }
+
+    /// Add another interval to this interval
+    fn add_interval(&self, other: &IntervalValue) -> IntervalValue {
+        IntervalValue::new(
+            combine_bounds(&self.lower, &other.lower, RationalValue::add),
+            combine_bounds(&self.upper, &other.upper, RationalValue::add),
+        )
+    }
+
+    /// Subtract another interval from this interval
+    fn sub_interval(&self, other: &IntervalValue) -> IntervalValue {
+        IntervalValue::new(
+            combine_bounds(&self.lower, &other.upper, RationalValue::sub),
+            combine_bounds(&self.upper, &other.lower, RationalValue::sub),
+        )
+    }
+
+    /// Multiply this interval by another interval
+    fn mul_interval(&self, other: &IntervalValue) -> IntervalValue {
+        if !self.is_bounded() || !other.is_bounded() {
+            return IntervalValue::all();
+        }
+
+        // The extremes of a product are among the products of the endpoints
+        let candidates = [&self.lower, &self.upper]
+            .into_iter()
+            .flat_map(|lhs| {
+                [&other.lower, &other.upper]
+                    .into_iter()
+                    .map(move |rhs| combine_bounds(lhs, rhs, RationalValue::mul))
+            })
+            .collect::<Vec<_>>();
+        let mut lower = candidates[0].clone();
+        let mut upper = candidates[0].clone();
+        for candidate in &candidates[1..] {
+            lower = looser_bound(&lower, candidate, Ordering::Less);
+            upper = looser_bound(&upper, candidate, Ordering::Greater);
+        }
+        IntervalValue::new(lower, upper)
+    }
+
+    /// Get the interval of values contained in both this interval and another
+    pub fn intersection(&self, other: &IntervalValue) -> IntervalValue {
+        let interval = IntervalValue::new(
+            tighter_bound(&self.lower, &other.lower, Ordering::Greater),
+            tighter_bound(&self.upper, &other.upper, Ordering::Less),
+        );
+        if interval.is_empty() {
+            IntervalValue::empty()
+        } else {
+            interval
+        }
+    }
+}
+
+/// Pick whichever bound is further in the given direction, preferring open bounds on ties
+fn tighter_bound(lhs: &Bound, rhs: &Bound, direction: Ordering) -> Bound {
+    match (lhs.get_value(), rhs.get_value()) {
+        (None, _) => rhs.clone(),
+        (_, None) => lhs.clone(),
+        (Some(lhs_value), Some(rhs_value)) => match lhs_value.cmp(rhs_value) {
+            Some(Ordering::Equal) if lhs.is_closed() => rhs.clone(),
+            Some(Ordering::Equal) => lhs.clone(),
+            Some(ordering) if ordering == direction => lhs.clone(),
+            _ => rhs.clone(),
+        },
+    }
+}
+
+/// Pick whichever bound is further in the given direction, preferring closed bounds on ties
+fn looser_bound(lhs: &Bound, rhs: &Bound, direction: Ordering) -> Bound {
+    match (lhs.get_value(), rhs.get_value()) {
+        (None, _) => lhs.clone(),
+        (_, None) => rhs.clone(),
+        (Some(lhs_value), Some(rhs_value)) => match lhs_value.cmp(rhs_value) {
+            Some(Ordering::Equal) if lhs.is_closed() => lhs.clone(),
+            Some(Ordering::Equal) => rhs.clone(),
+            Some(ordering) if ordering == direction => lhs.clone(),
+            _ => rhs.clone(),
+        },
+    }
+}
+
+/// Apply a rational operation to two rational values
+fn combine(
+    lhs: &RationalValue,
+    rhs: &RationalValue,
+    operation: fn(&RationalValue, &dyn Value) -> Box<dyn Value>,
+) -> RationalValue {
+    operation(lhs, rhs)
+        .downcast_ref::<RationalValue>()
+        .cloned()
+        .expect("Unexpected error: rational arithmetic didn't yield a rational value!")
+}
+
+/// Apply a rational operation to two bounds, keeping the result closed only if both are closed
+fn combine_bounds(
+    lhs: &Bound,
+    rhs: &Bound,
+    operation: fn(&RationalValue, &dyn Value) -> Box<dyn Value>,
+) -> Bound {
+    match (lhs, rhs) {
+        (Bound::Closed(lhs), Bound::Closed(rhs)) => Bound::Closed(combine(lhs, rhs, operation)),
+        (Bound::Unbounded, _) | (_, Bound::Unbounded) => Bound::Unbounded,
+        (lhs, rhs) => Bound::Open(combine(
+            lhs.get_value().unwrap(),
+            rhs.get_value().unwrap(),
+            operation,
+        )),
+    }
+}
+
+/// View a value as an interval, enclosing irrational values in an interval around them
+///
+/// Floats carry an unknown error from however they were computed, so they can't be enclosed
+fn as_interval(value: &dyn Value) -> Option<IntervalValue> {
+    if let Some(value) = value.downcast_ref::<IntervalValue>() {
+        Some(value.clone())
+    } else if let Some(value) = value.downcast_ref::<RationalValue>() {
+        Some(IntervalValue::point(value.clone()))
+    } else if let Some(value) = value.downcast_ref::<DecimalValue>() {
+        Some(IntervalValue::point(value.to_rational()))
+    } else if let Some(value) = value.downcast_ref::<ConstantValue>() {
+        Some(IntervalValue::enclosing_constant(value))
+    } else {
+        IntervalValue::enclosing_surd(value.downcast_ref::<SurdValue>()?)
+    }
+}
+
+impl Value for IntervalValue {
+    fn add(&self, other: &dyn Value) -> Box<dyn Value> {
+        match as_interval(other) {
+            Some(other) if self.is_empty() || other.is_empty() => Box::new(IntervalValue::empty()),
+            Some(other) => Box::new(self.add_interval(&other)),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn sub(&self, other: &dyn Value) -> Box<dyn Value> {
+        match as_interval(other) {
+            Some(other) if self.is_empty() || other.is_empty() => Box::new(IntervalValue::empty()),
+            Some(other) => Box::new(self.sub_interval(&other)),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn mul(&self, other: &dyn Value) -> Box<dyn Value> {
+        match as_interval(other) {
+            Some(other) if self.is_empty() || other.is_empty() => Box::new(IntervalValue::empty()),
+            Some(other) => Box::new(self.mul_interval(&other)),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn div(&self, other: &dyn Value) -> Box<dyn Value> {
+        match as_interval(other).and_then(|other| other.reciprocal()) {
+            Some(_) if self.is_empty() => Box::new(IntervalValue::empty()),
+            Some(reciprocal) => Box::new(self.mul_interval(&reciprocal)),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn cmp(&self, other: &dyn Value) -> Option<Ordering> {
+        let other = as_interval(other)?;
+
+        // Intervals are only ordered when they cannot overlap
+        let separated = |lhs: &IntervalValue, rhs: &IntervalValue| match (
+            lhs.upper.get_value(),
+            rhs.lower.get_value(),
+        ) {
+            (Some(upper), Some(lower)) => match upper.cmp(lower) {
+                Some(Ordering::Less) => true,
+                Some(Ordering::Equal) => !(lhs.upper.is_closed() && rhs.lower.is_closed()),
+                _ => false,
+            },
+            _ => false,
+        };
+        if separated(self, &other) {
+            Some(Ordering::Less)
+        } else if separated(&other, self) {
+            Some(Ordering::Greater)
+        } else {
+            match (&self.lower, &self.upper, &other.lower, &other.upper) {
+                (
+                    Bound::Closed(lhs_lower),
+                    Bound::Closed(lhs_upper),
+                    Bound::Closed(rhs_lower),
+                    Bound::Closed(rhs_upper),
+                ) if [lhs_upper, rhs_lower, rhs_upper]
+                    .iter()
+                    .all(|value| lhs_lower.cmp(*value) == Some(Ordering::Equal)) =>
+                {
+                    Some(Ordering::Equal)
+                }
+                _ => None,
+            }
+        }
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        if self.is_empty() {
+            return "\\emptyset".to_string();
+        }
+        let lower = match &self.lower {
+            Bound::Unbounded => "(-\\infty".to_string(),
+            Bound::Open(value) => format!("({}", value.format(options)),
+            Bound::Closed(value) => format!("[{}", value.format(options)),
+        };
+        let upper = match &self.upper {
+            Bound::Unbounded => "\\infty)".to_string(),
+            Bound::Open(value) => format!("{})", value.format(options)),
+            Bound::Closed(value) => format!("{}]", value.format(options)),
+        };
+        format!(
+            "{}{} {}",
+            lower,
+            options.get_locale().argument_separator(),
+            upper
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parse::{parse_latex, parse_pairs},
+        value::{
+            Constant, ConstantValue, FloatValue, RationalValue, SurdValue, UndefinedValue, Value,
+        },
+    };
+
+    use super::{Bound, IntervalValue, SQRT_ERROR};
+
+    fn closed(lower: i32, upper: i32) -> IntervalValue {
+        IntervalValue::new(
+            Bound::Closed(lower.to_string().parse::<RationalValue>().unwrap()),
+            Bound::Closed(upper.to_string().parse::<RationalValue>().unwrap()),
+        )
+    }
+
+    #[test]
+    fn bounds() {
+        let expression = parse_pairs(parse_latex("x * x - 2x").unwrap());
+        assert_eq!(
+            expression.bounds("x", &closed(0, 2)).unwrap().to_string(),
+            "[-4, 4]"
+        );
+        assert_eq!(
+            expression
+                .subdivided_bounds("x", &closed(0, 2), 4)
+                .unwrap()
+                .to_string(),
+            "[-2, 1]"
+        );
+    }
+
+    #[test]
+    fn irrational_values() {
+        // Constants are enclosed by the digits they're known to
+        let pi = ConstantValue::from(Constant::pi());
+        let product = closed(1, 2).mul(&pi);
+        let product = product.downcast_ref::<IntervalValue>().unwrap();
+        assert!(product.contains(&"3.1415926535897932385".parse().unwrap()));
+        assert!(product.contains(&"6.28318530717958647692".parse().unwrap()));
+        assert!(!product.contains(&"3.14159265358979".parse().unwrap()));
+
+        // Surds are enclosed by a square root whose bounds are checked exactly
+        let root = SurdValue::sqrt(&"2".parse().unwrap()).unwrap();
+        let sum = closed(1, 2).add(root.as_ref());
+        let sum = sum.downcast_ref::<IntervalValue>().unwrap();
+        assert!(sum.contains(&"2.41421356237309504880".parse().unwrap()));
+        assert!(!sum.contains(&"2.414213562373".parse().unwrap()));
+
+        // Floats carry an unknown error, so they can't be enclosed
+        assert!(FloatValue::new(0.1)
+            .add(&closed(1, 2))
+            .is::<UndefinedValue>());
+        assert!(closed(1, 2)
+            .mul(&FloatValue::new(0.1))
+            .is::<UndefinedValue>());
+        assert!(IntervalValue::enclosing(f64::NAN, SQRT_ERROR).is_none());
+    }
+
+    #[test]
+    fn division_by_interval_containing_zero() {
+        let expression = parse_pairs(parse_latex("1 / x").unwrap());
+        assert!(expression.bounds("x", &closed(-1, 1)).is_none());
+        assert_eq!(
+            expression.bounds("x", &closed(1, 2)).unwrap().to_string(),
+            "[1/2, 1]"
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use std::cmp::Ordering;
+
+use crate::format::FormatOptions;
+
+use super::{Bound, IntervalValue, RationalValue, UndefinedValue, Value};
+
+/// A union of disjoint intervals of rational values
+#[derive(Debug, Clone)]
+pub struct IntervalUnionValue {
+    intervals: Vec<IntervalValue>,
+}
+
+impl IntervalUnionValue {
+    /// Construct a union of intervals, merging any that overlap
+    pub fn new(intervals: Vec<IntervalValue>) -> Self {
+        let mut intervals = intervals
+            .into_iter()
+            .filter(|interval| !interval.is_empty())
+            .collect::<Vec<_>>();
+        intervals.sort_by(|lhs, rhs| compare_lower(lhs.get_lower(), rhs.get_lower()));
+
+        // Merge neighbouring intervals that overlap or touch
+        let mut merged: Vec<IntervalValue> = Vec::new();
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if touches(last, &interval) => {
+                    let upper = match compare_upper(last.get_upper(), interval.get_upper()) {
+                        Ordering::Less => interval.get_upper().clone(),
+                        _ => last.get_upper().clone(),
+                    };
+                    *last = IntervalValue::new(last.get_lower().clone(), upper);
+                }
+                _ => merged.push(interval),
+            }
+        }
+        Self { intervals: merged }
+    }
+
+    /// Construct the union containing every value
+    pub fn all() -> Self {
+        Self::new(vec![IntervalValue::all()])
+    }
+
+    /// Construct the union containing no values
+    pub fn empty() -> Self {
+        Self::new(Vec::new())
+    }
+
+    pub fn get_intervals(&self) -> &[IntervalValue] {
+        &self.intervals
+    }
+
+    /// Check if this union contains no values
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Check if this union contains every value
+    pub fn is_all(&self) -> bool {
+        matches!(
+            self.intervals.as_slice(),
+            [interval] if matches!(
+                (interval.get_lower(), interval.get_upper()),
+                (Bound::Unbounded, Bound::Unbounded)
+            )
+        )
+    }
+
+    /// Check if this union contains a value
+    pub fn contains(&self, value: &RationalValue) -> bool {
+        self.intervals
+            .iter()
+            .any(|interval| interval.contains(value))
+    }
+
+    /// Get the union of values contained in both this union and another
+    pub fn intersection(&self, other: &IntervalUnionValue) -> IntervalUnionValue {
+        IntervalUnionValue::new(
+            self.intervals
+                .iter()
+                .flat_map(|lhs| other.intervals.iter().map(|rhs| lhs.intersection(rhs)))
+                .collect(),
+        )
+    }
+
+    /// Get the union of values contained in either this union or another
+    pub fn union(&self, other: &IntervalUnionValue) -> IntervalUnionValue {
+        IntervalUnionValue::new(
+            self.intervals
+                .iter()
+                .chain(other.intervals.iter())
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Remove a single value from this union
+    pub fn excluding(&self, value: &RationalValue) -> IntervalUnionValue {
+        IntervalUnionValue::new(
+            self.intervals
+                .iter()
+                .flat_map(|interval| {
+                    if interval.contains(value) {
+                        vec![
+                            IntervalValue::new(
+                                interval.get_lower().clone(),
+                                Bound::Open(value.clone()),
+                            ),
+                            IntervalValue::new(
+                                Bound::Open(value.clone()),
+                                interval.get_upper().clone(),
+                            ),
+                        ]
+                    } else {
+                        vec![interval.clone()]
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+impl From<IntervalValue> for IntervalUnionValue {
+    fn from(value: IntervalValue) -> Self {
+        Self::new(vec![value])
+    }
+}
+
+/// Order lower bounds, with closed bounds coming before open bounds at the same value
+fn compare_lower(lhs: &Bound, rhs: &Bound) -> Ordering {
+    match (lhs.get_value(), rhs.get_value()) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (Some(lhs_value), Some(rhs_value)) => lhs_value
+            .cmp(rhs_value)
+            .unwrap_or(Ordering::Equal)
+            .then(rhs.is_closed().cmp(&lhs.is_closed())),
+    }
+}
+
+/// Order upper bounds, with closed bounds coming after open bounds at the same value
+fn compare_upper(lhs: &Bound, rhs: &Bound) -> Ordering {
+    match (lhs.get_value(), rhs.get_value()) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(lhs_value), Some(rhs_value)) => lhs_value
+            .cmp(rhs_value)
+            .unwrap_or(Ordering::Equal)
+            .then(lhs.is_closed().cmp(&rhs.is_closed())),
+    }
+}
+
+/// Check if an interval overlaps or touches another interval that starts after it
+fn touches(first: &IntervalValue, second: &IntervalValue) -> bool {
+    match (
+        first.get_upper().get_value(),
+        second.get_lower().get_value(),
+    ) {
+        (None, _) | (_, None) => true,
+        (Some(upper), Some(lower)) => match upper.cmp(lower) {
+            Some(Ordering::Greater) => true,
+            Some(Ordering::Equal) => {
+                first.get_upper().is_closed() || second.get_lower().is_closed()
+            }
+            _ => false,
+        },
+    }
+}
+
+impl Value for IntervalUnionValue {
+    fn add(&self, _other: &dyn Value) -> Box<dyn Value> {
+        Box::new(UndefinedValue::new())
+    }
+
+    fn sub(&self, _other: &dyn Value) -> Box<dyn Value> {
+        Box::new(UndefinedValue::new())
+    }
+
+    fn mul(&self, _other: &dyn Value) -> Box<dyn Value> {
+        Box::new(UndefinedValue::new())
+    }
+
+    fn div(&self, _other: &dyn Value) -> Box<dyn Value> {
+        Box::new(UndefinedValue::new())
+    }
+
+    fn cmp(&self, _other: &dyn Value) -> Option<Ordering> {
+        None
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        if self.is_empty() {
+            "\\emptyset".to_string()
+        } else {
+            self.intervals
+                .iter()
+                .map(|interval| interval.format(options))
+                .collect::<Vec<_>>()
+                .join(" \\cup ")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parse::{parse_latex, parse_pairs},
+        value::Value,
+    };
+
+    #[test]
+    fn domain_excludes_zero_denominators() {
+        let expression = parse_pairs(parse_latex("1 / (x - 2) + 3 / x").unwrap());
+        assert_eq!(
+            expression.domain("x").unwrap().to_string(),
+            "(-\\infty, 0) \\cup (0, 2) \\cup (2, \\infty)"
+        );
+
+        // Denominators that aren't linear are solved for their zeros too
+        let domain = |input: &str| {
+            parse_pairs(parse_latex(input).unwrap())
+                .domain("x")
+                .map(|domain| domain.to_string())
+        };
+        assert_eq!(
+            domain("\\frac{1}{x^2 - 1}").as_deref(),
+            Some("(-\\infty, -1) \\cup (-1, 1) \\cup (1, \\infty)")
+        );
+        assert_eq!(
+            domain("\\frac{x}{x^2 + 1}").as_deref(),
+            Some("(-\\infty, \\infty)")
+        );
+        assert_eq!(
+            domain("\\frac{x}{\\pi}").as_deref(),
+            Some("(-\\infty, \\infty)")
+        );
+        assert_eq!(domain("\\frac{1}{x^2 - 2}"), None);
+    }
+}
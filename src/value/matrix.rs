@@ -0,0 +1,247 @@
+use std::cmp::Ordering;
+
+use crate::format::FormatOptions;
+
+use super::{rational::rational, RationalValue, UndefinedValue, Value, VectorValue};
+
+/// A matrix of exact rational entries
+#[derive(Debug, Clone)]
+pub struct MatrixValue {
+    rows: Vec<Vec<RationalValue>>,
+}
+
+impl MatrixValue {
+    /// Construct a new matrix value from its rows, which must all have the same length
+    pub fn new(rows: Vec<Vec<RationalValue>>) -> Self {
+        assert!(
+            rows.windows(2).all(|rows| rows[0].len() == rows[1].len()),
+            "The rows of a matrix must all have the same length!"
+        );
+        Self { rows }
+    }
+
+    pub fn get_rows(&self) -> &[Vec<RationalValue>] {
+        &self.rows
+    }
+
+    /// Get the number of rows and columns of this matrix
+    pub fn get_shape(&self) -> (usize, usize) {
+        (self.rows.len(), self.rows.first().map_or(0, Vec::len))
+    }
+
+    /// Multiply each entry of this matrix by a rational value
+    pub fn scaled(&self, scalar: &RationalValue) -> Self {
+        Self::new(
+            self.rows
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|entry| rational(entry.mul(scalar)))
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    /// Combine two matrices of the same shape entry by entry, or `None` if their shapes differ
+    fn entrywise(
+        &self,
+        other: &MatrixValue,
+        operation: impl Fn(&RationalValue, &RationalValue) -> Box<dyn Value>,
+    ) -> Option<Self> {
+        if self.get_shape() != other.get_shape() {
+            return None;
+        }
+        Some(Self::new(
+            self.rows
+                .iter()
+                .zip(&other.rows)
+                .map(|(lhs, rhs)| {
+                    lhs.iter()
+                        .zip(rhs)
+                        .map(|(lhs, rhs)| rational(operation(lhs, rhs)))
+                        .collect()
+                })
+                .collect(),
+        ))
+    }
+
+    /// Multiply this matrix by another, or `None` if the columns of this matrix don't match the
+    /// rows of the other
+    fn product(&self, other: &MatrixValue) -> Option<Self> {
+        let ((rows, inner), (other_rows, columns)) = (self.get_shape(), other.get_shape());
+        if inner != other_rows {
+            return None;
+        }
+        Some(Self::new(
+            (0..rows)
+                .map(|row| {
+                    (0..columns)
+                        .map(|column| {
+                            (0..inner).fold(RationalValue::zero(), |sum, k| {
+                                let term = self.rows[row][k].mul(&other.rows[k][column]);
+                                rational(sum.add(term.as_ref()))
+                            })
+                        })
+                        .collect()
+                })
+                .collect(),
+        ))
+    }
+
+    /// Multiply this matrix by a column vector, or `None` if the columns of this matrix don't
+    /// match the length of the vector
+    pub fn apply(&self, vector: &VectorValue) -> Option<VectorValue> {
+        self.rows
+            .iter()
+            .map(|row| VectorValue::new(row.clone()).dot(vector))
+            .collect::<Option<_>>()
+            .map(VectorValue::new)
+    }
+}
+
+/// Box a result, which is undefined if there isn't one
+fn defined<T: Value>(value: Option<T>) -> Box<dyn Value> {
+    match value {
+        Some(value) => Box::new(value),
+        None => Box::new(UndefinedValue::new()),
+    }
+}
+
+impl Value for MatrixValue {
+    fn add(&self, other: &dyn Value) -> Box<dyn Value> {
+        defined(
+            other
+                .downcast_ref::<MatrixValue>()
+                .and_then(|other| self.entrywise(other, |lhs, rhs| lhs.add(rhs))),
+        )
+    }
+
+    fn sub(&self, other: &dyn Value) -> Box<dyn Value> {
+        defined(
+            other
+                .downcast_ref::<MatrixValue>()
+                .and_then(|other| self.entrywise(other, |lhs, rhs| lhs.sub(rhs))),
+        )
+    }
+
+    fn mul(&self, other: &dyn Value) -> Box<dyn Value> {
+        if let Some(other) = other.downcast_ref::<MatrixValue>() {
+            defined(self.product(other))
+        } else if let Some(vector) = other.downcast_ref::<VectorValue>() {
+            defined(self.apply(vector))
+        } else if let Some(scalar) = other.downcast_ref::<RationalValue>() {
+            Box::new(self.scaled(scalar))
+        } else {
+            Box::new(UndefinedValue::new())
+        }
+    }
+
+    fn div(&self, other: &dyn Value) -> Box<dyn Value> {
+        // Only dividing by a scalar is defined
+        match other.downcast_ref::<RationalValue>() {
+            Some(scalar) if !scalar.is_zero() => Box::new(self.scaled(&scalar.get_reciprocal())),
+            _ => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn cmp(&self, other: &dyn Value) -> Option<Ordering> {
+        // Matrices aren't ordered, but equal matrices are still equal
+        let other = other.downcast_ref::<MatrixValue>()?;
+        let equal = self.get_shape() == other.get_shape()
+            && self
+                .rows
+                .iter()
+                .flatten()
+                .zip(other.rows.iter().flatten())
+                .all(|(lhs, rhs)| lhs.cmp(rhs) == Some(Ordering::Equal));
+        equal.then_some(Ordering::Equal)
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|entry| entry.format(options))
+                    .collect::<Vec<_>>()
+                    .join(" & ")
+            })
+            .collect::<Vec<_>>()
+            .join(" \\\\ ");
+        format!("\\begin{{pmatrix}} {} \\end{{pmatrix}}", rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use num::BigInt;
+
+    use crate::{
+        expression::Bindings,
+        parse::{parse, ParserOptions},
+        value::{RationalValue, Sign, Value},
+    };
+
+    use super::MatrixValue;
+
+    #[test]
+    fn matrix_values() {
+        let integer = |value: i32| RationalValue::from(BigInt::from(value));
+        let matrix = |rows: &[&[i32]]| {
+            MatrixValue::new(
+                rows.iter()
+                    .map(|row| row.iter().map(|&entry| integer(entry)).collect())
+                    .collect(),
+            )
+        };
+        let square = matrix(&[&[1, 2], &[3, 4]]);
+        let column = matrix(&[&[1], &[-1]]);
+        assert_eq!(square.get_shape(), (2, 2));
+        assert_eq!(
+            square.to_string(),
+            "\\begin{pmatrix} 1 & 2 \\\\ 3 & 4 \\end{pmatrix}"
+        );
+        assert_eq!(
+            square.add(&square).to_string(),
+            "\\begin{pmatrix} 2 & 4 \\\\ 6 & 8 \\end{pmatrix}"
+        );
+        assert_eq!(
+            square.mul(&column).to_string(),
+            "\\begin{pmatrix} -1 \\\\ -1 \\end{pmatrix}"
+        );
+        assert_eq!(
+            square
+                .div(&RationalValue::new(Sign::Positive, 2_u32, 1_u32))
+                .to_string(),
+            "\\begin{pmatrix} 1/2 & 1 \\\\ 3/2 & 2 \\end{pmatrix}"
+        );
+        assert_eq!(
+            integer(-1).mul(&column).to_string(),
+            "\\begin{pmatrix} -1 \\\\ 1 \\end{pmatrix}"
+        );
+        assert_eq!(
+            square.sub(&square).cmp(&matrix(&[&[0, 0], &[0, 0]])),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(square.cmp(&column), None);
+
+        // Mismatched shapes are undefined
+        assert_eq!(square.add(&column).to_string(), "undefined");
+        assert_eq!(column.mul(&column).to_string(), "undefined");
+        assert_eq!(square.div(&RationalValue::zero()).to_string(), "undefined");
+
+        // Matrices of rational entries evaluate to matrix values
+        let options = ParserOptions::default();
+        let value = parse("\\begin{bmatrix} 1/2 & x \\end{bmatrix}", &options)
+            .unwrap()
+            .evaluate(&Bindings::from([(
+                "x".to_string(),
+                Box::new(integer(3)) as Box<dyn Value>,
+            )]));
+        assert_eq!(value.to_string(), "\\begin{pmatrix} 1/2 & 3 \\end{pmatrix}");
+    }
+}
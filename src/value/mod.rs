@@ -4,11 +4,41 @@ use downcast_rs::{impl_downcast, Downcast};
 
 use dyn_clone::{clone_trait_object, DynClone};
 
-pub use rational::RationalValue;
+use crate::format::FormatOptions;
+
+pub use boolean::BooleanValue;
+pub use complex::ComplexValue;
+pub use constant::{Constant, ConstantValue};
+pub use decimal::{DecimalValue, DEFAULT_PRECISION};
+pub use float::FloatValue;
+pub use infinity::InfinityValue;
+pub use interval::{Bound, IntervalValue};
+pub use interval_union::IntervalUnionValue;
+pub use matrix::MatrixValue;
+pub use modular::ModularValue;
+pub use polynomial::PolynomialValue;
+pub use quantity::{QuantityValue, Unit};
+pub use rational::{RationalValue, Sign};
+pub use surd::SurdValue;
 pub use undefined::UndefinedValue;
+pub use vector::VectorValue;
 
+mod boolean;
+mod complex;
+mod constant;
+mod decimal;
+mod float;
+mod infinity;
+mod interval;
+mod interval_union;
+mod matrix;
+mod modular;
+mod polynomial;
+mod quantity;
 mod rational;
+mod surd;
 mod undefined;
+mod vector;
 
 /// The root trait for all values
 pub trait Value: Downcast + DynClone + Debug {
@@ -27,8 +57,13 @@ pub trait Value: Downcast + DynClone + Debug {
     /// Compare this value to another value
     fn cmp(&self, other: &dyn Value) -> Option<Ordering>;
 
+    /// Get a string representation of this value, written according to some format options
+    fn format(&self, options: &FormatOptions) -> String;
+
     /// Get a string representation of this value
-    fn to_string(&self) -> String;
+    fn to_string(&self) -> String {
+        self.format(&FormatOptions::default())
+    }
 }
 impl_downcast!(Value);
 clone_trait_object!(Value);
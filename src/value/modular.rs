@@ -0,0 +1,179 @@
+use std::cmp::Ordering;
+
+use num::{BigInt, BigUint, Integer as _, One as _, Zero as _};
+
+use crate::format::FormatOptions;
+
+use super::{RationalValue, UndefinedValue, Value};
+
+/// A residue class of the integers modulo some positive modulus, like `3 \pmod{7}`
+#[derive(Debug, Clone)]
+pub struct ModularValue {
+    value: BigUint,
+    modulus: BigUint,
+}
+
+impl ModularValue {
+    /// Construct the residue class of an integer modulo a modulus
+    ///
+    /// Returns `None` if the modulus is zero
+    pub fn new(value: impl Into<BigInt>, modulus: impl Into<BigUint>) -> Option<Self> {
+        let modulus = modulus.into();
+        if modulus.is_zero() {
+            return None;
+        }
+        let residue = Self {
+            value: BigUint::zero(),
+            modulus,
+        };
+        Some(residue.with_value(value.into()))
+    }
+
+    /// Get the residue class of an integer with the same modulus as this one
+    fn with_value(&self, value: BigInt) -> Self {
+        let value = value
+            .mod_floor(&BigInt::from(self.modulus.clone()))
+            .to_biguint()
+            .expect("Unexpected error: a value reduced by a positive modulus was negative!");
+        Self {
+            value,
+            modulus: self.modulus.clone(),
+        }
+    }
+
+    /// Get the least non-negative value in this residue class
+    pub fn get_value(&self) -> &BigUint {
+        &self.value
+    }
+
+    /// Get the modulus of this residue class, which is always positive
+    pub fn get_modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    /// Get the multiplicative inverse of this residue class, or `None` if it isn't coprime to the
+    /// modulus
+    pub fn get_inverse(&self) -> Option<Self> {
+        let value = BigInt::from(self.value.clone());
+        let modulus = BigInt::from(self.modulus.clone());
+        let gcd = value.extended_gcd(&modulus);
+        gcd.gcd.is_one().then(|| self.with_value(gcd.x))
+    }
+
+    /// Get the residue class of an integer with the same modulus as this one, which is undefined
+    /// if the value isn't an integer
+    pub fn residue(&self, value: &RationalValue) -> Box<dyn Value> {
+        match value.to_integer() {
+            Some(integer) => Box::new(self.with_value(integer)),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    /// Get a value as a residue class with the same modulus, if it is an integer or such a class
+    fn matching(&self, value: &dyn Value) -> Option<Self> {
+        if let Some(value) = value.downcast_ref::<ModularValue>() {
+            (value.modulus == self.modulus).then(|| value.clone())
+        } else {
+            let integer = value.downcast_ref::<RationalValue>()?.to_integer()?;
+            Some(self.with_value(integer))
+        }
+    }
+
+    /// Combine this residue class with another value of the same modulus
+    fn combine(
+        &self,
+        other: &dyn Value,
+        operation: impl Fn(BigInt, BigInt) -> BigInt,
+    ) -> Box<dyn Value> {
+        match self.matching(other) {
+            Some(other) => Box::new(self.with_value(operation(
+                BigInt::from(self.value.clone()),
+                BigInt::from(other.value),
+            ))),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+}
+
+impl Value for ModularValue {
+    fn add(&self, other: &dyn Value) -> Box<dyn Value> {
+        self.combine(other, |lhs, rhs| lhs + rhs)
+    }
+
+    fn sub(&self, other: &dyn Value) -> Box<dyn Value> {
+        self.combine(other, |lhs, rhs| lhs - rhs)
+    }
+
+    fn mul(&self, other: &dyn Value) -> Box<dyn Value> {
+        self.combine(other, |lhs, rhs| lhs * rhs)
+    }
+
+    fn div(&self, other: &dyn Value) -> Box<dyn Value> {
+        // Dividing multiplies by the inverse, which only exists for classes coprime to the modulus
+        match self.matching(other).and_then(|other| other.get_inverse()) {
+            Some(inverse) => self.mul(&inverse),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn cmp(&self, other: &dyn Value) -> Option<Ordering> {
+        // Residue classes aren't ordered, but the same class is still equal
+        let other = self.matching(other)?;
+        (other.value == self.value).then_some(Ordering::Equal)
+    }
+
+    fn format(&self, _options: &FormatOptions) -> String {
+        format!("{} \\pmod{{{}}}", self.value, self.modulus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use num::BigInt;
+
+    use crate::value::{RationalValue, Sign, Value};
+
+    use super::ModularValue;
+
+    #[test]
+    fn residues() {
+        let residue = |value: i32| ModularValue::new(value, 7_u32).unwrap();
+        let integer = |value: i32| RationalValue::from(BigInt::from(value));
+        assert_eq!(residue(-2).to_string(), "5 \\pmod{7}");
+        assert_eq!(residue(5).add(&residue(4)).to_string(), "2 \\pmod{7}");
+        assert_eq!(residue(2).sub(&residue(5)).to_string(), "4 \\pmod{7}");
+        assert_eq!(residue(3).mul(&residue(5)).to_string(), "1 \\pmod{7}");
+        assert_eq!(residue(3).get_inverse().unwrap().to_string(), "5 \\pmod{7}");
+        assert_eq!(residue(1).div(&residue(3)).to_string(), "5 \\pmod{7}");
+
+        // Integers take part in the same class, but fractions don't
+        assert_eq!(integer(10).sub(&residue(1)).to_string(), "2 \\pmod{7}");
+        assert_eq!(residue(4).mul(&integer(-1)).to_string(), "3 \\pmod{7}");
+        assert_eq!(residue(12).cmp(&integer(5)), Some(Ordering::Equal));
+        assert_eq!(residue(1).cmp(&residue(2)), None);
+        assert_eq!(
+            residue(1)
+                .add(&RationalValue::new(Sign::Positive, 1_u32, 2_u32))
+                .to_string(),
+            "undefined"
+        );
+
+        // Classes that share a factor with the modulus have no inverse
+        let even = ModularValue::new(4, 10_u32).unwrap();
+        assert!(even.get_inverse().is_none());
+        assert_eq!(
+            ModularValue::new(1, 10_u32).unwrap().div(&even).to_string(),
+            "undefined"
+        );
+        assert_eq!(residue(1).add(&even).to_string(), "undefined");
+        assert_eq!(
+            ModularValue::new(3, 1_u32).unwrap().to_string(),
+            "0 \\pmod{1}"
+        );
+
+        // There are no residues modulo zero
+        assert!(ModularValue::new(3, 0_u32).is_none());
+    }
+}
@@ -0,0 +1,286 @@
+use std::cmp::Ordering;
+
+use crate::format::FormatOptions;
+
+use super::{rational::rational, RationalValue, UndefinedValue, Value};
+
+/// A polynomial in one variable with exact rational coefficients, like `x^{2} - 2x + 1/2`
+#[derive(Debug, Clone)]
+pub struct PolynomialValue {
+    variable: String,
+    /// The coefficients from the constant term up, without trailing zeros
+    coefficients: Vec<RationalValue>,
+}
+
+impl PolynomialValue {
+    /// Construct a new polynomial from its coefficients, starting with the constant term
+    pub fn new(variable: impl Into<String>, coefficients: Vec<RationalValue>) -> Self {
+        let mut coefficients = coefficients;
+        while coefficients.last().is_some_and(RationalValue::is_zero) {
+            coefficients.pop();
+        }
+        Self {
+            variable: variable.into(),
+            coefficients,
+        }
+    }
+
+    /// Construct a new polynomial that is only a constant
+    pub fn constant(variable: impl Into<String>, value: RationalValue) -> Self {
+        Self::new(variable, vec![value])
+    }
+
+    /// Construct the polynomial that is only its variable
+    pub fn variable(variable: impl Into<String>) -> Self {
+        Self::new(variable, vec![RationalValue::zero(), RationalValue::one()])
+    }
+
+    pub fn get_variable(&self) -> &str {
+        &self.variable
+    }
+
+    pub fn get_coefficients(&self) -> &[RationalValue] {
+        &self.coefficients
+    }
+
+    /// Get the degree of this polynomial, or `None` if it is zero
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+
+    /// Get the value of this polynomial with its variable set to a value
+    pub fn evaluate_at(&self, value: &RationalValue) -> RationalValue {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(RationalValue::zero(), |sum, coefficient| {
+                rational(rational(sum.mul(value)).add(coefficient))
+            })
+    }
+
+    /// Raise this polynomial to a natural power
+    pub fn pow(&self, exponent: u32) -> Self {
+        let one = Self::constant(self.variable.clone(), RationalValue::one());
+        (0..exponent).fold(one, |power, _| power.product(self))
+    }
+
+    /// Divide this polynomial by another, getting the quotient and the remainder
+    ///
+    /// Returns `None` if the divisor is zero or in a different variable
+    pub fn div_rem(&self, divisor: &PolynomialValue) -> Option<(Self, Self)> {
+        if divisor.variable != self.variable {
+            return None;
+        }
+        let divisor_degree = divisor.degree()?;
+        let leading = &divisor.coefficients[divisor_degree];
+
+        // Cancel the leading term of the remainder until its degree is below the divisor's
+        let mut quotient = vec![RationalValue::zero(); self.coefficients.len()];
+        let mut remainder = self.coefficients.clone();
+        while remainder.len() > divisor_degree {
+            let shift = remainder.len() - 1 - divisor_degree;
+            let factor = rational(remainder[remainder.len() - 1].div(leading));
+            for (index, coefficient) in divisor.coefficients.iter().enumerate() {
+                let term = factor.mul(coefficient);
+                remainder[shift + index] = rational(remainder[shift + index].sub(term.as_ref()));
+            }
+            quotient[shift] = factor;
+            remainder.pop();
+        }
+        Some((
+            Self::new(self.variable.clone(), quotient),
+            Self::new(self.variable.clone(), remainder),
+        ))
+    }
+
+    /// Get a value as a polynomial in the same variable, if it is a rational value or such a
+    /// polynomial
+    fn matching(&self, value: &dyn Value) -> Option<Self> {
+        if let Some(value) = value.downcast_ref::<PolynomialValue>() {
+            (value.variable == self.variable).then(|| value.clone())
+        } else {
+            value
+                .downcast_ref::<RationalValue>()
+                .map(|value| Self::constant(self.variable.clone(), value.clone()))
+        }
+    }
+
+    /// Combine two polynomials coefficient by coefficient
+    fn termwise(
+        &self,
+        other: &PolynomialValue,
+        operation: impl Fn(&RationalValue, &RationalValue) -> Box<dyn Value>,
+    ) -> Self {
+        let zero = RationalValue::zero();
+        let length = self.coefficients.len().max(other.coefficients.len());
+        Self::new(
+            self.variable.clone(),
+            (0..length)
+                .map(|index| {
+                    let lhs = self.coefficients.get(index).unwrap_or(&zero);
+                    let rhs = other.coefficients.get(index).unwrap_or(&zero);
+                    rational(operation(lhs, rhs))
+                })
+                .collect(),
+        )
+    }
+
+    /// Multiply this polynomial by another
+    fn product(&self, other: &PolynomialValue) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::new(self.variable.clone(), Vec::new());
+        }
+        let mut coefficients =
+            vec![RationalValue::zero(); self.coefficients.len() + other.coefficients.len() - 1];
+        for (i, lhs) in self.coefficients.iter().enumerate() {
+            for (j, rhs) in other.coefficients.iter().enumerate() {
+                coefficients[i + j] = rational(coefficients[i + j].add(lhs.mul(rhs).as_ref()));
+            }
+        }
+        Self::new(self.variable.clone(), coefficients)
+    }
+}
+
+impl Value for PolynomialValue {
+    fn add(&self, other: &dyn Value) -> Box<dyn Value> {
+        match self.matching(other) {
+            Some(other) => Box::new(self.termwise(&other, |lhs, rhs| lhs.add(rhs))),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn sub(&self, other: &dyn Value) -> Box<dyn Value> {
+        match self.matching(other) {
+            Some(other) => Box::new(self.termwise(&other, |lhs, rhs| lhs.sub(rhs))),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn mul(&self, other: &dyn Value) -> Box<dyn Value> {
+        match self.matching(other) {
+            Some(other) => Box::new(self.product(&other)),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn div(&self, other: &dyn Value) -> Box<dyn Value> {
+        // Only divisions without a remainder stay polynomials
+        match self.matching(other).and_then(|other| self.div_rem(&other)) {
+            Some((quotient, remainder)) if remainder.is_zero() => Box::new(quotient),
+            _ => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn cmp(&self, other: &dyn Value) -> Option<Ordering> {
+        // Polynomials only compare everywhere the same way if they differ by a constant
+        let difference = self.termwise(&self.matching(other)?, |lhs, rhs| lhs.sub(rhs));
+        match difference.degree() {
+            None => Some(Ordering::Equal),
+            Some(0) => difference.coefficients[0].cmp(&RationalValue::zero()),
+            Some(_) => None,
+        }
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        let mut terms = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, coefficient)| !coefficient.is_zero())
+            .map(|(degree, coefficient)| {
+                let power = match degree {
+                    0 => String::new(),
+                    1 => self.variable.clone(),
+                    _ => format!("{}^{{{}}}", self.variable, degree),
+                };
+                let magnitude = coefficient.get_absolute();
+                let multiple = if degree > 0
+                    && magnitude.cmp(&RationalValue::one()) == Some(Ordering::Equal)
+                {
+                    power
+                } else {
+                    format!("{}{}", magnitude.format(options), power)
+                };
+                (coefficient.is_negative(), multiple)
+            });
+        let Some((negative, first)) = terms.next() else {
+            return RationalValue::zero().format(options);
+        };
+        let mut written = if negative {
+            format!("-{}", first)
+        } else {
+            first
+        };
+        let mut compound = false;
+        for (negative, term) in terms {
+            let operator = if negative { '-' } else { '+' };
+            written = format!("{} {} {}", written, operator, term);
+            compound = true;
+        }
+        if compound {
+            format!("({})", written)
+        } else {
+            written
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use num::BigInt;
+
+    use crate::value::{RationalValue, Sign, Value};
+
+    use super::PolynomialValue;
+
+    #[test]
+    fn polynomials() {
+        let integer = |value: i32| RationalValue::from(BigInt::from(value));
+        let polynomial = |coefficients: &[i32]| {
+            PolynomialValue::new("x", coefficients.iter().map(|&c| integer(c)).collect())
+        };
+        let x = PolynomialValue::variable("x");
+        let square = x.sub(&integer(1)).mul(x.add(&integer(1)).as_ref());
+        assert_eq!(square.to_string(), "(x^{2} - 1)");
+        assert_eq!(polynomial(&[1, -2, 0, 0]).to_string(), "(-2x + 1)");
+        assert_eq!(polynomial(&[0, 0]).to_string(), "0");
+        assert_eq!(
+            x.mul(&RationalValue::new(Sign::Negative, 1_u32, 2_u32))
+                .to_string(),
+            "-1/2x"
+        );
+        assert_eq!(x.pow(3).sub(&x).to_string(), "(x^{3} - x)");
+        assert_eq!(polynomial(&[0, 0, 5]).degree(), Some(2));
+        assert_eq!(polynomial(&[]).degree(), None);
+        assert_eq!(
+            polynomial(&[1, 2, 3]).evaluate_at(&integer(2)).to_string(),
+            "17"
+        );
+
+        // Division keeps the remainder, and exact division stays a polynomial
+        let (quotient, remainder) = polynomial(&[1, 0, 2, 1])
+            .div_rem(&polynomial(&[1, 1]))
+            .unwrap();
+        assert_eq!(quotient.to_string(), "(x^{2} + x - 1)");
+        assert_eq!(remainder.to_string(), "2");
+        assert_eq!(square.div(&polynomial(&[-1, 1])).to_string(), "(x + 1)");
+        assert_eq!(square.div(&x).to_string(), "undefined");
+        assert!(x.div_rem(&polynomial(&[])).is_none());
+
+        // Polynomials only compare when they differ by a constant
+        assert_eq!(square.cmp(&x.pow(2)), Some(Ordering::Less));
+        assert_eq!(polynomial(&[3]).cmp(&integer(3)), Some(Ordering::Equal));
+        assert_eq!(square.cmp(&x), None);
+        assert_eq!(
+            x.add(&PolynomialValue::variable("y")).to_string(),
+            "undefined"
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use std::{cmp::Ordering, fmt::Display};
+
+use crate::format::FormatOptions;
+
+use super::{UndefinedValue, Value};
+
+/// The symbols of the SI base units, in the order their exponents are stored
+const BASE_UNITS: [&str; 7] = ["kg", "m", "s", "A", "K", "mol", "cd"];
+
+/// A unit, stored as the exponents of the SI base units
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unit {
+    exponents: [i8; 7],
+}
+
+impl Unit {
+    /// Construct a unit from the exponents of kilograms, metres, seconds, amperes, kelvin, moles, and candelas
+    pub fn new(exponents: [i8; 7]) -> Self {
+        Self { exponents }
+    }
+
+    /// Construct the unit of a dimensionless quantity
+    pub fn dimensionless() -> Self {
+        Self::new([0; 7])
+    }
+
+    pub fn get_exponents(&self) -> &[i8; 7] {
+        &self.exponents
+    }
+
+    /// Check if this unit has no dimensions
+    pub fn is_dimensionless(&self) -> bool {
+        self.exponents.iter().all(|exponent| *exponent == 0)
+    }
+
+    /// Get the unit of the product of quantities in this unit and another
+    ///
+    /// Returns `None` if an exponent overflows
+    pub fn mul(&self, other: &Unit) -> Option<Unit> {
+        self.combine(other, i8::checked_add)
+    }
+
+    /// Get the unit of the quotient of quantities in this unit and another
+    ///
+    /// Returns `None` if an exponent overflows
+    pub fn div(&self, other: &Unit) -> Option<Unit> {
+        self.combine(other, i8::checked_sub)
+    }
+
+    /// Combine the exponents of this unit with another unit
+    fn combine(&self, other: &Unit, operation: fn(i8, i8) -> Option<i8>) -> Option<Unit> {
+        let mut exponents = self.exponents;
+        for (exponent, other) in exponents.iter_mut().zip(other.exponents) {
+            *exponent = operation(*exponent, other)?;
+        }
+        Some(Unit::new(exponents))
+    }
+}
+
+impl Display for Unit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbols = BASE_UNITS
+            .iter()
+            .zip(self.exponents)
+            .filter(|(_, exponent)| *exponent != 0)
+            .map(|(symbol, exponent)| match exponent {
+                1 => format!("\\mathrm{{{}}}", symbol),
+                exponent => format!("\\mathrm{{{}}}^{{{}}}", symbol, exponent),
+            })
+            .collect::<Vec<_>>();
+        write!(f, "{}", symbols.join("\\,"))
+    }
+}
+
+/// A value tagged with a unit
+#[derive(Debug, Clone)]
+pub struct QuantityValue {
+    magnitude: Box<dyn Value>,
+    unit: Unit,
+}
+
+impl QuantityValue {
+    pub fn new(magnitude: Box<dyn Value>, unit: Unit) -> Self {
+        Self { magnitude, unit }
+    }
+
+    pub fn get_magnitude(&self) -> &dyn Value {
+        self.magnitude.as_ref()
+    }
+
+    pub fn get_unit(&self) -> Unit {
+        self.unit
+    }
+
+    /// Construct a dimensionless quantity
+    pub fn dimensionless(magnitude: Box<dyn Value>) -> Self {
+        Self::new(magnitude, Unit::dimensionless())
+    }
+
+    /// Construct a quantity, dropping the unit when it is dimensionless
+    fn quantity(magnitude: Box<dyn Value>, unit: Unit) -> Box<dyn Value> {
+        if magnitude.is::<UndefinedValue>() || unit.is_dimensionless() {
+            magnitude
+        } else {
+            Box::new(QuantityValue::new(magnitude, unit))
+        }
+    }
+
+    /// View any value as a quantity
+    fn as_quantity(value: &dyn Value) -> QuantityValue {
+        match value.downcast_ref::<QuantityValue>() {
+            Some(value) => value.clone(),
+            None => QuantityValue::dimensionless(dyn_clone::clone_box(value)),
+        }
+    }
+}
+
+impl Value for QuantityValue {
+    fn add(&self, other: &dyn Value) -> Box<dyn Value> {
+        let other = QuantityValue::as_quantity(other);
+        if other.unit == self.unit {
+            QuantityValue::quantity(self.magnitude.add(other.get_magnitude()), self.unit)
+        } else {
+            Box::new(UndefinedValue::new())
+        }
+    }
+
+    fn sub(&self, other: &dyn Value) -> Box<dyn Value> {
+        let other = QuantityValue::as_quantity(other);
+        if other.unit == self.unit {
+            QuantityValue::quantity(self.magnitude.sub(other.get_magnitude()), self.unit)
+        } else {
+            Box::new(UndefinedValue::new())
+        }
+    }
+
+    fn mul(&self, other: &dyn Value) -> Box<dyn Value> {
+        let other = QuantityValue::as_quantity(other);
+        match self.unit.mul(&other.unit) {
+            Some(unit) => QuantityValue::quantity(self.magnitude.mul(other.get_magnitude()), unit),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn div(&self, other: &dyn Value) -> Box<dyn Value> {
+        let other = QuantityValue::as_quantity(other);
+        match self.unit.div(&other.unit) {
+            Some(unit) => QuantityValue::quantity(self.magnitude.div(other.get_magnitude()), unit),
+            None => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn cmp(&self, other: &dyn Value) -> Option<Ordering> {
+        let other = QuantityValue::as_quantity(other);
+        if other.unit == self.unit {
+            self.magnitude.cmp(other.get_magnitude())
+        } else {
+            None
+        }
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        format!("{}\\,{}", self.magnitude.format(options), self.unit)
+    }
+}
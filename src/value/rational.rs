@@ -1,8 +1,17 @@
-use std::{ops::BitXor, str::FromStr, string::ParseError};
+use std::{cmp::Ordering, ops::BitXor, str::FromStr};
 
-use num::{bigint::ParseBigIntError, BigUint, Integer as _};
+use num::{bigint::ParseBigIntError, BigInt, BigUint, Integer as _, One as _, Zero as _};
 
-use super::{UndefinedValue, Value};
+use crate::{
+    format::{FormatOptions, RationalStyle},
+    numeric::rational_digits,
+};
+
+use super::{
+    ComplexValue, ConstantValue, DecimalValue, FloatValue, InfinityValue, IntervalValue,
+    MatrixValue, ModularValue, PolynomialValue, QuantityValue, SurdValue, UndefinedValue, Value,
+    VectorValue,
+};
 
 /// The sign of a rational value
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -42,9 +51,9 @@ impl AsRef<bool> for Sign {
     }
 }
 
-impl Into<bool> for Sign {
-    fn into(self) -> bool {
-        match self {
+impl From<Sign> for bool {
+    fn from(value: Sign) -> Self {
+        match value {
             Sign::Positive => false,
             Sign::Negative => true,
         }
@@ -80,6 +89,16 @@ impl RationalValue {
         }
     }
 
+    /// Construct a rational value equal to zero
+    pub fn zero() -> Self {
+        Self::new(Sign::Positive, 0_u32, 1_u32)
+    }
+
+    /// Construct a rational value equal to one
+    pub fn one() -> Self {
+        Self::new(Sign::Positive, 1_u32, 1_u32)
+    }
+
     /// Get the sign of this rational value
     pub fn get_sign(&self) -> &Sign {
         &self.sign
@@ -95,11 +114,30 @@ impl RationalValue {
         &self.denominator
     }
 
+    /// Check if this rational value is zero
+    pub fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+
+    /// Check if this rational value is an integer
+    pub fn is_integer(&self) -> bool {
+        self.simplified().denominator.is_one()
+    }
+
+    /// Check if this rational value is strictly negative
+    pub fn is_negative(&self) -> bool {
+        self.sign == Sign::Negative && !self.is_zero()
+    }
+
     /// Return the simplified version of this rational value
     pub fn simplified(&self) -> Self {
         let gcd = self.numerator.gcd(&self.denominator);
         Self::new(
-            self.sign,
+            if self.is_zero() {
+                Sign::Positive
+            } else {
+                self.sign
+            },
             self.get_numerator() / &gcd,
             self.get_denominator() / &gcd,
         )
@@ -113,9 +151,93 @@ impl RationalValue {
         )
     }
 
+    /// Get the absolute value of this rational value
+    pub fn get_absolute(&self) -> Self {
+        Self::new(
+            Sign::Positive,
+            self.numerator.clone(),
+            self.denominator.clone(),
+        )
+    }
+
     pub fn get_reciprocal(&self) -> Self {
         Self::new(self.sign, self.denominator.clone(), self.numerator.clone())
     }
+
+    /// Parse an integer written in the given radix
+    pub fn from_str_radix(digits: &str, radix: u32) -> Option<Self> {
+        BigUint::parse_bytes(digits.as_bytes(), radix)
+            .map(|numerator| Self::new(Sign::Positive, numerator, 1_u32))
+    }
+
+    /// Get this rational value as a signed integer, if it is one
+    pub fn to_integer(&self) -> Option<BigInt> {
+        let simplified = self.simplified();
+        if !simplified.denominator.is_one() {
+            return None;
+        }
+        let magnitude = BigInt::from(simplified.numerator);
+        Some(if simplified.sign == Sign::Negative {
+            -magnitude
+        } else {
+            magnitude
+        })
+    }
+
+    /// Get the largest integer not greater than this rational value
+    pub fn floor(&self) -> BigInt {
+        self.signed_numerator()
+            .div_floor(&BigInt::from(self.denominator.clone()))
+    }
+
+    /// Get the smallest integer not less than this rational value
+    pub fn ceil(&self) -> BigInt {
+        self.signed_numerator()
+            .div_ceil(&BigInt::from(self.denominator.clone()))
+    }
+
+    /// Get the numerator of this rational value with its sign
+    fn signed_numerator(&self) -> BigInt {
+        let magnitude = BigInt::from(self.numerator.clone());
+        if self.sign == Sign::Negative {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    /// Raise this rational value to an integer power
+    ///
+    /// Returns `None` if zero is raised to a negative power
+    pub fn pow(&self, exponent: i32) -> Option<Self> {
+        let base = if exponent < 0 {
+            if self.is_zero() {
+                return None;
+            }
+            self.get_reciprocal()
+        } else {
+            self.clone()
+        };
+        let exponent = exponent.unsigned_abs();
+        let sign = if exponent.is_multiple_of(2) {
+            Sign::Positive
+        } else {
+            base.sign
+        };
+        Some(
+            Self::new(
+                sign,
+                base.numerator.pow(exponent),
+                base.denominator.pow(exponent),
+            )
+            .simplified(),
+        )
+    }
+
+    /// Get a floating point approximation of this rational value
+    pub fn approximated(&self) -> FloatValue {
+        FloatValue::new(FloatValue::approximate(self).unwrap_or(f64::NAN))
+    }
 }
 
 impl Value for RationalValue {
@@ -136,6 +258,24 @@ impl Value for RationalValue {
                     .simplified(),
                 )
             }
+        } else if other.is::<IntervalValue>() {
+            IntervalValue::point(self.clone()).add(other)
+        } else if other.is::<FloatValue>() {
+            self.approximated().add(other)
+        } else if other.is::<QuantityValue>() {
+            QuantityValue::dimensionless(Box::new(self.clone())).add(other)
+        } else if other.is::<InfinityValue>() {
+            other.add(self)
+        } else if other.is::<ComplexValue>() {
+            ComplexValue::from(self.clone()).add(other)
+        } else if let Some(decimal) = other.downcast_ref::<DecimalValue>() {
+            DecimalValue::from_rational(self, decimal.get_precision()).add(other)
+        } else if let Some(surd) = other.downcast_ref::<SurdValue>() {
+            SurdValue::new(self.clone(), Self::zero(), surd.get_radicand().clone()).add(other)
+        } else if let Some(polynomial) = other.downcast_ref::<PolynomialValue>() {
+            PolynomialValue::constant(polynomial.get_variable(), self.clone()).add(other)
+        } else if let Some(modular) = other.downcast_ref::<ModularValue>() {
+            modular.residue(self).add(other)
         } else {
             Box::new(UndefinedValue::new())
         }
@@ -147,7 +287,8 @@ impl Value for RationalValue {
                 self.add(&other.get_opposite())
             } else if *self.get_sign() == Sign::Negative {
                 if let Some(sum) = self
-                    .add(&other.get_opposite())
+                    .get_opposite()
+                    .add(other)
                     .downcast_ref::<RationalValue>()
                 {
                     Box::new(sum.get_opposite())
@@ -155,16 +296,39 @@ impl Value for RationalValue {
                     panic!("Unexpected error: adding two rational values didn't yield a rational value!")
                 }
             } else {
-                Box::new(
-                    RationalValue::new(
-                        Sign::Positive,
-                        self.get_numerator() * other.get_denominator()
-                            - other.get_numerator() * self.get_denominator(),
-                        self.get_denominator() * other.get_denominator(),
+                let lhs = self.get_numerator() * other.get_denominator();
+                let rhs = other.get_numerator() * self.get_denominator();
+                let denominator = self.get_denominator() * other.get_denominator();
+
+                // Keep the magnitude non-negative
+                if lhs >= rhs {
+                    Box::new(
+                        RationalValue::new(Sign::Positive, lhs - rhs, denominator).simplified(),
                     )
-                    .simplified(),
-                )
+                } else {
+                    Box::new(
+                        RationalValue::new(Sign::Negative, rhs - lhs, denominator).simplified(),
+                    )
+                }
             }
+        } else if other.is::<IntervalValue>() {
+            IntervalValue::point(self.clone()).sub(other)
+        } else if other.is::<FloatValue>() {
+            self.approximated().sub(other)
+        } else if other.is::<QuantityValue>() {
+            QuantityValue::dimensionless(Box::new(self.clone())).sub(other)
+        } else if let Some(other) = other.downcast_ref::<InfinityValue>() {
+            other.get_opposite().add(self)
+        } else if other.is::<ComplexValue>() {
+            ComplexValue::from(self.clone()).sub(other)
+        } else if let Some(decimal) = other.downcast_ref::<DecimalValue>() {
+            DecimalValue::from_rational(self, decimal.get_precision()).sub(other)
+        } else if let Some(surd) = other.downcast_ref::<SurdValue>() {
+            SurdValue::new(self.clone(), Self::zero(), surd.get_radicand().clone()).sub(other)
+        } else if let Some(polynomial) = other.downcast_ref::<PolynomialValue>() {
+            PolynomialValue::constant(polynomial.get_variable(), self.clone()).sub(other)
+        } else if let Some(modular) = other.downcast_ref::<ModularValue>() {
+            modular.residue(self).sub(other)
         } else {
             Box::new(UndefinedValue::new())
         }
@@ -172,11 +336,36 @@ impl Value for RationalValue {
 
     fn mul(&self, other: &dyn Value) -> Box<dyn Value> {
         if let Some(other) = other.downcast_ref::<RationalValue>() {
-            Box::new(RationalValue::new(
-                *self.get_sign() ^ *other.get_sign(),
-                self.get_numerator() * other.get_numerator(),
-                self.get_denominator() * other.get_denominator(),
-            ))
+            Box::new(
+                RationalValue::new(
+                    *self.get_sign() ^ *other.get_sign(),
+                    self.get_numerator() * other.get_numerator(),
+                    self.get_denominator() * other.get_denominator(),
+                )
+                .simplified(),
+            )
+        } else if other.is::<IntervalValue>() {
+            IntervalValue::point(self.clone()).mul(other)
+        } else if other.is::<FloatValue>() {
+            self.approximated().mul(other)
+        } else if other.is::<QuantityValue>() {
+            QuantityValue::dimensionless(Box::new(self.clone())).mul(other)
+        } else if other.is::<ConstantValue>()
+            || other.is::<InfinityValue>()
+            || other.is::<MatrixValue>()
+            || other.is::<VectorValue>()
+        {
+            other.mul(self)
+        } else if other.is::<ComplexValue>() {
+            ComplexValue::from(self.clone()).mul(other)
+        } else if let Some(decimal) = other.downcast_ref::<DecimalValue>() {
+            DecimalValue::from_rational(self, decimal.get_precision()).mul(other)
+        } else if let Some(surd) = other.downcast_ref::<SurdValue>() {
+            SurdValue::new(self.clone(), Self::zero(), surd.get_radicand().clone()).mul(other)
+        } else if let Some(polynomial) = other.downcast_ref::<PolynomialValue>() {
+            PolynomialValue::constant(polynomial.get_variable(), self.clone()).mul(other)
+        } else if let Some(modular) = other.downcast_ref::<ModularValue>() {
+            modular.residue(self).mul(other)
         } else {
             Box::new(UndefinedValue::new())
         }
@@ -184,63 +373,132 @@ impl Value for RationalValue {
 
     fn div(&self, other: &dyn Value) -> Box<dyn Value> {
         if let Some(other) = other.downcast_ref::<RationalValue>() {
+            if other.is_zero() {
+                return Box::new(UndefinedValue::new());
+            }
             self.mul(&other.get_reciprocal())
+        } else if other.is::<IntervalValue>() {
+            IntervalValue::point(self.clone()).div(other)
+        } else if other.is::<FloatValue>() {
+            self.approximated().div(other)
+        } else if other.is::<QuantityValue>() {
+            QuantityValue::dimensionless(Box::new(self.clone())).div(other)
+        } else if other.is::<InfinityValue>() {
+            Box::new(RationalValue::zero())
+        } else if other.is::<ComplexValue>() {
+            ComplexValue::from(self.clone()).div(other)
+        } else if let Some(decimal) = other.downcast_ref::<DecimalValue>() {
+            DecimalValue::from_rational(self, decimal.get_precision()).div(other)
+        } else if let Some(surd) = other.downcast_ref::<SurdValue>() {
+            SurdValue::new(self.clone(), Self::zero(), surd.get_radicand().clone()).div(other)
+        } else if let Some(polynomial) = other.downcast_ref::<PolynomialValue>() {
+            PolynomialValue::constant(polynomial.get_variable(), self.clone()).div(other)
+        } else if let Some(modular) = other.downcast_ref::<ModularValue>() {
+            modular.residue(self).div(other)
         } else {
             Box::new(UndefinedValue::new())
         }
     }
 
-    fn cmp(&self, other: &dyn Value) -> Option<std::cmp::Ordering> {
-        if let Some(other) = other.downcast_ref::<RationalValue>() {
-            Some(
-                (self.get_numerator() * other.get_denominator())
-                    .cmp(&(other.get_numerator() * self.get_denominator())),
-            )
-        } else {
-            None
+    fn cmp(&self, other: &dyn Value) -> Option<Ordering> {
+        if other.is::<InfinityValue>()
+            || other.is::<ComplexValue>()
+            || other.is::<DecimalValue>()
+            || other.is::<SurdValue>()
+            || other.is::<PolynomialValue>()
+            || other.is::<ModularValue>()
+        {
+            return other.cmp(self).map(Ordering::reverse);
+        } else if other.is::<IntervalValue>() {
+            return IntervalValue::point(self.clone()).cmp(other);
+        } else if other.is::<FloatValue>() || other.is::<ConstantValue>() {
+            return self.approximated().cmp(other);
+        } else if other.is::<QuantityValue>() {
+            return QuantityValue::dimensionless(Box::new(self.clone())).cmp(other);
         }
+        other.downcast_ref::<RationalValue>().map(|other| {
+            // Compare the signs first, treating zero as positive
+            match (self.is_negative(), other.is_negative()) {
+                (false, true) => Ordering::Greater,
+                (true, false) => Ordering::Less,
+                (negative, _) => {
+                    let magnitude = (self.get_numerator() * other.get_denominator())
+                        .cmp(&(other.get_numerator() * self.get_denominator()));
+                    if negative {
+                        magnitude.reverse()
+                    } else {
+                        magnitude
+                    }
+                }
+            }
+        })
     }
 
-    fn to_string(&self) -> String {
-        if self.denominator == BigUint::from(1u32) {
-            format!(
-                "{}{}",
-                if self.sign.into() { "-" } else { "" },
-                self.numerator
-            )
-        } else {
-            format!(
-                "{}{}/{}",
-                if self.sign.into() { "-" } else { "" },
-                self.numerator,
-                self.denominator
-            )
+    fn format(&self, options: &FormatOptions) -> String {
+        let negative: bool = self.sign.into();
+        match options.get_rational_style() {
+            RationalStyle::Decimal => {
+                // Decimals that don't terminate within the decimal places are cut off
+                let magnitude = Self::new(
+                    Sign::Positive,
+                    self.numerator.clone(),
+                    self.denominator.clone(),
+                );
+                let digits = rational_digits(&magnitude, options.get_decimal_places());
+                let (integer, fraction) = digits.split_once('.').unwrap_or((&digits, ""));
+                options.decimal(
+                    negative && !self.is_zero(),
+                    integer,
+                    fraction.trim_end_matches('0'),
+                )
+            }
+            RationalStyle::Fraction => {
+                let sign = if negative { "-" } else { "" };
+                let numerator = options.decimal(false, &self.numerator.to_string(), "");
+                if self.denominator.is_one() {
+                    format!("{}{}", sign, numerator)
+                } else {
+                    let denominator = options.decimal(false, &self.denominator.to_string(), "");
+                    format!("{}{}", sign, options.fraction(&numerator, &denominator))
+                }
+            }
         }
     }
 }
 
+impl From<BigInt> for RationalValue {
+    fn from(value: BigInt) -> Self {
+        let (sign, magnitude) = value.into_parts();
+        Self::new((sign == num::bigint::Sign::Minus).into(), magnitude, 1_u32)
+    }
+}
+
+/// Get the rational result of an operation on two rational values
+pub(super) fn rational(value: Box<dyn Value>) -> RationalValue {
+    value
+        .downcast_ref::<RationalValue>()
+        .expect("Unexpected error: an operation on rational values didn't yield a rational value!")
+        .clone()
+}
+
 impl FromStr for RationalValue {
     type Err = ParseBigIntError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Some(s) = s.strip_prefix('-') {
             if let Some((before, after)) = s.split_once(".") {
-                let combined = format!("{}{}", before, after);
-                let combined = combined.trim_end_matches('0');
-                let numerator = BigUint::from_str(combined)?;
-                let denominator = BigUint::from(after.len());
-                Ok(Self::new(Sign::Negative, numerator, denominator))
+                let numerator = BigUint::from_str(&format!("{}{}", before, after))?;
+                let denominator = BigUint::from(10_u32).pow(after.len() as u32);
+                Ok(Self::new(Sign::Negative, numerator, denominator).simplified())
             } else {
                 let numerator = BigUint::from_str(s)?;
                 Ok(Self::new(Sign::Negative, numerator, BigUint::from(1u32)))
             }
         } else {
             if let Some((before, after)) = s.split_once(".") {
-                let combined = format!("{}{}", before, after);
-                let combined = combined.trim_end_matches('0');
-                let numerator = BigUint::from_str(combined)?;
-                let denominator = BigUint::from(after.len());
-                Ok(Self::new(Sign::Positive, numerator, denominator))
+                let numerator = BigUint::from_str(&format!("{}{}", before, after))?;
+                let denominator = BigUint::from(10_u32).pow(after.len() as u32);
+                Ok(Self::new(Sign::Positive, numerator, denominator).simplified())
             } else {
                 let numerator = BigUint::from_str(s)?;
                 Ok(Self::new(Sign::Positive, numerator, BigUint::from(1u32)))
@@ -265,4 +523,13 @@ mod tests {
             Some(std::cmp::Ordering::Equal)
         );
     }
+
+    #[test]
+    fn pow() {
+        let value = RationalValue::new(Sign::Negative, 2_u32, 3_u32);
+        assert_eq!(value.pow(3).unwrap().to_string(), "-8/27");
+        assert_eq!(value.pow(-2).unwrap().to_string(), "9/4");
+        assert_eq!(value.pow(0).unwrap().to_string(), "1");
+        assert!(RationalValue::zero().pow(-1).is_none());
+    }
 }
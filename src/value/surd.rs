@@ -0,0 +1,308 @@
+use std::cmp::Ordering;
+
+use num::{BigUint, One as _};
+
+use crate::{format::FormatOptions, numeric::extract_nth_power};
+
+use super::{rational::rational, FloatValue, RationalValue, Sign, UndefinedValue, Value};
+
+/// An exact quadratic surd `a + b\sqrt{c}`, with rational `a` and `b` and a square free integer `c`
+#[derive(Debug, Clone)]
+pub struct SurdValue {
+    rational: RationalValue,
+    coefficient: RationalValue,
+    radicand: BigUint,
+}
+
+impl SurdValue {
+    pub fn new(rational: RationalValue, coefficient: RationalValue, radicand: BigUint) -> Self {
+        Self {
+            rational,
+            coefficient,
+            radicand,
+        }
+    }
+
+    /// Take the square root of a rational value, which is rational if it is a perfect square
+    ///
+    /// Returns `None` if the root isn't real
+    pub fn sqrt(value: &RationalValue) -> Option<Box<dyn Value>> {
+        if value.is_negative() {
+            return None;
+        }
+
+        // Move the denominator under the root, so only an integer is left there
+        let value = value.simplified();
+        let denominator = value.get_denominator();
+        let (outside, inside) = extract_nth_power(&(value.get_numerator() * denominator), 2);
+        let coefficient = RationalValue::new(Sign::Positive, outside, denominator.clone());
+        Some(Self::new(RationalValue::zero(), coefficient.simplified(), inside).normalized())
+    }
+
+    pub fn get_rational(&self) -> &RationalValue {
+        &self.rational
+    }
+
+    pub fn get_coefficient(&self) -> &RationalValue {
+        &self.coefficient
+    }
+
+    pub fn get_radicand(&self) -> &BigUint {
+        &self.radicand
+    }
+
+    /// Get the conjugate of this value, `a - b\sqrt{c}`
+    pub fn get_conjugate(&self) -> Self {
+        Self::new(
+            self.rational.clone(),
+            self.coefficient.get_opposite(),
+            self.radicand.clone(),
+        )
+    }
+
+    /// Get a floating point approximation of this value
+    pub fn approximated(&self) -> FloatValue {
+        let radicand = FloatValue::approximate(&RationalValue::new(
+            Sign::Positive,
+            self.radicand.clone(),
+            1_u32,
+        ))
+        .unwrap_or(f64::NAN);
+        FloatValue::new(
+            FloatValue::approximate(&self.rational).unwrap_or(f64::NAN)
+                + FloatValue::approximate(&self.coefficient).unwrap_or(f64::NAN) * radicand.sqrt(),
+        )
+    }
+
+    /// Get a value as a surd with the same radicand, if it is a rational value or such a surd
+    fn matching(&self, value: &dyn Value) -> Option<Self> {
+        if let Some(value) = value.downcast_ref::<SurdValue>() {
+            (value.radicand == self.radicand).then(|| value.clone())
+        } else {
+            value
+                .downcast_ref::<RationalValue>()
+                .map(|value| Self::new(value.clone(), RationalValue::zero(), self.radicand.clone()))
+        }
+    }
+
+    /// Box this value, collapsing it to a rational value if there is no root left
+    fn normalized(self) -> Box<dyn Value> {
+        if self.coefficient.is_zero() || self.radicand.is_one() {
+            let root = RationalValue::new(Sign::Positive, self.radicand, 1_u32);
+            self.rational.add(self.coefficient.mul(&root).as_ref())
+        } else {
+            Box::new(self)
+        }
+    }
+
+    /// Get `a^2 - b^2 c`, the product of this value and its conjugate
+    fn norm(&self) -> RationalValue {
+        let radicand = RationalValue::new(Sign::Positive, self.radicand.clone(), 1_u32);
+        let square = rational(self.coefficient.mul(&self.coefficient));
+        rational(
+            self.rational
+                .mul(&self.rational)
+                .sub(square.mul(&radicand).as_ref()),
+        )
+    }
+
+    /// Compare this value to zero
+    fn sign(&self) -> Option<Ordering> {
+        let zero = RationalValue::zero();
+        let rational = self.rational.cmp(&zero)?;
+        let coefficient = self.coefficient.cmp(&zero)?;
+        if rational == coefficient || coefficient == Ordering::Equal {
+            return Some(rational);
+        }
+        if rational == Ordering::Equal {
+            return Some(coefficient);
+        }
+
+        // The parts have opposite signs, so the larger square wins
+        let norm = self.norm().cmp(&zero)?;
+        Some(match norm {
+            Ordering::Greater => rational,
+            Ordering::Less => coefficient,
+            Ordering::Equal => Ordering::Equal,
+        })
+    }
+}
+
+impl Value for SurdValue {
+    fn add(&self, other: &dyn Value) -> Box<dyn Value> {
+        let Some(other) = self.matching(other) else {
+            return self.approximated().add(other);
+        };
+        Self::new(
+            rational(self.rational.add(&other.rational)),
+            rational(self.coefficient.add(&other.coefficient)),
+            self.radicand.clone(),
+        )
+        .normalized()
+    }
+
+    fn sub(&self, other: &dyn Value) -> Box<dyn Value> {
+        let Some(other) = self.matching(other) else {
+            return self.approximated().sub(other);
+        };
+        Self::new(
+            rational(self.rational.sub(&other.rational)),
+            rational(self.coefficient.sub(&other.coefficient)),
+            self.radicand.clone(),
+        )
+        .normalized()
+    }
+
+    fn mul(&self, other: &dyn Value) -> Box<dyn Value> {
+        let Some(other) = self.matching(other) else {
+            return self.approximated().mul(other);
+        };
+
+        // (a + b\sqrt{c})(d + e\sqrt{c}) = (ad + bec) + (ae + bd)\sqrt{c}
+        let radicand = RationalValue::new(Sign::Positive, self.radicand.clone(), 1_u32);
+        let roots = rational(self.coefficient.mul(&other.coefficient));
+        let rational_part = self
+            .rational
+            .mul(&other.rational)
+            .add(roots.mul(&radicand).as_ref());
+        let coefficient = self
+            .rational
+            .mul(&other.coefficient)
+            .add(self.coefficient.mul(&other.rational).as_ref());
+        Self::new(
+            rational(rational_part),
+            rational(coefficient),
+            self.radicand.clone(),
+        )
+        .normalized()
+    }
+
+    fn div(&self, other: &dyn Value) -> Box<dyn Value> {
+        let Some(other) = self.matching(other) else {
+            return self.approximated().div(other);
+        };
+
+        // Multiply through by the conjugate, so only a rational value is left to divide by
+        let norm = other.norm();
+        if norm.is_zero() {
+            return Box::new(UndefinedValue::new());
+        }
+        let Some(numerator) = self.matching(self.mul(&other.get_conjugate()).as_ref()) else {
+            return Box::new(UndefinedValue::new());
+        };
+        Self::new(
+            rational(numerator.rational.div(&norm)),
+            rational(numerator.coefficient.div(&norm)),
+            self.radicand.clone(),
+        )
+        .normalized()
+    }
+
+    fn cmp(&self, other: &dyn Value) -> Option<Ordering> {
+        match self.matching(other) {
+            Some(other) => Self::new(
+                rational(self.rational.sub(&other.rational)),
+                rational(self.coefficient.sub(&other.coefficient)),
+                self.radicand.clone(),
+            )
+            .sign(),
+            None => self.approximated().cmp(other),
+        }
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        let root = format!("\\sqrt{{{}}}", self.radicand);
+        let multiple = |value: &RationalValue| {
+            if value.cmp(&RationalValue::one()) == Some(Ordering::Equal) {
+                root.clone()
+            } else {
+                format!("{}{}", value.format(options), root)
+            }
+        };
+        if self.rational.is_zero() {
+            return match self.coefficient.get_sign() {
+                Sign::Negative => format!("-{}", multiple(&self.coefficient.get_absolute())),
+                Sign::Positive => multiple(&self.coefficient),
+            };
+        }
+        let operator = match self.coefficient.get_sign() {
+            Sign::Negative => '-',
+            Sign::Positive => '+',
+        };
+        format!(
+            "({} {} {})",
+            self.rational.format(options),
+            operator,
+            multiple(&self.coefficient.get_absolute())
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use num::BigInt;
+
+    use crate::{
+        expression::Bindings,
+        parse::{parse, ParserOptions},
+        value::{FloatValue, RationalValue, Value},
+    };
+
+    use super::SurdValue;
+
+    #[test]
+    fn surds() {
+        let integer = |value: i32| RationalValue::from(BigInt::from(value));
+        let root = |value: i32| SurdValue::sqrt(&integer(value)).unwrap();
+        assert_eq!(root(8).to_string(), "2\\sqrt{2}");
+        assert_eq!(root(9).to_string(), "3");
+        assert_eq!(
+            SurdValue::sqrt(&"0.5".parse().unwrap())
+                .unwrap()
+                .to_string(),
+            "1/2\\sqrt{2}"
+        );
+        assert!(SurdValue::sqrt(&integer(-2)).is_none());
+
+        // Arithmetic with the same radicand stays exact
+        let golden = integer(1).add(root(5).as_ref()).div(&integer(2));
+        assert_eq!(golden.to_string(), "(1/2 + 1/2\\sqrt{5})");
+        assert_eq!(
+            golden.mul(golden.as_ref()).sub(golden.as_ref()).to_string(),
+            "1"
+        );
+        assert_eq!(integer(1).div(root(2).as_ref()).to_string(), "1/2\\sqrt{2}");
+        assert_eq!(root(2).mul(root(2).as_ref()).to_string(), "2");
+        assert_eq!(
+            integer(3)
+                .sub(root(5).as_ref())
+                .div(root(5).sub(&integer(2)).as_ref())
+                .to_string(),
+            "(1 + \\sqrt{5})"
+        );
+
+        // Surds compare exactly, and approximate otherwise
+        assert_eq!(
+            root(2).cmp(&"1.5".parse::<RationalValue>().unwrap()),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            integer(3).sub(root(8).as_ref()).cmp(&RationalValue::zero()),
+            Some(Ordering::Greater)
+        );
+        assert!(root(2).add(root(3).as_ref()).is::<FloatValue>());
+
+        // Square roots of rational values evaluate to surds
+        let options = ParserOptions::default();
+        let value = parse("\\frac{1 + \\sqrt{5}}{2}", &options)
+            .unwrap()
+            .evaluate(&Bindings::new());
+        assert_eq!(value.to_string(), "(1/2 + 1/2\\sqrt{5})");
+        assert!(
+            (FloatValue::approximate(value.as_ref()).unwrap() - 1.618_033_988_749_895).abs()
+                < 1e-15
+        );
+    }
+}
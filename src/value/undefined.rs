@@ -1,6 +1,9 @@
+use crate::format::FormatOptions;
+
 use super::Value;
 
-#[derive(Debug, Clone)]
+/// An undefined value
+#[derive(Debug, Clone, Default)]
 pub struct UndefinedValue;
 
 impl UndefinedValue {
@@ -10,19 +13,19 @@ impl UndefinedValue {
 }
 
 impl Value for UndefinedValue {
-    fn add(&self, _other: &dyn Value) -> Box<(dyn Value + 'static)> {
+    fn add(&self, _other: &dyn Value) -> Box<dyn Value> {
         Box::new(UndefinedValue::new())
     }
 
-    fn sub(&self, _other: &dyn Value) -> Box<(dyn Value + 'static)> {
+    fn sub(&self, _other: &dyn Value) -> Box<dyn Value> {
         Box::new(UndefinedValue::new())
     }
 
-    fn mul(&self, _other: &dyn Value) -> Box<(dyn Value + 'static)> {
+    fn mul(&self, _other: &dyn Value) -> Box<dyn Value> {
         Box::new(UndefinedValue::new())
     }
 
-    fn div(&self, _other: &dyn Value) -> Box<(dyn Value + 'static)> {
+    fn div(&self, _other: &dyn Value) -> Box<dyn Value> {
         Box::new(UndefinedValue::new())
     }
 
@@ -30,7 +33,7 @@ impl Value for UndefinedValue {
         None
     }
 
-    fn to_string(&self) -> String {
+    fn format(&self, _options: &FormatOptions) -> String {
         "undefined".to_string()
     }
 }
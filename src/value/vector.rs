@@ -0,0 +1,212 @@
+use std::cmp::Ordering;
+
+use crate::format::FormatOptions;
+
+use super::{rational::rational, MatrixValue, RationalValue, UndefinedValue, Value};
+
+/// A vector of a fixed number of exact rational components
+#[derive(Debug, Clone)]
+pub struct VectorValue {
+    components: Vec<RationalValue>,
+}
+
+impl VectorValue {
+    pub fn new(components: Vec<RationalValue>) -> Self {
+        Self { components }
+    }
+
+    pub fn get_components(&self) -> &[RationalValue] {
+        &self.components
+    }
+
+    /// Get the number of components of this vector
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Check if this vector has no components
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// Multiply each component of this vector by a rational value
+    pub fn scaled(&self, scalar: &RationalValue) -> Self {
+        Self::new(
+            self.components
+                .iter()
+                .map(|component| rational(component.mul(scalar)))
+                .collect(),
+        )
+    }
+
+    /// Get the dot product of this vector and another, or `None` if their lengths differ
+    pub fn dot(&self, other: &VectorValue) -> Option<RationalValue> {
+        if self.len() != other.len() {
+            return None;
+        }
+        Some(
+            self.components
+                .iter()
+                .zip(&other.components)
+                .fold(RationalValue::zero(), |sum, (lhs, rhs)| {
+                    rational(sum.add(lhs.mul(rhs).as_ref()))
+                }),
+        )
+    }
+
+    /// Multiply this vector as a row by a matrix, or `None` if its length doesn't match the rows of
+    /// the matrix
+    pub fn transformed(&self, matrix: &MatrixValue) -> Option<Self> {
+        let (rows, columns) = matrix.get_shape();
+        if self.len() != rows {
+            return None;
+        }
+        (0..columns)
+            .map(|column| {
+                let column = matrix
+                    .get_rows()
+                    .iter()
+                    .map(|row| row[column].clone())
+                    .collect();
+                self.dot(&Self::new(column))
+            })
+            .collect::<Option<_>>()
+            .map(Self::new)
+    }
+
+    /// Combine two vectors of the same length component by component, or `None` if their lengths
+    /// differ
+    fn componentwise(
+        &self,
+        other: &VectorValue,
+        operation: impl Fn(&RationalValue, &RationalValue) -> Box<dyn Value>,
+    ) -> Option<Self> {
+        if self.len() != other.len() {
+            return None;
+        }
+        Some(Self::new(
+            self.components
+                .iter()
+                .zip(&other.components)
+                .map(|(lhs, rhs)| rational(operation(lhs, rhs)))
+                .collect(),
+        ))
+    }
+}
+
+/// Box a result, which is undefined if there isn't one
+fn defined<T: Value>(value: Option<T>) -> Box<dyn Value> {
+    match value {
+        Some(value) => Box::new(value),
+        None => Box::new(UndefinedValue::new()),
+    }
+}
+
+impl Value for VectorValue {
+    fn add(&self, other: &dyn Value) -> Box<dyn Value> {
+        defined(
+            other
+                .downcast_ref::<VectorValue>()
+                .and_then(|other| self.componentwise(other, |lhs, rhs| lhs.add(rhs))),
+        )
+    }
+
+    fn sub(&self, other: &dyn Value) -> Box<dyn Value> {
+        defined(
+            other
+                .downcast_ref::<VectorValue>()
+                .and_then(|other| self.componentwise(other, |lhs, rhs| lhs.sub(rhs))),
+        )
+    }
+
+    fn mul(&self, other: &dyn Value) -> Box<dyn Value> {
+        if let Some(other) = other.downcast_ref::<VectorValue>() {
+            defined(self.dot(other))
+        } else if let Some(matrix) = other.downcast_ref::<MatrixValue>() {
+            defined(self.transformed(matrix))
+        } else if let Some(scalar) = other.downcast_ref::<RationalValue>() {
+            Box::new(self.scaled(scalar))
+        } else {
+            Box::new(UndefinedValue::new())
+        }
+    }
+
+    fn div(&self, other: &dyn Value) -> Box<dyn Value> {
+        // Only dividing by a scalar is defined
+        match other.downcast_ref::<RationalValue>() {
+            Some(scalar) if !scalar.is_zero() => Box::new(self.scaled(&scalar.get_reciprocal())),
+            _ => Box::new(UndefinedValue::new()),
+        }
+    }
+
+    fn cmp(&self, other: &dyn Value) -> Option<Ordering> {
+        // Vectors aren't ordered, but equal vectors are still equal
+        let other = other.downcast_ref::<VectorValue>()?;
+        let equal = self.len() == other.len()
+            && self
+                .components
+                .iter()
+                .zip(&other.components)
+                .all(|(lhs, rhs)| lhs.cmp(rhs) == Some(Ordering::Equal));
+        equal.then_some(Ordering::Equal)
+    }
+
+    fn format(&self, options: &FormatOptions) -> String {
+        let components = self
+            .components
+            .iter()
+            .map(|component| component.format(options))
+            .collect::<Vec<_>>();
+        format!(
+            "({})",
+            components.join(&format!("{} ", options.get_locale().argument_separator()))
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigInt;
+
+    use crate::{
+        expression::Bindings,
+        parse::{parse, ParserOptions},
+        value::{MatrixValue, RationalValue, Value},
+    };
+
+    use super::VectorValue;
+
+    #[test]
+    fn vectors() {
+        let integer = |value: i32| RationalValue::from(BigInt::from(value));
+        let vector =
+            |components: &[i32]| VectorValue::new(components.iter().map(|&c| integer(c)).collect());
+        let u = vector(&[1, 2, 3]);
+        let v = vector(&[4, -5, 6]);
+        assert_eq!(u.to_string(), "(1, 2, 3)");
+        assert_eq!(u.add(&v).to_string(), "(5, -3, 9)");
+        assert_eq!(u.sub(&v).to_string(), "(-3, 7, -3)");
+        assert_eq!(u.mul(&v).to_string(), "12");
+        assert_eq!(integer(2).mul(&u).to_string(), "(2, 4, 6)");
+        assert_eq!(u.div(&integer(2)).to_string(), "(1/2, 1, 3/2)");
+        assert_eq!(u.add(&vector(&[1, 2])).to_string(), "undefined");
+        assert_eq!(u.mul(&vector(&[1, 2])).to_string(), "undefined");
+
+        // Matrices transform vectors from either side
+        let matrix = MatrixValue::new(vec![
+            vec![integer(1), integer(0), integer(2)],
+            vec![integer(0), integer(1), integer(-1)],
+        ]);
+        assert_eq!(matrix.mul(&u).to_string(), "(7, -1)");
+        assert_eq!(vector(&[1, 1]).mul(&matrix).to_string(), "(1, 1, 1)");
+        assert_eq!(matrix.mul(&vector(&[1, 1])).to_string(), "undefined");
+
+        // Tuples of rational elements evaluate to vectors
+        let options = ParserOptions::default();
+        let value = parse("(1, \\frac{1}{2}, 3)", &options)
+            .unwrap()
+            .evaluate(&Bindings::new());
+        assert_eq!(value.to_string(), "(1, 1/2, 3)");
+        assert_eq!(value.mul(&u).to_string(), "11");
+    }
+}